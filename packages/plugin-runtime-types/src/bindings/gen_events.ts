@@ -29,7 +29,11 @@ export type CallHttpRequestActionArgs = { httpRequest: HttpRequest, };
 
 export type CallHttpRequestActionRequest = { index: number, pluginRefId: string, args: CallHttpRequestActionArgs, };
 
-export type CallTemplateFunctionArgs = { purpose: RenderPurpose, values: { [key in string]?: string }, };
+export type CallTemplateFunctionArgs = { purpose: RenderPurpose, values: { [key in string]?: string }, 
+/**
+ * When set, random output (eg. uuids) should be derived from this seed
+ */
+seed: number | null, };
 
 export type CallTemplateFunctionRequest = { name: string, args: CallTemplateFunctionArgs, };
 
@@ -376,7 +380,7 @@ export type RenderHttpRequestRequest = { httpRequest: HttpRequest, purpose: Rend
 
 export type RenderHttpRequestResponse = { httpRequest: HttpRequest, };
 
-export type RenderPurpose = "send" | "preview";
+export type RenderPurpose = "send" | "preview" | "export";
 
 export type SendHttpRequestRequest = { httpRequest: Partial<HttpRequest>, };
 
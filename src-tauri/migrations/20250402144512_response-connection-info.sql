@@ -0,0 +1,2 @@
+ALTER TABLE http_responses ADD COLUMN local_addr TEXT;
+ALTER TABLE http_responses ADD COLUMN reused_connection BOOLEAN DEFAULT FALSE NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_response_dir TEXT NULL DEFAULT NULL;
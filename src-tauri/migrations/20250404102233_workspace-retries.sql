@@ -0,0 +1,2 @@
+ALTER TABLE workspaces ADD COLUMN setting_max_retries INTEGER DEFAULT 0 NOT NULL;
+ALTER TABLE workspaces ADD COLUMN setting_max_retry_wait INTEGER DEFAULT 30000 NOT NULL;
@@ -0,0 +1,2 @@
+ALTER TABLE workspaces ADD COLUMN setting_request_id_header TEXT NULL DEFAULT NULL;
+ALTER TABLE http_responses ADD COLUMN correlation_id TEXT;
@@ -0,0 +1,2 @@
+ALTER TABLE settings ADD COLUMN dark_appearance_start TEXT NULL DEFAULT NULL;
+ALTER TABLE settings ADD COLUMN dark_appearance_end TEXT NULL DEFAULT NULL;
@@ -0,0 +1 @@
+ALTER TABLE http_responses ADD COLUMN set_cookies TEXT DEFAULT '[]' NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_max_concurrency INTEGER DEFAULT 1 NOT NULL;
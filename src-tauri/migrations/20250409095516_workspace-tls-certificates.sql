@@ -0,0 +1,3 @@
+ALTER TABLE workspaces ADD COLUMN setting_ca_bundle_path TEXT NULL DEFAULT NULL;
+ALTER TABLE workspaces ADD COLUMN setting_client_cert_path TEXT NULL DEFAULT NULL;
+ALTER TABLE workspaces ADD COLUMN setting_client_key_path TEXT NULL DEFAULT NULL;
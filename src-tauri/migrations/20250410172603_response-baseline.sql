@@ -0,0 +1 @@
+ALTER TABLE http_responses ADD COLUMN baseline BOOLEAN DEFAULT FALSE NOT NULL;
@@ -0,0 +1,2 @@
+ALTER TABLE workspaces ADD COLUMN setting_accept_encoding TEXT NULL DEFAULT NULL;
+ALTER TABLE workspaces ADD COLUMN setting_decompress BOOLEAN DEFAULT TRUE NOT NULL;
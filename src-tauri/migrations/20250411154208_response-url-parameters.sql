@@ -0,0 +1 @@
+ALTER TABLE http_responses ADD COLUMN request_url_parameters TEXT DEFAULT '[]' NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_title_case_headers BOOLEAN DEFAULT FALSE NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_redirect_preserve_method BOOLEAN DEFAULT FALSE NOT NULL;
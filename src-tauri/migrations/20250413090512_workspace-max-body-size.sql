@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_max_body_size INTEGER DEFAULT 104857600 NOT NULL;
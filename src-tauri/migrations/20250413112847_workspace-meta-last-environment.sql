@@ -0,0 +1 @@
+ALTER TABLE workspace_metas ADD COLUMN last_environment_id TEXT NULL DEFAULT NULL;
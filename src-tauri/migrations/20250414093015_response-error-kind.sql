@@ -0,0 +1 @@
+ALTER TABLE http_responses ADD COLUMN error_kind TEXT NULL DEFAULT NULL;
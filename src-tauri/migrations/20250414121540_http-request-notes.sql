@@ -0,0 +1 @@
+ALTER TABLE http_requests ADD COLUMN notes TEXT DEFAULT '' NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_redirect_allow_downgrade BOOLEAN DEFAULT FALSE NOT NULL;
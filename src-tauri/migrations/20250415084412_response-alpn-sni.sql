@@ -0,0 +1,2 @@
+ALTER TABLE http_responses ADD COLUMN alpn_protocol TEXT NULL DEFAULT NULL;
+ALTER TABLE http_responses ADD COLUMN sni_hostname TEXT NULL DEFAULT NULL;
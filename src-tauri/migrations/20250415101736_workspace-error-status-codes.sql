@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_error_status_codes TEXT NULL DEFAULT NULL;
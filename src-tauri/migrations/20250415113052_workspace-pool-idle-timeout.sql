@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_pool_idle_timeout INTEGER DEFAULT 90000 NOT NULL;
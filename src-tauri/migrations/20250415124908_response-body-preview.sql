@@ -0,0 +1 @@
+ALTER TABLE http_responses ADD COLUMN body_preview TEXT NULL DEFAULT NULL;
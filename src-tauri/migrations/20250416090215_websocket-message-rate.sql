@@ -0,0 +1 @@
+ALTER TABLE websocket_requests ADD COLUMN max_messages_per_second INTEGER DEFAULT 0 NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE http_requests ADD COLUMN cache_responses BOOLEAN DEFAULT FALSE NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE http_responses ADD COLUMN warnings TEXT DEFAULT '[]' NOT NULL;
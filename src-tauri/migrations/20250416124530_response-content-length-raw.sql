@@ -0,0 +1 @@
+ALTER TABLE http_responses ADD COLUMN content_length_raw INTEGER;
@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_insecure_hosts TEXT NULL DEFAULT NULL;
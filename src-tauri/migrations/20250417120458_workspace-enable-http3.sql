@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_enable_http3 BOOLEAN DEFAULT FALSE NOT NULL;
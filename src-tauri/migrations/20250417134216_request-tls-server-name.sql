@@ -0,0 +1 @@
+ALTER TABLE http_requests ADD COLUMN tls_server_name TEXT NULL DEFAULT NULL;
@@ -0,0 +1 @@
+ALTER TABLE http_responses ADD COLUMN request_cookies TEXT DEFAULT '[]' NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE http_requests ADD COLUMN ca_cert_pem TEXT NULL DEFAULT NULL;
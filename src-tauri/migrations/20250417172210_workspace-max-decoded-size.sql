@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_max_decoded_size INTEGER DEFAULT 1073741824 NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE http_responses ADD COLUMN cookie_jar_id TEXT NULL DEFAULT NULL;
@@ -0,0 +1 @@
+ALTER TABLE http_requests ADD COLUMN timeout_ms INTEGER NULL DEFAULT NULL;
@@ -0,0 +1 @@
+ALTER TABLE environments ADD COLUMN base_url TEXT NULL DEFAULT NULL;
@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_send_accept_encoding BOOLEAN DEFAULT TRUE NOT NULL;
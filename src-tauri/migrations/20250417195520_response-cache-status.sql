@@ -0,0 +1,2 @@
+ALTER TABLE http_responses ADD COLUMN cache_age INTEGER NULL DEFAULT NULL;
+ALTER TABLE http_responses ADD COLUMN from_cache BOOLEAN DEFAULT FALSE NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_add_charset BOOLEAN DEFAULT FALSE NOT NULL;
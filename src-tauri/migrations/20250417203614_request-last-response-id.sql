@@ -0,0 +1 @@
+ALTER TABLE http_requests ADD COLUMN last_response_id TEXT NULL DEFAULT NULL;
@@ -0,0 +1 @@
+ALTER TABLE folders ADD COLUMN default_headers TEXT DEFAULT '[]' NOT NULL;
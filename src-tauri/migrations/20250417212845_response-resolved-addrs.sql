@@ -0,0 +1 @@
+ALTER TABLE http_responses ADD COLUMN resolved_addrs TEXT DEFAULT '[]' NOT NULL;
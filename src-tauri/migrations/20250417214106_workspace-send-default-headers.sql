@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_send_default_headers BOOLEAN DEFAULT TRUE NOT NULL;
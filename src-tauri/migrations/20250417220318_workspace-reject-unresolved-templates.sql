@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_reject_unresolved_templates BOOLEAN DEFAULT FALSE NOT NULL;
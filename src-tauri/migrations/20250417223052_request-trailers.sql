@@ -0,0 +1 @@
+ALTER TABLE http_requests ADD COLUMN trailers TEXT DEFAULT '[]' NOT NULL;
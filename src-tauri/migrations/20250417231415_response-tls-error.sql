@@ -0,0 +1 @@
+ALTER TABLE http_responses ADD COLUMN tls_error TEXT NULL DEFAULT NULL;
@@ -0,0 +1,2 @@
+ALTER TABLE workspaces ADD COLUMN setting_max_header_count INTEGER DEFAULT 0 NOT NULL;
+ALTER TABLE workspaces ADD COLUMN setting_max_header_size INTEGER DEFAULT 0 NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE http_requests ADD COLUMN depends_on TEXT DEFAULT '[]' NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE http_responses DROP COLUMN alpn_protocol;
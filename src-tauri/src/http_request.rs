@@ -1,32 +1,65 @@
 use crate::error::Error::GenericError;
 use crate::error::Result;
-use crate::render::render_http_request;
-use crate::response_err;
-use http::header::{ACCEPT, USER_AGENT};
+use crate::oauth2::{is_pkce_flow, pkce_authorization_header};
+use crate::render::{
+    SecretTracker, render_cookie_jar, render_default_headers, render_http_request,
+    render_proxy_setting, with_variables,
+};
+use crate::{preflight_err, response_err};
+use http::header::{
+    ACCEPT, ACCEPT_ENCODING, AUTHORIZATION, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, COOKIE,
+    HOST, IF_MODIFIED_SINCE, IF_NONE_MATCH, LOCATION, PROXY_AUTHORIZATION, RETRY_AFTER, SET_COOKIE,
+    TRAILER, USER_AGENT,
+};
 use http::{HeaderMap, HeaderName, HeaderValue, Uri};
 use log::{debug, error, warn};
-use mime_guess::Mime;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
 use reqwest::redirect::Policy;
+use reqwest::tls::TlsInfo;
 use reqwest::{multipart, Proxy, Url};
-use reqwest::{Method, Response};
-use rustls::crypto::ring;
-use rustls::ClientConfig;
-use rustls_platform_verifier::BuilderVerifierExt;
+use reqwest::{Method, Response, StatusCode};
+use reqwest_cookie_store::CookieStoreMutex;
+use serde::Serialize;
 use serde_json::Value;
-use std::collections::BTreeMap;
-use std::path::PathBuf;
+use hyper_util::client::legacy::connect::HttpInfo;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
-use tauri::{Manager, Runtime, WebviewWindow};
+use tauri::{Emitter, Manager, Runtime, WebviewWindow};
 use tokio::fs;
 use tokio::fs::{create_dir_all, File};
 use tokio::io::AsyncWriteExt;
 use tokio::sync::watch::Receiver;
 use tokio::sync::{oneshot, Mutex};
+use tokio_util::io::ReaderStream;
+use yaak_common::tls::{CertificateErrors, TlsCertificates, get_tls_config};
+use yaak_http::cache::{cache_age, conditional_headers, is_cacheable, served_from_cache};
+use yaak_http::charset::{content_type_charset, encode_body, with_utf8_charset};
+use yaak_http::decode::BodyDecoder;
+use yaak_http::error::error_chain;
+use yaak_http::graphql::variable_warnings;
+use yaak_http::header_limits::{header_limit_error, with_header_limit_hint};
+use yaak_http::multipart::{directory_files, field_name, part_content_type};
+use yaak_http::ndjson::{NdjsonLine, NdjsonParser, is_ndjson};
+use yaak_http::query::query_param_name;
+use yaak_http::redirect::{MAX_REDIRECTS, is_downgrade, is_redirect_status, redirect_method};
+use yaak_http::retry::{is_retryable_status, retry_wait};
+use yaak_http::secrets::mask_secrets;
+use yaak_http::status::{parse_status_ranges, status_in_ranges};
+use yaak_http::timeout::request_timeout;
+use yaak_http::tls::{insecure_hosts, sni_hostname};
+use yaak_http::trailers::WithTrailers;
+use yaak_http::tunnel::{ConnectTunnel, probe_connect};
+use yaak_http::unresolved::unresolved_template;
+use yaak_http::url::find_url_error;
+use yaak_models::bodies::{BinaryBody, GraphqlBody, HttpRequestBody, TextBody};
 use yaak_models::models::{
-    Cookie, CookieJar, Environment, HttpRequest, HttpResponse, HttpResponseHeader,
-    HttpResponseState, ProxySetting, ProxySettingAuth,
+    Cookie, CookieJar, Environment, HttpRequest, HttpResponse, HttpResponseErrorKind,
+    HttpResponseHeader, HttpResponseState, HttpResponseUrlParameter, ProxySetting,
+    ProxySettingAuth, Settings, Workspace, WorkspaceMeta,
 };
 use yaak_models::query_manager::QueryManagerExt;
 use yaak_models::util::UpdateSource;
@@ -36,12 +69,89 @@ use yaak_plugins::events::{
 use yaak_plugins::manager::PluginManager;
 use yaak_plugins::template_callback::PluginTemplateCallback;
 
+/// Wait between retries of a 429/503 response that didn't include a `Retry-After` header
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Max number of bytes of the body to keep inline on the response
+const BODY_PREVIEW_LEN: usize = 4096;
+
+/// Event emitted as the body of a response downloads
+const PROGRESS_EVENT: &str = "http_response_progress";
+
+/// Minimum time between progress events for a single response
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Event emitted as lines of a newline-delimited JSON body arrive
+const NDJSON_EVENT: &str = "http_response_ndjson";
+
+/// Default limit, in bytes, of the HTTP client on an HTTP/2 response's headers
+const HTTP2_MAX_HEADER_LIST_SIZE: u32 = 16 * 1024;
+
+/// How long an HTTP/3 attempt gets before the request is sent over TCP instead. Servers without
+/// QUIC never answer, so waiting for the QUIC handshake to time out would take 30 seconds.
+const HTTP3_FALLBACK_DELAY: Duration = Duration::from_secs(3);
+
+/// ALPN protocols offered when HTTP/3 is enabled, most preferred first
+const HTTP3_ALPN_PROTOCOLS: [&[u8]; 3] = [b"h3", b"h2", b"http/1.1"];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpResponseProgress {
+    pub response_id: String,
+    /// Bytes of the body received so far, before decoding
+    pub bytes_downloaded: u64,
+    /// Milliseconds since the request was sent
+    pub elapsed: u64,
+}
+
+/// Line of a newline-delimited JSON body
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NdjsonEvent {
+    pub response_id: String,
+    /// 1-based line number within the body
+    pub line: usize,
+    pub data: String,
+    /// Why the line isn't valid JSON, if it isn't
+    pub error: Option<String>,
+}
+
+impl NdjsonEvent {
+    pub fn new(response_id: &str, line: NdjsonLine) -> Self {
+        NdjsonEvent {
+            response_id: response_id.to_string(),
+            line: line.line,
+            data: line.data,
+            error: line.error,
+        }
+    }
+}
+
+/// Optional behavior for a single send
+#[derive(Default)]
+pub struct SendOptions<'a> {
+    /// Client to send with instead of building a new one
+    pub shared_client: Option<&'a SharedClient>,
+    /// Fixed seed for template functions, so random helpers render the same on every send
+    pub seed: Option<u32>,
+    /// Extra template variables, which take precedence over the environment's
+    pub variables: Option<&'a BTreeMap<String, String>>,
+    /// Write the body to this file instead of storing it with the response
+    pub download_path: Option<PathBuf>,
+    /// Stop short of sending, and return the built request's URL and headers on the response. The
+    /// response is only stored if it was already.
+    pub dry_run: bool,
+    /// Leave authentication off, since applying it can call out to a server (eg. for a token)
+    pub skip_auth: bool,
+}
+
 pub async fn send_http_request<R: Runtime>(
     window: &WebviewWindow<R>,
     unrendered_request: &HttpRequest,
     og_response: &HttpResponse,
     environment: Option<Environment>,
     cookie_jar: Option<CookieJar>,
+    options: SendOptions<'_>,
     cancelled_rx: &mut Receiver<bool>,
 ) -> Result<HttpResponse> {
     let app_handle = window.app_handle().clone();
@@ -54,28 +164,73 @@ pub async fn send_http_request<R: Runtime>(
     };
     let base_environment =
         app_handle.db().get_base_environment(&unrendered_request.workspace_id)?;
+    let unrendered_request = &with_auth_profile(window, unrendered_request);
 
     let response_id = og_response.id.clone();
-    let response = Arc::new(Mutex::new(og_response.clone()));
+    let response = Arc::new(Mutex::new(HttpResponse {
+        cookie_jar_id: cookie_jar.as_ref().map(|j| j.id.clone()),
+        ..og_response.clone()
+    }));
 
     let cb = PluginTemplateCallback::new(
         window.app_handle(),
         &WindowContext::from_window(window),
         RenderPurpose::Send,
-    );
+    )
+    .with_seed(options.seed);
     let update_source = UpdateSource::from_window(window);
 
-    let request = match render_http_request(
-        &unrendered_request,
-        &base_environment,
-        environment.as_ref(),
-        &cb,
-    )
-    .await
-    {
+    // Remember the environment, so it can be restored when the workspace is opened again
+    if let Some(e) = environment.as_ref().filter(|_| !options.dry_run) {
+        let db = window.db();
+        let meta = db.get_or_create_workspace_meta(&workspace.id, &update_source)?;
+        if meta.last_environment_id.as_deref() != Some(e.id.as_str()) {
+            let meta = WorkspaceMeta {
+                last_environment_id: Some(e.id.clone()),
+                ..meta
+            };
+            db.upsert_workspace_meta(&meta, &update_source)?;
+        }
+    }
+
+    // Point the request at this response, which is now its latest
+    if !response_id.is_empty() && !options.dry_run {
+        window.db().set_last_response_id(&unrendered_request.id, &response_id)?;
+    }
+
+    let environment = match options.variables {
+        Some(variables) => with_variables(environment.as_ref(), variables),
+        None => environment,
+    };
+    let folder_headers =
+        window.db().folder_default_headers(unrendered_request.folder_id.as_deref())?;
+
+    let secret_tracker = SecretTracker::new(&cb, &base_environment, environment.as_ref());
+    let rendered = async {
+        let r = render_http_request(
+            &unrendered_request,
+            &base_environment,
+            environment.as_ref(),
+            &secret_tracker,
+        )
+        .await?;
+        let folder_headers = render_default_headers(
+            &folder_headers,
+            &base_environment,
+            environment.as_ref(),
+            &secret_tracker,
+        )
+        .await?;
+        // Folder defaults go first, so the request's own headers override them
+        Ok::<_, yaak_templates::error::Error>(HttpRequest {
+            headers: [folder_headers, r.headers.clone()].concat(),
+            ..r
+        })
+    };
+    let request = match rendered.await {
         Ok(r) => r,
         Err(e) => {
-            return Ok(response_err(
+            return Ok(preflight_err(
                 &app_handle,
                 &*response.lock().await,
                 e.to_string(),
@@ -84,99 +239,151 @@ pub async fn send_http_request<R: Runtime>(
         }
     };
 
+    let max_body_size = workspace.setting_max_body_size.max(0) as usize;
+    let body_size = rendered_body_size(&request.body);
+    if max_body_size > 0 && body_size > max_body_size {
+        return Ok(preflight_err(
+            &app_handle,
+            &*response.lock().await,
+            format!("Rendered body of {body_size} bytes is over the {max_body_size} byte limit"),
+            &update_source,
+        ));
+    }
+
+    if workspace.setting_reject_unresolved_templates {
+        if let Some(e) = unresolved_template_error(&request, environment.as_ref()) {
+            return Ok(preflight_err(&app_handle, &*response.lock().await, e, &update_source));
+        }
+    }
+
+    let timeout_ms = request.timeout_ms.unwrap_or(workspace.setting_request_timeout);
+    let timeout = match request_timeout(timeout_ms) {
+        Ok(t) => t,
+        Err(e) => {
+            return Ok(preflight_err(&app_handle, &*response.lock().await, e, &update_source));
+        }
+    };
+
     let mut url_string = request.url;
 
     url_string = ensure_proto(&url_string);
     if !url_string.starts_with("http://") && !url_string.starts_with("https://") {
         url_string = format!("http://{}", url_string);
     }
-    debug!("Sending request to {} {url_string}", request.method);
 
-    let mut client_builder = reqwest::Client::builder()
-        .redirect(match workspace.setting_follow_redirects {
-            true => Policy::limited(10), // TODO: Handle redirects natively
-            false => Policy::none(),
-        })
-        .connection_verbose(true)
-        .gzip(true)
-        .brotli(true)
-        .deflate(true)
-        .referer(false)
-        .tls_info(true);
+    // Rendered secrets, which are masked wherever the request is logged
+    let secrets = secret_tracker.values();
+    debug!("Sending request to {} {}", request.method, mask_secrets(&url_string, &secrets));
 
-    if workspace.setting_validate_certificates {
-        // Use platform-native verifier to validate certificates
-        let arc_crypto_provider = Arc::new(ring::default_provider());
-        let config = ClientConfig::builder_with_provider(arc_crypto_provider)
-            .with_safe_default_protocol_versions()
-            .unwrap()
-            .with_platform_verifier()
-            .with_no_client_auth();
-        client_builder = client_builder.use_preconfigured_tls(config)
-    } else {
-        // Use rustls to skip validation because rustls_platform_verifier does not have this ability
-        client_builder = client_builder
-            .use_rustls_tls()
-            .danger_accept_invalid_hostnames(true)
-            .danger_accept_invalid_certs(true);
-    }
-
-    match settings.proxy {
-        Some(ProxySetting::Disabled) => client_builder = client_builder.no_proxy(),
-        Some(ProxySetting::Enabled { http, https, auth }) => {
-            debug!("Using proxy http={http} https={https}");
-            let mut proxy = Proxy::custom(move |url| {
-                let http = if http.is_empty() { None } else { Some(http.to_owned()) };
-                let https = if https.is_empty() { None } else { Some(https.to_owned()) };
-                let proxy_url = match (url.scheme(), http, https) {
-                    ("http", Some(proxy_url), _) => Some(proxy_url),
-                    ("https", _, Some(proxy_url)) => Some(proxy_url),
-                    _ => None,
-                };
-                proxy_url
-            });
+    // reqwest can't send a CONNECT that's any use, so the tunnel is opened by hand
+    if request.method.eq_ignore_ascii_case("CONNECT") && !options.dry_run {
+        let environment = environment.as_ref();
+        let settings =
+            match render_proxy_settings(window, settings, &base_environment, environment).await {
+                Ok(s) => s,
+                Err(e) => {
+                    return Ok(preflight_err(
+                        &app_handle,
+                        &*response.lock().await,
+                        format!("Failed to render proxy settings: {e}"),
+                        &update_source,
+                    ));
+                }
+            };
+        let response = response.lock().await.clone();
+        return send_connect_request(
+            window,
+            &response,
+            &settings,
+            &url_string,
+            timeout,
+            &update_source,
+        )
+        .await;
+    }
 
-            if let Some(ProxySettingAuth { user, password }) = auth {
-                debug!("Using proxy auth");
-                proxy = proxy.basic_auth(user.as_str(), password.as_str());
+    let tls_server_name =
+        request.tls_server_name.as_deref().map(str::trim).filter(|n| !n.is_empty());
+    let mut server_name_target = None;
+    if let (Some(name), Ok(mut url)) = (tls_server_name, Url::parse(&url_string)) {
+        if url.scheme() == "https" {
+            match resolve_server_name(&mut url, name).await {
+                Ok(t) => {
+                    url_string = url.to_string();
+                    server_name_target = Some(t);
+                }
+                Err(e) => {
+                    return Ok(preflight_err(
+                        &app_handle,
+                        &*response.lock().await,
+                        e,
+                        &update_source,
+                    ));
+                }
             }
-
-            client_builder = client_builder.proxy(proxy);
         }
-        None => {} // Nothing to do for this one, as it is the default
     }
 
-    // Add cookie store if specified
-    let maybe_cookie_manager = match cookie_jar.clone() {
-        Some(cj) => {
-            // HACK: Can't construct Cookie without serde, so we have to do this
-            let cookies = cj
-                .cookies
-                .iter()
-                .map(|cookie| {
-                    let json_cookie = serde_json::to_value(cookie).unwrap();
-                    serde_json::from_value(json_cookie).expect("Failed to deserialize cookie")
-                })
-                .map(|c| Ok(c))
-                .collect::<Vec<Result<_>>>();
-
-            let store = reqwest_cookie_store::CookieStore::from_cookies(cookies, true)?;
-            let cookie_store = reqwest_cookie_store::CookieStoreMutex::new(store);
-            let cookie_store = Arc::new(cookie_store);
-            client_builder = client_builder.cookie_provider(Arc::clone(&cookie_store));
-
-            Some((cookie_store, cj))
+    let overrides = ClientOverrides {
+        resolve: server_name_target.as_ref().map(|t| (t.server_name.as_str(), t.addr)),
+        ca_cert_pem: request.ca_cert_pem.as_deref().filter(|s| !s.trim().is_empty()),
+    };
+    let SharedClient {
+        client,
+        cookie_store,
+        local_addrs,
+        resolved_addrs,
+        certificate_errors,
+        tunnel: _tunnel,
+    } = match options.shared_client {
+        Some(c) if overrides.is_empty() => c.clone(),
+        _ => {
+            let environment = environment.as_ref();
+            let settings =
+                match render_proxy_settings(window, settings, &base_environment, environment).await
+                {
+                    Ok(s) => s,
+                    Err(e) => {
+                        return Ok(preflight_err(
+                            &app_handle,
+                            &*response.lock().await,
+                            format!("Failed to render proxy settings: {e}"),
+                            &update_source,
+                        ));
+                    }
+                };
+            let base_environment = &base_environment;
+            let cookie_jar =
+                match render_cookies(window, cookie_jar.as_ref(), base_environment, environment)
+                    .await
+                {
+                    Ok(j) => j,
+                    Err(e) => {
+                        return Ok(preflight_err(
+                            &app_handle,
+                            &*response.lock().await,
+                            format!("Failed to render cookies: {e}"),
+                            &update_source,
+                        ));
+                    }
+                };
+            match build_client(&workspace, &settings, cookie_jar.as_ref(), overrides) {
+                Ok(c) => c,
+                Err(e) => {
+                    return Ok(preflight_err(
+                        &app_handle,
+                        &*response.lock().await,
+                        e.to_string(),
+                        &update_source,
+                    ));
+                }
+            }
         }
-        None => None,
     };
-
-    if workspace.setting_request_timeout > 0 {
-        client_builder = client_builder.timeout(Duration::from_millis(
-            workspace.setting_request_timeout.unsigned_abs() as u64,
-        ));
-    }
-
-    let client = client_builder.build()?;
+    let maybe_cookie_manager = match (cookie_store, cookie_jar.clone()) {
+        (Some(cookie_store), Some(cj)) => Some((cookie_store, cj)),
+        _ => None,
+    };
 
     // Render query parameters
     let mut query_params = Vec::new();
@@ -184,16 +391,29 @@ pub async fn send_http_request<R: Runtime>(
         if !p.enabled || p.name.is_empty() {
             continue;
         }
-        query_params.push((p.name, p.value));
+        let name = match query_param_name(&p.name, p.notation) {
+            Ok(name) => name,
+            Err(e) => {
+                return Ok(preflight_err(&app_handle, &*response.lock().await, e, &update_source));
+            }
+        };
+        query_params.push((name, p.value));
     }
+    response.lock().await.request_url_parameters = query_params
+        .iter()
+        .map(|(name, value)| HttpResponseUrlParameter {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+        .collect();
 
     let uri = match Uri::from_str(url_string.as_str()) {
         Ok(u) => u,
         Err(e) => {
-            return Ok(response_err(
+            return Ok(preflight_err(
                 &app_handle,
                 &*response.lock().await,
-                format!("Failed to parse URL \"{}\": {}", url_string, e.to_string()),
+                url_parse_error(&url_string, e),
                 &update_source,
             ));
         }
@@ -202,10 +422,10 @@ pub async fn send_http_request<R: Runtime>(
     let url = match Url::from_str(uri.to_string().as_str()) {
         Ok(u) => u,
         Err(e) => {
-            return Ok(response_err(
+            return Ok(preflight_err(
                 &app_handle,
                 &*response.lock().await,
-                format!("Failed to parse URL \"{}\": {}", url_string, e.to_string()),
+                url_parse_error(&url_string, e),
                 &update_source,
             ));
         }
@@ -213,33 +433,26 @@ pub async fn send_http_request<R: Runtime>(
 
     let m = Method::from_str(&request.method.to_uppercase())
         .map_err(|e| GenericError(e.to_string()))?;
-    let mut request_builder = client.request(m, url).query(&query_params);
+    let mut request_builder = client.request(m, url.clone()).query(&query_params);
+    // Set on the request rather than the client, so a request with its own timeout can still
+    // share a client with the others in a run
+    if let Some(timeout) = timeout {
+        request_builder = request_builder.timeout(timeout);
+    }
 
     let mut headers = HeaderMap::new();
-    headers.insert(USER_AGENT, HeaderValue::from_static("yaak"));
-    headers.insert(ACCEPT, HeaderValue::from_static("*/*"));
-
-    // TODO: Set cookie header ourselves once we also handle redirects. We need to do this
-    //  because reqwest doesn't give us a way to inspect the headers it sent (we have to do
-    //  everything manually to know that).
-    // if let Some(cookie_store) = maybe_cookie_store.clone() {
-    //     let values1 = cookie_store.get_request_values(&url);
-    //     let raw_value = cookie_store.get_request_values(&url)
-    //         .map(|(name, value)| format!("{}={}", name, value))
-    //         .collect::<Vec<_>>()
-    //         .join("; ");
-    //     headers.insert(
-    //         COOKIE,
-    //         HeaderValue::from_str(&raw_value).expect("Failed to create cookie header"),
-    //     );
-    // }
+    if workspace.setting_send_default_headers {
+        headers.insert(USER_AGENT, HeaderValue::from_static("yaak"));
+        headers.insert(ACCEPT, HeaderValue::from_static("*/*"));
+    }
 
+    let environment_id = environment.as_ref().map(|e| e.id.as_str());
     for h in request.headers.clone() {
         if h.name.is_empty() && h.value.is_empty() {
             continue;
         }
 
-        if !h.enabled {
+        if !h.enabled || !h.in_environment(environment_id) {
             continue;
         }
 
@@ -261,55 +474,139 @@ pub async fn send_http_request<R: Runtime>(
         headers.insert(header_name, header_value);
     }
 
-    let request_body = request.body.clone();
-    if let Some(body_type) = &request.body_type {
-        if body_type == "graphql" {
-            let query = get_str_h(&request_body, "query");
-            let variables = get_str_h(&request_body, "variables");
+    // Send the jar's cookies ourselves rather than leaving it to the client, which doesn't say
+    // which ones it sent. A Cookie header set on the request takes precedence.
+    let mut request_cookies = Vec::new();
+    let cookie_store = maybe_cookie_manager.as_ref().map(|(store, _)| store);
+    if let Some(store) = cookie_store.filter(|_| !headers.contains_key(COOKIE)) {
+        let cookies: Vec<String> = store
+            .lock()
+            .unwrap()
+            .get_request_values(&url)
+            .map(|(name, value)| {
+                request_cookies.push(name.to_string());
+                format!("{name}={value}")
+            })
+            .collect();
+        if !cookies.is_empty() {
+            match HeaderValue::from_str(&cookies.join("; ")) {
+                Ok(v) => {
+                    headers.insert(COOKIE, v);
+                }
+                Err(e) => {
+                    warn!("Failed to create cookie header: {e}");
+                    request_cookies.clear();
+                }
+            }
+        }
+    }
+    // Redirects pick up cookies from the jar again, including any the redirect itself set
+    let jar_cookies = !request_cookies.is_empty();
+    response.lock().await.request_cookies = request_cookies;
+
+    // The URL's host was swapped for the TLS server name, so keep the original as the Host header
+    // unless one was set explicitly
+    if let Some(t) = server_name_target.as_ref().filter(|_| !headers.contains_key(HOST)) {
+        match HeaderValue::from_str(&t.authority) {
+            Ok(v) => {
+                headers.insert(HOST, v);
+            }
+            Err(e) => warn!("Invalid Host header value {}: {e}", t.authority),
+        }
+    }
+
+    // Advertise the workspace's encodings, or the ones that can be decoded when decompression is
+    // on. Bodies are still decoded (or not) according to the decompress setting.
+    let advertise = workspace.setting_send_accept_encoding;
+    let accept_encoding =
+        workspace.setting_accept_encoding.as_deref().filter(|e| advertise && !e.is_empty());
+    if let Some(encoding) = accept_encoding {
+        if !headers.contains_key(ACCEPT_ENCODING) {
+            match HeaderValue::from_str(encoding) {
+                Ok(v) => {
+                    headers.insert(ACCEPT_ENCODING, v);
+                }
+                Err(e) => warn!("Invalid Accept-Encoding value {encoding}: {e}"),
+            }
+        }
+    }
+    if advertise && workspace.setting_decompress && !headers.contains_key(ACCEPT_ENCODING) {
+        headers.insert(ACCEPT_ENCODING, HeaderValue::from_static("gzip, br, deflate"));
+    }
+
+    // Tag the request with a correlation ID so it can be found in server logs. A header the user
+    // set themselves takes precedence, so an ID can be pinned by adding it to the request.
+    if let Some(name) = workspace.setting_request_id_header.as_deref().filter(|n| !n.is_empty()) {
+        match HeaderName::from_str(name) {
+            Ok(name) => {
+                let value = match headers.get(&name).and_then(|v| v.to_str().ok()) {
+                    Some(v) => v.to_string(),
+                    None => uuid::Uuid::new_v4().to_string(),
+                };
+                headers.insert(name, HeaderValue::from_str(&value).unwrap());
+                response.lock().await.correlation_id = Some(value);
+            }
+            Err(e) => warn!("Invalid request ID header name {name}: {e}"),
+        }
+    }
+
+    // Revalidate the last response instead of fetching the whole body again, unless the request
+    // sets its own conditions. A 304 then reuses that response's body.
+    let cached_response = match request.cache_responses
+        && request.method.eq_ignore_ascii_case("GET")
+        && !headers.contains_key(IF_NONE_MATCH)
+        && !headers.contains_key(IF_MODIFIED_SINCE)
+    {
+        true => latest_cached_response(window, &request.id),
+        false => None,
+    };
+    for (name, value) in cached_response.iter().flat_map(|r| conditional_headers(&r.headers)) {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(name, value);
+        }
+    }
+
+    let request_body = match HttpRequestBody::from_request(&request) {
+        Ok(b) => b,
+        Err(e) => {
+            return Ok(preflight_err(
+                &app_handle,
+                &*response.lock().await,
+                format!("Invalid body: {e}"),
+                &update_source,
+            ));
+        }
+    };
+    match request_body {
+        Some(HttpRequestBody::Graphql(GraphqlBody { query, variables })) => {
+            // Only warn, since the server might accept variables this doesn't understand
+            response.lock().await.warnings = variable_warnings(&query, &variables);
             let body = if variables.trim().is_empty() {
-                format!(r#"{{"query":{}}}"#, serde_json::to_string(query).unwrap_or_default())
+                format!(r#"{{"query":{}}}"#, serde_json::to_string(&query).unwrap_or_default())
             } else {
                 format!(
                     r#"{{"query":{},"variables":{variables}}}"#,
-                    serde_json::to_string(query).unwrap_or_default()
+                    serde_json::to_string(&query).unwrap_or_default()
                 )
             };
             request_builder = request_builder.body(body.to_owned());
-        } else if body_type == "application/x-www-form-urlencoded"
-            && request_body.contains_key("form")
-        {
-            let mut form_params = Vec::new();
-            let form = request_body.get("form");
-            if let Some(f) = form {
-                match f.as_array() {
-                    None => {}
-                    Some(a) => {
-                        for p in a {
-                            let enabled = get_bool(p, "enabled", true);
-                            let name = get_str(p, "name");
-                            if !enabled || name.is_empty() {
-                                continue;
-                            }
-                            let value = get_str(p, "value");
-                            form_params.push((name, value));
-                        }
-                    }
-                }
-            }
+        }
+        Some(HttpRequestBody::UrlEncodedForm(body)) => {
+            let form_params = body
+                .form
+                .iter()
+                .filter(|p| p.enabled && !p.name.is_empty())
+                .map(|p| (p.name.as_str(), p.value.as_str()))
+                .collect::<Vec<_>>();
             request_builder = request_builder.form(&form_params);
-        } else if body_type == "binary" && request_body.contains_key("filePath") {
-            let file_path = request_body
-                .get("filePath")
-                .ok_or(GenericError("filePath not set".to_string()))?
-                .as_str()
-                .unwrap_or_default();
-
+        }
+        Some(HttpRequestBody::Binary(BinaryBody { file_path })) => {
             match fs::read(file_path).await.map_err(|e| e.to_string()) {
                 Ok(f) => {
                     request_builder = request_builder.body(f);
                 }
                 Err(e) => {
-                    return Ok(response_err(
+                    return Ok(preflight_err(
                         &app_handle,
                         &*response.lock().await,
                         e,
@@ -317,94 +614,125 @@ pub async fn send_http_request<R: Runtime>(
                     ));
                 }
             }
-        } else if body_type == "multipart/form-data" && request_body.contains_key("form") {
+        }
+        Some(HttpRequestBody::Multipart(body)) => {
             let mut multipart_form = multipart::Form::new();
-            if let Some(form_definition) = request_body.get("form") {
-                match form_definition.as_array() {
-                    None => {}
-                    Some(fd) => {
-                        for p in fd {
-                            let enabled = get_bool(p, "enabled", true);
-                            let name = get_str(p, "name").to_string();
-
-                            if !enabled || name.is_empty() {
-                                continue;
-                            }
-
-                            let file_path = get_str(p, "file").to_owned();
-                            let value = get_str(p, "value").to_owned();
-
-                            let mut part = if file_path.is_empty() {
-                                multipart::Part::text(value.clone())
-                            } else {
-                                match fs::read(file_path.clone()).await {
-                                    Ok(f) => multipart::Part::bytes(f),
-                                    Err(e) => {
-                                        return Ok(response_err(
-                                            &app_handle,
-                                            &*response.lock().await,
-                                            e.to_string(),
-                                            &update_source,
-                                        ));
-                                    }
-                                }
-                            };
+            for p in body.form.into_iter().filter(|p| p.enabled && !p.name.is_empty()) {
+                // An entry pointing at a directory sends each file in it as its own part, named
+                // after the file
+                let is_dir = match p.file.is_empty() {
+                    true => false,
+                    false => fs::metadata(&p.file).await.is_ok_and(|m| m.is_dir()),
+                };
+                let files = match is_dir {
+                    false => vec![(p.name.clone(), p.file.clone())],
+                    true => match directory_files(Path::new(&p.file), &p.filter).await {
+                        Ok(files) => files
+                            .iter()
+                            .map(|f| (field_name(f), f.to_string_lossy().to_string()))
+                            .collect(),
+                        Err(e) => {
+                            return Ok(preflight_err(
+                                &app_handle,
+                                &*response.lock().await,
+                                format!("Failed to read directory {}: {e}", p.file),
+                                &update_source,
+                            ));
+                        }
+                    },
+                };
 
-                            let content_type = get_str(p, "contentType");
-
-                            // Set or guess mimetype
-                            if !content_type.is_empty() {
-                                part = match part.mime_str(content_type) {
-                                    Ok(p) => p,
-                                    Err(e) => {
-                                        return Ok(response_err(
-                                            &app_handle,
-                                            &*response.lock().await,
-                                            format!("Invalid mime for multi-part entry {e:?}"),
-                                            &update_source,
-                                        ));
-                                    }
-                                };
-                            } else if !file_path.is_empty() {
-                                let default_mime =
-                                    Mime::from_str("application/octet-stream").unwrap();
-                                let mime =
-                                    mime_guess::from_path(file_path.clone()).first_or(default_mime);
-                                part = match part.mime_str(mime.essence_str()) {
-                                    Ok(p) => p,
-                                    Err(e) => {
-                                        return Ok(response_err(
-                                            &app_handle,
-                                            &*response.lock().await,
-                                            format!("Invalid mime for multi-part entry {e:?}"),
-                                            &update_source,
-                                        ));
-                                    }
-                                };
+                for (name, file_path) in files {
+                    let mut part = if file_path.is_empty() {
+                        multipart::Part::text(p.value.clone())
+                    } else {
+                        match file_part(&file_path).await {
+                            Ok(part) => part,
+                            Err(e) => {
+                                return Ok(preflight_err(
+                                    &app_handle,
+                                    &*response.lock().await,
+                                    e.to_string(),
+                                    &update_source,
+                                ));
                             }
+                        }
+                    };
 
-                            // Set file path if not empty
-                            if !file_path.is_empty() {
-                                let filename = PathBuf::from(file_path)
-                                    .file_name()
-                                    .unwrap_or_default()
-                                    .to_string_lossy()
-                                    .to_string();
-                                part = part.file_name(filename);
+                    // Set or guess mimetype
+                    if let Some(mime) = part_content_type(&p.content_type, &file_path) {
+                        part = match part.mime_str(&mime) {
+                            Ok(p) => p,
+                            Err(e) => {
+                                return Ok(preflight_err(
+                                    &app_handle,
+                                    &*response.lock().await,
+                                    format!("Invalid mime for multi-part entry {e:?}"),
+                                    &update_source,
+                                ));
                             }
+                        };
+                    }
 
-                            multipart_form = multipart_form.part(name, part);
-                        }
+                    // Set file path if not empty
+                    if !file_path.is_empty() {
+                        let filename = PathBuf::from(file_path)
+                            .file_name()
+                            .unwrap_or_default()
+                            .to_string_lossy()
+                            .to_string();
+                        part = part.file_name(filename);
                     }
+
+                    multipart_form = multipart_form.part(name, part);
                 }
             }
             headers.remove("Content-Type"); // reqwest will add this automatically
             request_builder = request_builder.multipart(multipart_form);
-        } else if request_body.contains_key("text") {
-            let body = get_str_h(&request_body, "text");
-            request_builder = request_builder.body(body.to_owned());
-        } else {
-            warn!("Unsupported body type: {}", body_type);
+        }
+        Some(HttpRequestBody::Json(TextBody { text })) => {
+            if !text.trim().is_empty() {
+                if let Err(e) = serde_json::from_str::<Value>(&text) {
+                    return Ok(preflight_err(
+                        &app_handle,
+                        &*response.lock().await,
+                        format!("Invalid JSON body: {e}"),
+                        &update_source,
+                    ));
+                }
+            }
+            if !headers.contains_key(CONTENT_TYPE) {
+                headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+            }
+            match text_body(&text, &mut headers, workspace.setting_add_charset) {
+                Ok(b) => request_builder = request_builder.body(b),
+                Err(e) => {
+                    return Ok(preflight_err(
+                        &app_handle,
+                        &*response.lock().await,
+                        e,
+                        &update_source,
+                    ));
+                }
+            }
+        }
+        Some(HttpRequestBody::Text(TextBody { text })) => {
+            match text_body(&text, &mut headers, workspace.setting_add_charset) {
+                Ok(b) => request_builder = request_builder.body(b),
+                Err(e) => {
+                    return Ok(preflight_err(
+                        &app_handle,
+                        &*response.lock().await,
+                        e,
+                        &update_source,
+                    ));
+                }
+            }
+        }
+        None => {
+            if let Some(body_type) = &request.body_type {
+                warn!("Unsupported body type: {}", body_type);
+            }
         }
     }
 
@@ -415,7 +743,7 @@ pub async fn send_http_request<R: Runtime>(
         Ok(r) => r,
         Err(e) => {
             warn!("Failed to build request builder {e:?}");
-            return Ok(response_err(
+            return Ok(preflight_err(
                 &app_handle,
                 &*response.lock().await,
                 e.to_string(),
@@ -426,62 +754,274 @@ pub async fn send_http_request<R: Runtime>(
 
     // Apply authentication
 
-    if let Some(auth_name) = request.authentication_type.to_owned() {
-        let req = CallHttpAuthenticationRequest {
-            context_id: format!("{:x}", md5::compute(request.id)),
-            values: serde_json::from_value(serde_json::to_value(&request.authentication).unwrap())
-                .unwrap(),
-            url: sendable_req.url().to_string(),
-            method: sendable_req.method().to_string(),
-            headers: sendable_req
-                .headers()
-                .iter()
-                .map(|(name, value)| HttpHeader {
-                    name: name.to_string(),
-                    value: value.to_str().unwrap_or_default().to_string(),
-                })
-                .collect(),
-        };
-        let auth_result = plugin_manager.call_http_authentication(window, &auth_name, req).await;
-        let plugin_result = match auth_result {
-            Ok(r) => r,
-            Err(e) => {
-                return Ok(response_err(
-                    &app_handle,
-                    &*response.lock().await,
-                    e.to_string(),
-                    &update_source,
-                ));
+    let context_id = format!("{:x}", md5::compute(request.id));
+    match request.authentication_type.to_owned().filter(|_| !options.skip_auth) {
+        Some(auth_name) if is_pkce_flow(&auth_name, &request.authentication) => {
+            let values = &request.authentication;
+            let result = pkce_authorization_header(window, &client, &context_id, values).await;
+            let header = match result {
+                Ok(v) => HeaderValue::from_str(&v).map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            match header {
+                Ok(v) => {
+                    sendable_req.headers_mut().insert(AUTHORIZATION, v);
+                }
+                Err(e) => {
+                    return Ok(preflight_err(
+                        &app_handle,
+                        &*response.lock().await,
+                        e,
+                        &update_source,
+                    ));
+                }
             }
-        };
-
-        let headers = sendable_req.headers_mut();
-        for header in plugin_result.set_headers {
-            headers.insert(
-                HeaderName::from_str(&header.name).unwrap(),
-                HeaderValue::from_str(&header.value).unwrap(),
-            );
         }
-    }
-
-    let (resp_tx, resp_rx) = oneshot::channel::<std::result::Result<Response, reqwest::Error>>();
-    let (done_tx, done_rx) = oneshot::channel::<HttpResponse>();
-
-    let start = std::time::Instant::now();
-
-    tokio::spawn(async move {
-        let _ = resp_tx.send(client.execute(sendable_req).await);
-    });
-
+        Some(auth_name) => {
+            let req = CallHttpAuthenticationRequest {
+                context_id,
+                values: serde_json::from_value(
+                    serde_json::to_value(&request.authentication).unwrap(),
+                )
+                .unwrap(),
+                url: sendable_req.url().to_string(),
+                method: sendable_req.method().to_string(),
+                headers: sendable_req
+                    .headers()
+                    .iter()
+                    .map(|(name, value)| HttpHeader {
+                        name: name.to_string(),
+                        value: value.to_str().unwrap_or_default().to_string(),
+                    })
+                    .collect(),
+            };
+            let auth_result =
+                plugin_manager.call_http_authentication(window, &auth_name, req).await;
+            let plugin_result = match auth_result {
+                Ok(r) => r,
+                Err(e) => {
+                    return Ok(preflight_err(
+                        &app_handle,
+                        &*response.lock().await,
+                        e.to_string(),
+                        &update_source,
+                    ));
+                }
+            };
+
+            let headers = sendable_req.headers_mut();
+            for header in plugin_result.set_headers {
+                headers.insert(
+                    HeaderName::from_str(&header.name).unwrap(),
+                    HeaderValue::from_str(&header.value).unwrap(),
+                );
+            }
+        }
+        None => {}
+    }
+
+    // Trailers go after the body, which is streamed so they can be sent once it's done. Streamed
+    // bodies can't be cloned, so requests with trailers aren't retried or redirected.
+    let mut trailers = HeaderMap::new();
+    for h in request.trailers.iter().filter(|h| h.enabled && h.in_environment(environment_id)) {
+        if h.name.is_empty() {
+            continue;
+        }
+        match (HeaderName::from_str(&h.name), HeaderValue::from_str(&h.value)) {
+            (Ok(name), Ok(value)) => {
+                trailers.append(name, value);
+            }
+            (Err(e), _) => error!("Failed to create trailer name: {}", e),
+            (_, Err(e)) => error!("Failed to create trailer value: {}", e),
+        }
+    }
+    let has_trailers = !trailers.is_empty();
+    if has_trailers {
+        // HTTP/1.1 only sends the trailers that this header announces
+        let names = trailers.keys().map(|k| k.as_str()).collect::<Vec<_>>().join(", ");
+        let headers = sendable_req.headers_mut();
+        headers.insert(TRAILER, HeaderValue::from_str(&names).unwrap());
+        headers.remove(CONTENT_LENGTH);
+        let body = sendable_req.body_mut().take().unwrap_or_else(|| reqwest::Body::from(""));
+        *sendable_req.body_mut() = Some(reqwest::Body::wrap(WithTrailers::new(body, trailers)));
+    }
+
+    // Recorded as sent, with auth and cookies, so the response can be resent as it was
+    let sent_headers = sendable_req.headers().clone();
+
+    if options.dry_run {
+        let mut r = response.lock().await;
+        r.url = sendable_req.url().to_string();
+        r.request_headers = sent_headers
+            .iter()
+            .map(|(k, v)| HttpResponseHeader {
+                name: k.as_str().to_string(),
+                value: v.to_str().unwrap_or_default().to_string(),
+            })
+            .collect();
+        r.state = HttpResponseState::Closed;
+        app_handle.db().update_http_response_if_id(&r, &update_source)?;
+        return Ok(r.clone());
+    }
+
+    let base_dir = match workspace.setting_response_dir.as_deref().filter(|d| !d.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => match app_handle.path().app_data_dir() {
+            Ok(dir) => dir.join("responses"),
+            Err(e) => {
+                return Ok(preflight_err(
+                    &app_handle,
+                    &*response.lock().await,
+                    format!("Failed to find the app data directory: {e}"),
+                    &update_source,
+                ));
+            }
+        },
+    };
+    if let Err(e) = create_dir_all(&base_dir).await {
+        return Ok(preflight_err(
+            &app_handle,
+            &*response.lock().await,
+            format!("Failed to create response directory {base_dir:?}: {e}"),
+            &update_source,
+        ));
+    }
+
+    let (resp_tx, resp_rx) = oneshot::channel::<std::result::Result<Response, reqwest::Error>>();
+    let (done_tx, done_rx) = oneshot::channel::<HttpResponse>();
+
+    let start = std::time::Instant::now();
+    let deadline = timeout.map(|t| tokio::time::Instant::from_std(start) + t);
+    let is_head = *sendable_req.method() == Method::HEAD;
+    let error_statuses = match workspace.setting_error_status_codes.as_deref() {
+        Some(spec) => parse_status_ranges(spec).unwrap_or_else(|e| {
+            warn!("Ignoring workspace error status codes: {e}");
+            Vec::new()
+        }),
+        None => Vec::new(),
+    };
+
+    let max_retries = workspace.setting_max_retries.max(0) as u32;
+    let max_retry_wait = Duration::from_millis(workspace.setting_max_retry_wait.max(0) as u64);
+    let manual_redirects = follows_redirects_manually(&workspace);
+    let redirect_secrets = secrets.clone();
+    // Only requests that can be sent again over TCP try HTTP/3, which isn't the case for
+    // streaming bodies
+    let can_fall_back = sendable_req.try_clone().is_some();
+    if workspace.setting_enable_http3 && sendable_req.url().scheme() == "https" && can_fall_back {
+        *sendable_req.version_mut() = reqwest::Version::HTTP_3;
+    }
+    let fallback_response = response.clone();
+    let execute_handle = tokio::spawn(async move {
+        let mut attempt = 0;
+        let mut redirects = 0;
+        let result = loop {
+            // Streaming bodies can't be cloned, so those requests are only ever sent once
+            let next_req = match attempt < max_retries || manual_redirects {
+                true => sendable_req.try_clone(),
+                false => None,
+            };
+            let is_http3 = sendable_req.version() == reqwest::Version::HTTP_3;
+            let fallback_req = if is_http3 { sendable_req.try_clone() } else { None };
+            let result = match fallback_req {
+                // Retry the same request over TCP when HTTP/3 fails or doesn't answer in time
+                Some(mut req) => match execute_http3(&client, sendable_req).await {
+                    Ok(r) => Ok(r),
+                    Err(e) => {
+                        debug!("HTTP/3 failed, falling back to TCP: {e}");
+                        let warning = format!("HTTP/3 failed, so HTTP/1.1 or HTTP/2 was used: {e}");
+                        fallback_response.lock().await.warnings.push(warning);
+                        *req.version_mut() = reqwest::Version::default();
+                        client.execute(req).await
+                    }
+                },
+                None => client.execute(sendable_req).await,
+            };
+            let (next_req, r) = match (next_req, &result) {
+                (Some(req), Ok(r)) => (req, r),
+                _ => break result,
+            };
+
+            let status = r.status().as_u16();
+            if manual_redirects && is_redirect_status(status) && redirects < MAX_REDIRECTS {
+                match redirect_request(next_req, r) {
+                    Some(mut req) => {
+                        if jar_cookies {
+                            req.headers_mut().remove(COOKIE);
+                        }
+                        let url = mask_secrets(req.url().as_str(), &redirect_secrets);
+                        debug!("Following {status} redirect to {url}");
+                        sendable_req = req;
+                        redirects += 1;
+                        continue;
+                    }
+                    None => break result,
+                }
+            }
+
+            if attempt >= max_retries || !is_retryable_status(status) {
+                break result;
+            }
+
+            let retry_after = r.headers().get(RETRY_AFTER);
+            let wait = retry_wait(
+                retry_after.and_then(|v| v.to_str().ok()),
+                RETRY_BACKOFF,
+                max_retry_wait,
+            );
+            debug!("Retrying request in {wait:?} (attempt {})", attempt + 1);
+            tokio::time::sleep(wait).await;
+            sendable_req = next_req;
+            attempt += 1;
+        };
+        let _ = resp_tx.send(result);
+    });
+
     let raw_response = tokio::select! {
         Ok(r) = resp_rx => r,
         _ = cancelled_rx.changed() => {
             debug!("Request cancelled");
+            // Dropping the execute future is what actually tears down a pending DNS lookup or
+            // connection attempt, so abort the task rather than letting it run to completion
+            execute_handle.abort();
             return Ok(response_err(&app_handle, &*response.lock().await, "Request was cancelled".to_string(), &update_source));
         }
     };
 
-    {
+    let header_limit = match &raw_response {
+        Ok(v) => header_limit_error(
+            v.headers(),
+            workspace.setting_max_header_size.max(0) as usize,
+            workspace.setting_max_header_count.max(0) as usize,
+        ),
+        Err(_) => None,
+    };
+    if let Some(e) = header_limit {
+        return Ok(response_err(&app_handle, &*response.lock().await, e, &update_source));
+    }
+
+    let (local_addr, reused_connection) = match &raw_response {
+        Ok(v) => connection_info(v, &local_addrs),
+        Err(_) => (None, false),
+    };
+    let resolved = match &raw_response {
+        Ok(v) => v.url().host_str().and_then(|h| resolved_addrs.lock().unwrap().get(h).cloned()),
+        Err(_) => None,
+    };
+    let resolved: Vec<String> =
+        resolved.unwrap_or_default().iter().map(|a| a.to_string()).collect();
+    let tls_error = match &raw_response {
+        Ok(v) => v.url().host_str().and_then(|h| {
+            let host = h.trim_start_matches('[').trim_end_matches(']');
+            certificate_errors.lock().unwrap().get(host).cloned()
+        }),
+        Err(_) => None,
+    };
+
+    let download_path = options.download_path;
+    let decompress = workspace.setting_decompress;
+    let max_decoded_size = workspace.setting_max_decoded_size.max(0) as usize;
+    let body_handle = {
         let app_handle = app_handle.clone();
         let window = window.clone();
         let cancelled_rx = cancelled_rx.clone();
@@ -493,22 +1033,42 @@ pub async fn send_http_request<R: Runtime>(
                 Ok(mut v) => {
                     let content_length = v.content_length();
                     let response_headers = v.headers().clone();
-                    let request_headers = headers.clone();
-                    let dir = app_handle.path().app_data_dir().unwrap();
-                    let base_dir = dir.join("responses");
-                    create_dir_all(base_dir.clone()).await.expect("Failed to create responses dir");
-                    let body_path = if response_id.is_empty() {
-                        base_dir.join(uuid::Uuid::new_v4().to_string())
-                    } else {
-                        base_dir.join(response_id.clone())
+                    let request_headers = sent_headers.clone();
+
+                    // A 304 has no body of its own, so it gets the one it revalidated
+                    let cached_body = match v.status() == StatusCode::NOT_MODIFIED {
+                        true => cached_response.as_ref().and_then(|r| r.body_path.clone()),
+                        false => None,
+                    };
+
+                    let (body_path, write_path) = match download_path {
+                        // The download belongs to the user, so it isn't stored as the body (or
+                        // deleted along with the response)
+                        Some(p) => (None, (!is_head).then_some(p)),
+                        None => {
+                            // HEAD responses and empty bodies have nothing to write, so skip
+                            // the file
+                            let is_empty = is_head || content_length == Some(0);
+                            let p = match is_empty && cached_body.is_none() {
+                                true => None,
+                                false if response_id.is_empty() => {
+                                    Some(base_dir.join(uuid::Uuid::new_v4().to_string()))
+                                }
+                                false => Some(base_dir.join(response_id.clone())),
+                            };
+                            (p.clone(), p)
+                        }
                     };
 
                     {
                         let mut r = response.lock().await;
-                        r.body_path = Some(body_path.to_str().unwrap().to_string());
+                        r.body_path = body_path.as_ref().map(|p| p.to_str().unwrap().to_string());
                         r.elapsed_headers = start.elapsed().as_millis() as i32;
                         r.status = v.status().as_u16() as i32;
                         r.status_reason = v.status().canonical_reason().map(|s| s.to_string());
+                        // Only the final response is recorded. Informational 1xx responses, like
+                        // `103 Early Hints`, are read and discarded by hyper, and reqwest has no
+                        // way to register hyper's `on_informational` callback for a request.
                         r.headers = response_headers
                             .iter()
                             .map(|(k, v)| HttpResponseHeader {
@@ -523,8 +1083,14 @@ pub async fn send_http_request<R: Runtime>(
                                 value: v.to_str().unwrap_or_default().to_string(),
                             })
                             .collect();
+                        r.cache_age = cache_age(&r.headers);
+                        r.from_cache = served_from_cache(&r.headers);
                         r.url = v.url().to_string();
+                        r.set_cookies = parse_set_cookies(&response_headers, v.url());
                         r.remote_addr = v.remote_addr().map(|a| a.to_string());
+                        r.resolved_addrs = resolved;
+                        r.local_addr = local_addr.map(|a| a.to_string());
+                        r.reused_connection = reused_connection;
                         r.version = match v.version() {
                             reqwest::Version::HTTP_09 => Some("HTTP/0.9".to_string()),
                             reqwest::Version::HTTP_10 => Some("HTTP/1.0".to_string()),
@@ -533,6 +1099,17 @@ pub async fn send_http_request<R: Runtime>(
                             reqwest::Version::HTTP_3 => Some("HTTP/3".to_string()),
                             _ => None,
                         };
+                        if has_trailers && v.version() < reqwest::Version::HTTP_2 {
+                            r.warnings.push(
+                                "Trailers were sent in a chunked HTTP/1.1 body, which many \
+                                 servers ignore, since HTTP/2 wasn't negotiated"
+                                    .to_string(),
+                            );
+                        }
+                        if v.extensions().get::<TlsInfo>().is_some() {
+                            r.sni_hostname = v.url().host_str().and_then(sni_hostname);
+                            r.tls_error = tls_error;
+                        }
 
                         r.state = HttpResponseState::Connected;
                         app_handle
@@ -542,57 +1119,217 @@ pub async fn send_http_request<R: Runtime>(
                     }
 
                     // Write body to FS
-                    let mut f = File::options()
-                        .create(true)
-                        .truncate(true)
-                        .write(true)
-                        .open(&body_path)
-                        .await
-                        .expect("Failed to open file");
+                    let open_file = match &write_path {
+                        Some(p) => Some(
+                            File::options().create(true).truncate(true).write(true).open(p).await,
+                        ),
+                        None => None,
+                    };
+                    let mut f = match open_file.transpose() {
+                        Ok(f) => f,
+                        Err(e) => {
+                            let mut r = response.lock().await;
+                            let path = write_path.unwrap_or_default();
+                            *r = response_err(
+                                &app_handle,
+                                &r,
+                                format!("Failed to open {path:?} for writing: {e}"),
+                                &update_source,
+                            );
+                            // Nobody is waiting anymore if the request was cancelled meanwhile
+                            let _ = done_tx.send(r.clone());
+                            return;
+                        }
+                    };
 
+                    // Decoded here rather than by the client, which would hide the encoded size
+                    let mut decoder = response_headers
+                        .get(CONTENT_ENCODING)
+                        .and_then(|v| v.to_str().ok())
+                        .filter(|_| decompress)
+                        .and_then(|v| BodyDecoder::new(v, max_decoded_size));
+                    let is_decoded = decoder.is_some();
+
+                    // Newline-delimited JSON streams are parsed as they arrive, so each object
+                    // can be shown without waiting for the stream to end
+                    let mut ndjson = response_headers
+                        .get(CONTENT_TYPE)
+                        .and_then(|v| v.to_str().ok())
+                        .filter(|v| is_ndjson(v))
+                        .map(|_| NdjsonParser::default());
+                    let emit_ndjson = |response_id: &str, lines: Vec<NdjsonLine>| {
+                        for line in lines {
+                            let event = NdjsonEvent::new(response_id, line);
+                            if let Err(e) = app_handle.emit(NDJSON_EVENT, event) {
+                                warn!("Failed to emit NDJSON event: {e}");
+                            }
+                        }
+                    };
+
+                    let mut raw_bytes: usize = 0;
                     let mut written_bytes: usize = 0;
+                    let mut preview = Vec::new();
+                    let mut last_progress: Option<std::time::Instant> = None;
+                    let emit_progress = |response_id: &str, raw_bytes: usize| {
+                        let progress = HttpResponseProgress {
+                            response_id: response_id.to_string(),
+                            bytes_downloaded: raw_bytes as u64,
+                            elapsed: start.elapsed().as_millis() as u64,
+                        };
+                        if let Err(e) = app_handle.emit(PROGRESS_EVENT, progress) {
+                            warn!("Failed to emit response progress: {e}");
+                        }
+                    };
                     loop {
-                        let chunk = v.chunk().await;
+                        // The client timeout doesn't stop a body that keeps trickling in, so
+                        // enforce the same deadline on every chunk
+                        let chunk = match deadline {
+                            Some(d) => tokio::time::timeout_at(d, v.chunk()).await,
+                            None => Ok(v.chunk().await),
+                        };
                         if *cancelled_rx.borrow() {
                             // Request was canceled
                             return;
                         }
-                        match chunk {
-                            Ok(Some(bytes)) => {
+                        let chunk = match chunk {
+                            Ok(chunk) => chunk,
+                            Err(_) => {
                                 let mut r = response.lock().await;
-                                r.elapsed = start.elapsed().as_millis() as i32;
-                                f.write_all(&bytes).await.expect("Failed to write to file");
-                                f.flush().await.expect("Failed to flush file");
-                                written_bytes += bytes.len();
-                                r.content_length = Some(written_bytes as i32);
-                                app_handle
-                                    .db()
-                                    .update_http_response_if_id(&r, &update_source)
-                                    .expect("Failed to update response");
+                                let timeout = timeout.unwrap_or_default().as_millis();
+                                *r = response_err(
+                                    &app_handle,
+                                    &r,
+                                    format!("Request timed out after {timeout}ms"),
+                                    &update_source,
+                                );
+                                break;
+                            }
+                        };
+                        let (bytes, done) = match chunk {
+                            Ok(Some(bytes)) => {
+                                raw_bytes += bytes.len();
+                                let bytes = match decoder.as_mut() {
+                                    Some(d) => d.decode(&bytes),
+                                    None => Ok(bytes.to_vec()),
+                                };
+                                (bytes, false)
                             }
-                            Ok(None) => {
+                            // Whatever the decoder held back still has to be written
+                            Ok(None) => match decoder.take() {
+                                Some(d) => (d.finish(), true),
+                                None => break,
+                            },
+                            Err(e) => {
+                                let mut r = response.lock().await;
+                                *r = response_err(&app_handle, &r, error_chain(&e), &update_source);
                                 break;
                             }
+                        };
+                        let bytes = match bytes {
+                            Ok(bytes) => bytes,
                             Err(e) => {
-                                response_err(
+                                let mut r = response.lock().await;
+                                *r = response_err(
                                     &app_handle,
-                                    &*response.lock().await,
-                                    e.to_string(),
+                                    &r,
+                                    format!("Failed to decode body: {e}"),
                                     &update_source,
                                 );
                                 break;
                             }
+                        };
+
+                        let mut r = response.lock().await;
+                        r.elapsed = start.elapsed().as_millis() as i32;
+                        if let Some(f) = f.as_mut() {
+                            // A full or read-only disk fails the response, not the whole app
+                            let written = match f.write_all(&bytes).await {
+                                Ok(()) => f.flush().await,
+                                Err(e) => Err(e),
+                            };
+                            if let Err(e) = written {
+                                let path = write_path.clone().unwrap_or_default();
+                                *r = response_err(
+                                    &app_handle,
+                                    &r,
+                                    format!("Failed to write body to {path:?}: {e}"),
+                                    &update_source,
+                                );
+                                break;
+                            }
+                        }
+                        written_bytes += bytes.len();
+                        if let Some(parser) = ndjson.as_mut() {
+                            emit_ndjson(&r.id, parser.push(&bytes));
+                        }
+                        let n = BODY_PREVIEW_LEN.saturating_sub(preview.len());
+                        preview.extend_from_slice(&bytes[..n.min(bytes.len())]);
+                        r.content_length = Some(written_bytes as i32);
+                        r.content_length_raw = Some(raw_bytes as i32);
+                        app_handle
+                            .db()
+                            .update_http_response_if_id(&r, &update_source)
+                            .expect("Failed to update response");
+                        if last_progress.is_none_or(|t| t.elapsed() >= PROGRESS_INTERVAL) {
+                            emit_progress(&r.id, raw_bytes);
+                            last_progress = Some(std::time::Instant::now());
+                        }
+                        if done {
+                            break;
+                        }
+                    }
+
+                    // Always report the final count, even if the last chunk was throttled
+                    emit_progress(&response.lock().await.id, raw_bytes);
+                    if let Some(last) = ndjson.and_then(|p| p.finish()) {
+                        emit_ndjson(&response.lock().await.id, vec![last]);
+                    }
+
+                    if let (Some(from), Some(to)) = (&cached_body, &write_path) {
+                        match fs::copy(from, to).await {
+                            Ok(n) => {
+                                written_bytes = n as usize;
+                                preview = cached_response
+                                    .and_then(|r| r.body_preview)
+                                    .unwrap_or_default()
+                                    .into_bytes();
+                            }
+                            Err(e) => warn!("Failed to reuse cached body {from}: {e}"),
                         }
                     }
 
                     // Set final content length
                     {
                         let mut r = response.lock().await;
+                        // A body that doesn't match the header is likely truncated, so report
+                        // what was actually received rather than what was promised
+                        let expected = content_length.filter(|_| cached_body.is_none());
+                        let mismatched = expected.filter(|l| *l != raw_bytes as u64);
+                        if let Some(expected) = mismatched {
+                            r.warnings.push(format!(
+                                "Content-Length was {expected} bytes but {raw_bytes} were received"
+                            ));
+                        }
+                        // The header has the encoded length, so it only applies to bodies that
+                        // weren't decoded
+                        let content_length =
+                            expected.filter(|_| !is_decoded && mismatched.is_none());
                         r.content_length = match content_length {
                             Some(l) => Some(l as i32),
                             None => Some(written_bytes as i32),
                         };
+                        r.content_length_raw = Some(raw_bytes as i32);
                         r.state = HttpResponseState::Closed;
+                        if body_path.is_some() {
+                            r.body_preview = Some(String::from_utf8_lossy(&preview).into_owned());
+                        }
+                        if r.error.is_none() && status_in_ranges(&error_statuses, r.status as u16) {
+                            r.error = Some(format!(
+                                "Status {} is configured as an error for this workspace",
+                                r.status
+                            ));
+                            r.error_kind = Some(HttpResponseErrorKind::Status);
+                        }
                         app_handle
                             .db()
                             .update_http_response_if_id(&r, &UpdateSource::from_window(&window))
@@ -619,7 +1356,9 @@ pub async fn send_http_request<R: Runtime>(
                                     .expect("Failed to deserialize cookie")
                             })
                             .collect::<Vec<_>>();
-                        cookie_jar.cookies = json_cookies;
+                        let set_cookies = response.lock().await.set_cookies.clone();
+                        cookie_jar.cookies =
+                            with_cookie_templates(json_cookies, &cookie_jar, &set_cookies);
                         if let Err(e) = app_handle
                             .db()
                             .upsert_cookie_jar(&cookie_jar, &UpdateSource::from_window(&window))
@@ -629,25 +1368,24 @@ pub async fn send_http_request<R: Runtime>(
                     }
                 }
                 Err(e) => {
-                    warn!("Failed to execute request {e}");
-                    response_err(
-                        &app_handle,
-                        &*response.lock().await,
-                        format!("{e} → {e:?}"),
-                        &update_source,
-                    );
+                    let e = with_header_limit_hint(error_chain(&e));
+                    warn!("Failed to execute request {}", mask_secrets(&e, &secrets));
+                    response_err(&app_handle, &*response.lock().await, e, &update_source);
                 }
             };
 
+            // The receiver is gone if cancellation won the race, which is fine
             let r = response.lock().await.clone();
-            done_tx.send(r).unwrap();
-        });
+            let _ = done_tx.send(r);
+        })
     };
 
     let app_handle = app_handle.clone();
     Ok(tokio::select! {
         Ok(r) = done_rx => r,
         _ = cancelled_rx.changed() => {
+            // Drop the body stream (and its connection) instead of waiting for the next chunk
+            body_handle.abort();
             match app_handle.with_db(|c| c.get_http_response(&response_id)) {
                 Ok(mut r) => {
                     r.state = HttpResponseState::Closed;
@@ -662,6 +1400,721 @@ pub async fn send_http_request<R: Runtime>(
     })
 }
 
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SavedHttpResponse {
+    pub response: HttpResponse,
+    /// Where the body was written. None if the request failed or was cancelled, or the response
+    /// had no body.
+    pub path: Option<String>,
+}
+
+/// Send a request and copy the response body to a file once it has finished. Unlike
+/// `SendOptions::download_path`, the body is also stored with the response as usual.
+pub async fn send_http_request_to_file<R: Runtime>(
+    window: &WebviewWindow<R>,
+    request: &HttpRequest,
+    response: &HttpResponse,
+    environment: Option<Environment>,
+    cookie_jar: Option<CookieJar>,
+    path: &Path,
+    cancelled_rx: &mut Receiver<bool>,
+) -> Result<SavedHttpResponse> {
+    let options = SendOptions::default();
+    let response = send_http_request(
+        window,
+        request,
+        response,
+        environment,
+        cookie_jar,
+        options,
+        cancelled_rx,
+    )
+    .await?;
+
+    // A cancelled response is closed too, but only has part of the body
+    let cancelled = *cancelled_rx.borrow();
+    let body_path = response.body_path.as_ref().filter(|_| response.error.is_none() && !cancelled);
+    let path = match body_path {
+        None => None,
+        Some(p) => {
+            fs::copy(p, path)
+                .await
+                .map_err(|e| GenericError(format!("Failed to write body to {path:?}: {e}")))?;
+            Some(path.to_string_lossy().to_string())
+        }
+    };
+
+    Ok(SavedHttpResponse { response, path })
+}
+
+/// Ask the proxy in settings to open a tunnel to the host and port of a URL, recording what it
+/// answered on the response. The tunnel is closed again without sending anything through it, so
+/// this only checks whether the proxy permits tunneling to that host.
+async fn send_connect_request<R: Runtime>(
+    window: &WebviewWindow<R>,
+    response: &HttpResponse,
+    settings: &Settings,
+    url: &str,
+    timeout: Option<Duration>,
+    update_source: &UpdateSource,
+) -> Result<HttpResponse> {
+    let app_handle = window.app_handle();
+    let target = match Url::parse(url) {
+        Ok(u) => match (u.host_str(), u.port_or_known_default()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            _ => {
+                let error = format!("URL \"{url}\" has no host to CONNECT to");
+                return Ok(preflight_err(app_handle, response, error, update_source));
+            }
+        },
+        Err(e) => {
+            let error = url_parse_error(url, e);
+            return Ok(preflight_err(app_handle, response, error, update_source));
+        }
+    };
+
+    // Tunnels are usually for HTTPS, so prefer that proxy when both are set
+    let (proxy, auth) = match &settings.proxy {
+        Some(ProxySetting::Enabled {
+            http, https, auth, ..
+        }) => {
+            let proxy = if https.is_empty() { http } else { https };
+            (proxy.as_str(), auth.as_ref().map(|a| (a.user.as_str(), a.password.as_str())))
+        }
+        _ => ("", None),
+    };
+    if proxy.is_empty() {
+        let error = "CONNECT requests are sent to a proxy, but none is enabled in settings";
+        return Ok(preflight_err(app_handle, response, error.to_string(), update_source));
+    }
+
+    let start = std::time::Instant::now();
+    let probe = probe_connect(proxy, &target, auth);
+    let result = match timeout {
+        Some(t) => match tokio::time::timeout(t, probe).await {
+            Ok(r) => r.map_err(|e| e.to_string()),
+            Err(_) => Err(format!("Request timed out after {}ms", t.as_millis())),
+        },
+        None => probe.await.map_err(|e| e.to_string()),
+    };
+    let connect = match result {
+        Ok(c) => c,
+        Err(e) => {
+            let error = format!("Failed to CONNECT to {target} through {proxy}: {e}");
+            return Ok(response_err(app_handle, response, error, update_source));
+        }
+    };
+
+    let mut r = response.clone();
+    r.elapsed = start.elapsed().as_millis() as i32;
+    r.elapsed_headers = r.elapsed;
+    r.url = target.clone();
+    r.remote_addr = connect.proxy_addr.map(|a| a.to_string());
+    match connect.status() {
+        Some((version, status, reason)) => {
+            r.version = Some(version.to_string());
+            r.status = status as i32;
+            r.status_reason = Some(reason.to_string()).filter(|r| !r.is_empty());
+        }
+        None => {
+            let line = &connect.status_line;
+            r.warnings.push(format!("Proxy sent an invalid status line {line:?}"));
+        }
+    }
+    r.headers = connect
+        .headers
+        .into_iter()
+        .map(|(name, value)| HttpResponseHeader { name, value })
+        .collect();
+    r.request_headers = vec![HttpResponseHeader {
+        name: HOST.to_string(),
+        value: target,
+    }];
+    r.content_length = Some(0);
+    r.state = HttpResponseState::Closed;
+    Ok(app_handle.db().update_http_response_if_id(&r, update_source)?)
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PingResult {
+    pub reachable: bool,
+    pub status: Option<u16>,
+    /// Milliseconds until response headers were received
+    pub elapsed: u64,
+    pub error: Option<String>,
+}
+
+/// Check whether a URL is reachable, using the same proxy and TLS settings as a real send but
+/// without creating a response. Any response at all counts as reachable, whatever its status.
+pub async fn ping_url(workspace: &Workspace, settings: &Settings, url: &str) -> Result<PingResult> {
+    let client = build_client(workspace, settings, None, Default::default())?.client;
+    let timeout = request_timeout(workspace.setting_request_timeout).map_err(GenericError)?;
+    let send = |builder: reqwest::RequestBuilder| match timeout {
+        Some(t) => builder.timeout(t).send(),
+        None => builder.send(),
+    };
+    let start = std::time::Instant::now();
+
+    // Some servers don't implement HEAD, so fall back to GET for those
+    let mut result = send(client.head(url)).await;
+    let unsupported = [StatusCode::METHOD_NOT_ALLOWED, StatusCode::NOT_IMPLEMENTED];
+    if matches!(&result, Ok(r) if unsupported.contains(&r.status())) {
+        result = send(client.get(url)).await;
+    }
+
+    let elapsed = start.elapsed().as_millis() as u64;
+    Ok(match result {
+        Ok(r) => PingResult {
+            reachable: true,
+            status: Some(r.status().as_u16()),
+            elapsed,
+            error: None,
+        },
+        Err(e) => PingResult {
+            reachable: false,
+            status: None,
+            elapsed,
+            error: Some(error_chain(&e)),
+        },
+    })
+}
+
+/// A client and its cookie store, which can be shared across multiple sends so that connections
+/// and cookies carry over from one request to the next (eg. when running a folder).
+#[derive(Clone)]
+pub struct SharedClient {
+    pub client: reqwest::Client,
+    pub cookie_store: Option<Arc<CookieStoreMutex>>,
+    /// Local socket addresses of connections this client has already received responses on
+    pub local_addrs: Arc<std::sync::Mutex<HashSet<SocketAddr>>>,
+    /// Addresses that each hostname this client looked up resolved to
+    pub resolved_addrs: ResolvedAddrs,
+    /// Why servers' certificates failed validation, for clients that don't require it to pass
+    pub certificate_errors: CertificateErrors,
+    /// Local tunnel that plain-HTTP requests are proxied through, which lives as long as the client
+    pub tunnel: Option<Arc<ConnectTunnel>>,
+}
+
+/// Total length of the strings in a rendered body, which is roughly the number of bytes it sends
+fn rendered_body_size(body: &BTreeMap<String, Value>) -> usize {
+    body.values().map(json_value_size).sum()
+}
+
+fn json_value_size(v: &Value) -> usize {
+    match v {
+        Value::String(s) => s.len(),
+        Value::Array(a) => a.iter().map(json_value_size).sum(),
+        Value::Object(o) => o.values().map(json_value_size).sum(),
+        _ => 0,
+    }
+}
+
+/// Whether redirects are followed by `send_http_request` instead of the client. The client can
+/// only follow or stop, so it's used unless the method must be preserved.
+fn follows_redirects_manually(workspace: &Workspace) -> bool {
+    workspace.setting_follow_redirects && workspace.setting_redirect_preserve_method
+}
+
+/// Redirect policy for the client. HTTPS to HTTP downgrades fail the request unless allowed,
+/// including when redirects are followed manually, since the client sees every redirect first.
+fn redirect_policy(workspace: &Workspace) -> Policy {
+    if !workspace.setting_follow_redirects {
+        return Policy::none();
+    }
+
+    let manual = follows_redirects_manually(workspace);
+    let allow_downgrade = workspace.setting_redirect_allow_downgrade;
+    Policy::custom(move |attempt| {
+        let from = attempt.previous().last().map(|u| u.scheme()).unwrap_or_default();
+        if !allow_downgrade && is_downgrade(from, attempt.url().scheme()) {
+            let msg = format!(
+                "Blocked redirect from HTTPS to HTTP ({}). Enable \"Allow HTTPS to HTTP \
+                 Redirects\" in workspace settings to follow it.",
+                attempt.url()
+            );
+            attempt.error(msg)
+        } else if manual {
+            attempt.stop()
+        } else if attempt.previous().len() > MAX_REDIRECTS {
+            attempt.error("Too many redirects")
+        } else {
+            attempt.follow()
+        }
+    })
+}
+
+/// Build the request that follows a redirect response, or None if it has no usable Location.
+/// Credentials are dropped when redirected to a different host, like the client does.
+fn redirect_request(mut req: reqwest::Request, response: &Response) -> Option<reqwest::Request> {
+    let location = response.headers().get(LOCATION)?.to_str().ok()?;
+    let url = response.url().join(location).ok()?;
+
+    let status = response.status().as_u16();
+    let method = Method::from_str(&redirect_method(status, req.method().as_str(), true)).ok()?;
+    if method != *req.method() {
+        *req.body_mut() = None;
+        req.headers_mut().remove(CONTENT_TYPE);
+        req.headers_mut().remove(CONTENT_LENGTH);
+    }
+
+    let same_origin = url.host_str() == req.url().host_str()
+        && url.port_or_known_default() == req.url().port_or_known_default();
+    if !same_origin {
+        req.headers_mut().remove(AUTHORIZATION);
+        req.headers_mut().remove(COOKIE);
+        req.headers_mut().remove(PROXY_AUTHORIZATION);
+    }
+
+    *req.method_mut() = method;
+    *req.url_mut() = url;
+    Some(req)
+}
+
+/// Latest response to a request with a body that can be reused if the server says the resource
+/// hasn't changed
+fn latest_cached_response<R: Runtime>(
+    window: &WebviewWindow<R>,
+    request_id: &str,
+) -> Option<HttpResponse> {
+    let responses = match window.db().list_http_responses_for_request(request_id, None) {
+        Ok(r) => r,
+        Err(e) => {
+            warn!("Failed to list responses to revalidate: {e}");
+            return None;
+        }
+    };
+    responses
+        .into_iter()
+        .filter(|r| is_cacheable(r) && r.body_path.as_ref().is_some_and(|p| Path::new(p).exists()))
+        .max_by_key(|r| r.created_at)
+}
+
+/// Get the local address of the connection a response came in on, and whether that connection
+/// was reused. Each new connection gets its own ephemeral local port, so seeing the same local
+/// address twice on one client means the connection was kept alive and reused.
+fn connection_info(
+    response: &Response,
+    local_addrs: &std::sync::Mutex<HashSet<SocketAddr>>,
+) -> (Option<SocketAddr>, bool) {
+    let local_addr = match response.extensions().get::<HttpInfo>() {
+        Some(info) => info.local_addr(),
+        None => return (None, false),
+    };
+    let reused = !local_addrs.lock().unwrap().insert(local_addr);
+    (Some(local_addr), reused)
+}
+
+/// Settings with the proxy rendered for an environment, since its URLs and credentials can
+/// reference variables
+pub async fn render_proxy_settings<R: Runtime>(
+    window: &WebviewWindow<R>,
+    settings: Settings,
+    base_environment: &Environment,
+    environment: Option<&Environment>,
+) -> Result<Settings> {
+    let proxy = match &settings.proxy {
+        Some(p) => p,
+        None => return Ok(settings),
+    };
+    let cb = PluginTemplateCallback::new(
+        window.app_handle(),
+        &WindowContext::from_window(window),
+        RenderPurpose::Send,
+    );
+    let proxy = render_proxy_setting(proxy, base_environment, environment, &cb).await?;
+    proxy.validate()?;
+    Ok(Settings {
+        proxy: Some(proxy),
+        ..settings
+    })
+}
+
+/// Copy of a cookie jar with its cookie values rendered for an environment. Only the client gets
+/// the rendered values, while the jar keeps its templates.
+pub async fn render_cookies<R: Runtime>(
+    window: &WebviewWindow<R>,
+    cookie_jar: Option<&CookieJar>,
+    base_environment: &Environment,
+    environment: Option<&Environment>,
+) -> Result<Option<CookieJar>> {
+    let cookie_jar = match cookie_jar {
+        Some(j) => j,
+        None => return Ok(None),
+    };
+    let cb = PluginTemplateCallback::new(
+        window.app_handle(),
+        &WindowContext::from_window(window),
+        RenderPurpose::Send,
+    );
+    Ok(Some(render_cookie_jar(cookie_jar, base_environment, environment, &cb).await?))
+}
+
+/// Cookies to save back to a jar after a request. Cookies with a template for a value were sent
+/// rendered, so the jar keeps the template, unless the server set the cookie again.
+fn with_cookie_templates(
+    cookies: Vec<Cookie>,
+    jar: &CookieJar,
+    set_cookies: &[Cookie],
+) -> Vec<Cookie> {
+    cookies
+        .into_iter()
+        .map(|c| {
+            let template =
+                jar.cookies.iter().find(|t| t.is_same_cookie(&c) && t.value().contains("${["));
+            match template {
+                Some(t) if !set_cookies.iter().any(|s| s.is_same_cookie(&c)) => t.clone(),
+                _ => c,
+            }
+        })
+        .collect()
+}
+
+/// Settings of a single request that its client is built with, so it can't share one
+#[derive(Default)]
+pub struct ClientOverrides<'a> {
+    /// Connect to an address instead of looking up a hostname, for sending a different name with
+    /// SNI than the host being connected to
+    pub resolve: Option<(&'a str, SocketAddr)>,
+    /// PEM text of CA certificates to trust in addition to the workspace's
+    pub ca_cert_pem: Option<&'a str>,
+}
+
+impl ClientOverrides<'_> {
+    fn is_empty(&self) -> bool {
+        self.resolve.is_none() && self.ca_cert_pem.is_none()
+    }
+}
+
+/// Build a client for the workspace's settings, with the overrides of a single request, if any
+pub fn build_client(
+    workspace: &Workspace,
+    settings: &Settings,
+    cookie_jar: Option<&CookieJar>,
+    overrides: ClientOverrides,
+) -> Result<SharedClient> {
+    let mut client_builder = reqwest::Client::builder()
+        .redirect(redirect_policy(workspace))
+        .connection_verbose(true)
+        // Bodies are decoded while they're read instead, to measure their size on the wire
+        .gzip(false)
+        .brotli(false)
+        .deflate(false)
+        .referer(false)
+        .tls_info(true);
+
+    if workspace.setting_title_case_headers {
+        client_builder = client_builder.http1_title_case_headers();
+    }
+
+    // Only ever raise the HTTP/2 limit, so smaller limits are enforced once the headers arrive,
+    // with an error that says which limit was hit
+    if workspace.setting_max_header_size > 0 {
+        let size = (workspace.setting_max_header_size as u32).max(HTTP2_MAX_HEADER_LIST_SIZE);
+        client_builder = client_builder.http2_max_header_list_size(size);
+    }
+
+    if let Some((domain, addr)) = overrides.resolve {
+        client_builder = client_builder.resolve(domain, addr);
+    }
+
+    // Only matters for clients that are reused, like the one shared by a folder run
+    client_builder = match workspace.setting_pool_idle_timeout.max(0) as u64 {
+        0 => client_builder.pool_max_idle_per_host(0),
+        ms => client_builder.pool_idle_timeout(Duration::from_millis(ms)),
+    };
+
+    // Use platform-native verifier (or the workspace's CA bundle) to validate certificates. Without
+    // validation, certificates are still checked the same way, but only to report why they fail.
+    let certificate_errors = CertificateErrors::default();
+    let certs = match workspace.setting_validate_certificates {
+        true => TlsCertificates {
+            ca_cert_pem: overrides.ca_cert_pem.map(|s| s.to_string()),
+            ..tls_certificates(workspace)
+        },
+        false => TlsCertificates {
+            ca_cert_pem: overrides.ca_cert_pem.map(|s| s.to_string()),
+            insecure_hosts: Vec::new(),
+            report_errors: Some(certificate_errors.clone()),
+            ..tls_certificates(workspace)
+        },
+    };
+    let mut config = get_tls_config(&certs).map_err(GenericError)?;
+    // The QUIC client uses this config too, and servers only answer over HTTP/3 when `h3` is
+    // offered. Connections over TCP pick one of the others.
+    if workspace.setting_enable_http3 {
+        config.alpn_protocols = HTTP3_ALPN_PROTOCOLS.iter().map(|p| p.to_vec()).collect();
+    }
+    client_builder = client_builder.use_preconfigured_tls(config);
+
+    let mut tunnel = None;
+    match settings.proxy.clone() {
+        Some(ProxySetting::Disabled) => client_builder = client_builder.no_proxy(),
+        Some(ProxySetting::Enabled {
+            http,
+            https,
+            auth,
+            always_connect,
+        }) => {
+            debug!("Using proxy http={http} https={https} always_connect={always_connect}");
+            if always_connect && !http.is_empty() {
+                // Send plain HTTP through a local tunnel, which handles the proxy auth itself
+                let auth = auth.as_ref().map(|a| (a.user.as_str(), a.password.as_str()));
+                let t = ConnectTunnel::start(&http, auth)
+                    .map_err(|e| GenericError(format!("Failed to start proxy tunnel: {e}")))?;
+                let tunnel_url = format!("http://{}", t.addr());
+                client_builder = client_builder.proxy(Proxy::http(tunnel_url)?);
+                tunnel = Some(Arc::new(t));
+            }
+            let http = if always_connect { String::new() } else { http };
+
+            let mut proxy = Proxy::custom(move |url| {
+                let http = if http.is_empty() { None } else { Some(http.to_owned()) };
+                let https = if https.is_empty() { None } else { Some(https.to_owned()) };
+                let proxy_url = match (url.scheme(), http, https) {
+                    ("http", Some(proxy_url), _) => Some(proxy_url),
+                    ("https", _, Some(proxy_url)) => Some(proxy_url),
+                    _ => None,
+                };
+                proxy_url
+            });
+
+            if let Some(ProxySettingAuth { user, password }) = auth {
+                debug!("Using proxy auth");
+                proxy = proxy.basic_auth(user.as_str(), password.as_str());
+            }
+
+            client_builder = client_builder.proxy(proxy);
+        }
+        None => {} // Nothing to do for this one, as it is the default
+    }
+
+    // Add cookie store if specified
+    let cookie_store = match cookie_jar {
+        Some(cj) => {
+            // HACK: Can't construct Cookie without serde, so we have to do this
+            let cookies = cj
+                .cookies
+                .iter()
+                .map(|cookie| {
+                    let json_cookie = serde_json::to_value(cookie).unwrap();
+                    serde_json::from_value(json_cookie).expect("Failed to deserialize cookie")
+                })
+                .map(|c| Ok(c))
+                .collect::<Vec<Result<_>>>();
+
+            let store = reqwest_cookie_store::CookieStore::from_cookies(cookies, true)?;
+            let cookie_store = Arc::new(CookieStoreMutex::new(store));
+            client_builder = client_builder.cookie_provider(Arc::clone(&cookie_store));
+
+            Some(cookie_store)
+        }
+        None => None,
+    };
+
+    let resolved_addrs = ResolvedAddrs::default();
+    client_builder = client_builder.dns_resolver(Arc::new(RecordingResolver {
+        resolved_addrs: resolved_addrs.clone(),
+    }));
+
+    Ok(SharedClient {
+        client: client_builder.build()?,
+        cookie_store,
+        local_addrs: Default::default(),
+        resolved_addrs,
+        certificate_errors,
+        tunnel,
+    })
+}
+
+/// Multipart part that streams a file while the request is sent, rather than reading it into
+/// memory first. Canceling the request drops the stream, which closes the file.
+async fn file_part(path: &str) -> std::io::Result<multipart::Part> {
+    let file = File::open(path).await?;
+    let len = file.metadata().await?.len();
+    let body = reqwest::Body::wrap_stream(ReaderStream::new(file));
+    Ok(multipart::Part::stream_with_length(body, len))
+}
+
+/// Bytes of a text body, in the charset its `Content-Type` names, or UTF-8 when it names none.
+/// Adds `; charset=utf-8` to text and JSON types first, if asked to.
+fn text_body(body: &str, headers: &mut HeaderMap, add_charset: bool) -> Result<Vec<u8>, String> {
+    let content_type = headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok()).unwrap_or_default();
+    if add_charset {
+        if let Some(v) = with_utf8_charset(content_type) {
+            if let Ok(v) = HeaderValue::from_str(&v) {
+                headers.insert(CONTENT_TYPE, v);
+            }
+        }
+    }
+
+    let content_type = headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok()).unwrap_or_default();
+    match content_type_charset(content_type) {
+        Some(charset) => encode_body(body, charset),
+        None => Ok(body.as_bytes().to_vec()),
+    }
+}
+
+/// Parse the Set-Cookie headers of a response into cookies, independent of any cookie jar. Invalid
+/// cookies, or ones the server isn't allowed to set for this URL, are skipped.
+fn parse_set_cookies(headers: &HeaderMap, url: &Url) -> Vec<Cookie> {
+    let mut store = reqwest_cookie_store::CookieStore::default();
+    for h in headers.get_all(SET_COOKIE) {
+        let raw = match h.to_str() {
+            Ok(raw) => raw,
+            Err(_) => continue,
+        };
+        if let Err(e) = store.parse(raw, url) {
+            debug!("Skipping invalid Set-Cookie header \"{raw}\": {e}");
+        }
+    }
+
+    // HACK: Can't construct Cookie without serde, so we have to do this
+    store
+        .iter_any()
+        .filter_map(|c| serde_json::to_value(c).ok())
+        .filter_map(|c| serde_json::from_value(c).ok())
+        .collect()
+}
+
+/// The extra certificates a workspace is configured to use for TLS, shared by HTTP and gRPC
+pub fn tls_certificates(workspace: &Workspace) -> TlsCertificates {
+    TlsCertificates {
+        ca_bundle_path: workspace.setting_ca_bundle_path.clone(),
+        ca_cert_pem: None,
+        client_cert_path: workspace.setting_client_cert_path.clone(),
+        client_key_path: workspace.setting_client_key_path.clone(),
+        insecure_hosts: insecure_hosts(workspace.setting_insecure_hosts.as_deref().unwrap_or("")),
+        report_errors: None,
+    }
+}
+
+/// Request with the authentication of the auth profile it uses, if any. A profile that doesn't
+/// exist, like one that wasn't synced along with the request, leaves the request's own.
+fn with_auth_profile<R: Runtime>(window: &WebviewWindow<R>, request: &HttpRequest) -> HttpRequest {
+    let mut request = request.clone();
+    let profile_id = match request.auth_profile_id.clone() {
+        Some(id) => id,
+        None => return request,
+    };
+    match window.db().get_auth_profile(&profile_id) {
+        Ok(p) => {
+            request.authentication = p.authentication;
+            request.authentication_type = p.authentication_type;
+        }
+        Err(e) => warn!("Failed to get auth profile {profile_id}: {e}"),
+    }
+    request
+}
+
+type ResolvedAddrs = Arc<std::sync::Mutex<HashMap<String, Vec<IpAddr>>>>;
+
+/// DNS resolver that looks hostnames up the same way as the default one, but also keeps every
+/// address each one resolved to, since the response only says which one was connected to
+struct RecordingResolver {
+    resolved_addrs: ResolvedAddrs,
+}
+
+impl Resolve for RecordingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(lookup_host(name.as_str().to_string(), self.resolved_addrs.clone()))
+    }
+}
+
+async fn lookup_host(
+    host: String,
+    resolved_addrs: ResolvedAddrs,
+) -> std::result::Result<Addrs, Box<dyn std::error::Error + Send + Sync>> {
+    // The port is filled in by the connector
+    let addrs = tokio::net::lookup_host((host.as_str(), 0)).await?.collect::<Vec<_>>();
+    resolved_addrs.lock().unwrap().insert(host, addrs.iter().map(|a| a.ip()).collect());
+    Ok(Box::new(addrs.into_iter()))
+}
+
+/// Connection details for a request whose TLS server name is overridden
+struct ServerNameTarget {
+    /// Name sent with SNI, which replaces the URL's host
+    server_name: String,
+    /// Address of the URL's original host, which the server name resolves to instead
+    addr: SocketAddr,
+    /// Original host and port of the URL, which is still sent as the Host header
+    authority: String,
+}
+
+/// Point the URL at the server name, resolving it to the URL's original host so the connection
+/// still goes there while SNI (and certificate validation) use the server name
+async fn resolve_server_name(
+    url: &mut Url,
+    server_name: &str,
+) -> std::result::Result<ServerNameTarget, String> {
+    let host = url.host_str().unwrap_or_default().to_string();
+    let port = url.port_or_known_default().unwrap_or(443);
+    let authority = match url.port() {
+        Some(p) => format!("{host}:{p}"),
+        None => host.clone(),
+    };
+
+    let ip = host.trim_start_matches('[').trim_end_matches(']');
+    let addr = match ip.parse::<IpAddr>() {
+        Ok(ip) => SocketAddr::new(ip, port),
+        Err(_) => tokio::net::lookup_host((host.as_str(), port))
+            .await
+            .map_err(|e| format!("Failed to resolve {host}: {e}"))?
+            .next()
+            .ok_or_else(|| format!("No addresses found for {host}"))?,
+    };
+
+    url.set_host(Some(server_name))
+        .map_err(|e| format!("Invalid TLS server name {server_name:?}: {e}"))?;
+    Ok(ServerNameTarget {
+        server_name: server_name.to_string(),
+        addr,
+        authority,
+    })
+}
+
+/// Send a request over HTTP/3, giving up after [`HTTP3_FALLBACK_DELAY`] so it can be sent over
+/// TCP instead
+async fn execute_http3(
+    client: &reqwest::Client,
+    req: reqwest::Request,
+) -> std::result::Result<Response, String> {
+    match tokio::time::timeout(HTTP3_FALLBACK_DELAY, client.execute(req)).await {
+        Ok(result) => result.map_err(|e| error_chain(&e)),
+        Err(_) => Err(format!("no response within {}s", HTTP3_FALLBACK_DELAY.as_secs())),
+    }
+}
+
+/// Why a rendered request shouldn't be sent, if its URL, query parameters or headers still contain
+/// a template marker
+fn unresolved_template_error(
+    request: &HttpRequest,
+    environment: Option<&Environment>,
+) -> Option<String> {
+    let environment_id = environment.map(|e| e.id.as_str());
+    let mut fields = vec![("URL".to_string(), request.url.as_str())];
+    for p in request.url_parameters.iter().filter(|p| p.enabled && !p.name.is_empty()) {
+        fields.push((format!("query parameter \"{}\"", p.name), p.name.as_str()));
+        fields.push((format!("query parameter \"{}\"", p.name), p.value.as_str()));
+    }
+    for h in request.headers.iter().filter(|h| h.enabled && h.in_environment(environment_id)) {
+        fields.push((format!("header \"{}\"", h.name), h.name.as_str()));
+        fields.push((format!("header \"{}\"", h.name), h.value.as_str()));
+    }
+
+    fields.into_iter().find_map(|(field, text)| {
+        let marker = unresolved_template(text)?;
+        Some(format!("Not sent because the {field} still contains the template {marker}"))
+    })
+}
+
+/// Error for a URL that failed to parse, pointing at the invalid part when it can be found
+fn url_parse_error(url: &str, e: impl ToString) -> String {
+    let detail = find_url_error(url).map(|e| e.to_string()).unwrap_or_else(|| e.to_string());
+    format!("Failed to parse URL \"{url}\": {detail}")
+}
+
 fn ensure_proto(url_str: &str) -> String {
     if url_str.starts_with("http://") || url_str.starts_with("https://") {
         return url_str.to_string();
@@ -684,24 +2137,3 @@ fn ensure_proto(url_str: &str) -> String {
 
     format!("http://{url_str}")
 }
-
-fn get_bool(v: &Value, key: &str, fallback: bool) -> bool {
-    match v.get(key) {
-        None => fallback,
-        Some(v) => v.as_bool().unwrap_or(fallback),
-    }
-}
-
-fn get_str<'a>(v: &'a Value, key: &str) -> &'a str {
-    match v.get(key) {
-        None => "",
-        Some(v) => v.as_str().unwrap_or_default(),
-    }
-}
-
-fn get_str_h<'a>(v: &'a BTreeMap<String, Value>, key: &str) -> &'a str {
-    match v.get(key) {
-        None => "",
-        Some(v) => v.as_str().unwrap_or_default(),
-    }
-}
@@ -4,17 +4,25 @@ extern crate objc;
 use crate::encoding::read_response_body;
 use crate::error::Error::GenericError;
 use crate::grpc::metadata_to_map;
-use crate::http_request::send_http_request;
+use crate::http_request::{
+    NdjsonEvent, PingResult, SavedHttpResponse, SendOptions, ping_url, render_proxy_settings,
+    send_http_request, send_http_request_to_file, tls_certificates,
+};
 use crate::notifications::YaakNotifier;
-use crate::render::{render_grpc_request, render_template};
+use crate::render::{render_grpc_request, render_http_request, render_template};
+use crate::runner::{
+    ChainRunResult, FolderRunResult, run_chain, run_folder, run_with_data, send_to_environments,
+};
 use crate::updates::{UpdateMode, UpdateTrigger, YaakUpdater};
 use crate::uri_scheme::handle_uri_scheme;
+use crate::variable_usage::{VariableUsageReport, analyze_variable_usage};
+use chrono::Utc;
 use error::Result as YaakResult;
 use eventsource_client::{EventParser, SSE};
 use log::{debug, error, warn};
 use std::collections::{BTreeMap, HashMap};
 use std::fs::{File, create_dir_all};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::Duration;
 use std::{fs, panic};
@@ -27,17 +35,25 @@ use tauri_plugin_window_state::{AppHandleExt, StateFlags};
 use tokio::fs::read_to_string;
 use tokio::sync::Mutex;
 use tokio::task::block_in_place;
+use yaak_common::tls::get_tls_config;
 use yaak_common::window::WorkspaceWindowTrait;
 use yaak_grpc::manager::{DynamicMessage, GrpcHandle};
 use yaak_grpc::{Code, ServiceDefinition, deserialize_message, serialize_message};
+use yaak_models::bodies::TextBody;
 use yaak_models::models::{
     CookieJar, Environment, Folder, GrpcConnection, GrpcConnectionState, GrpcEvent, GrpcEventType,
-    GrpcRequest, HttpRequest, HttpResponse, HttpResponseState, Plugin, WebsocketRequest, Workspace,
-    WorkspaceMeta,
+    GrpcRequest, HttpRequest, HttpResponse, HttpResponseErrorKind, HttpResponseState, Plugin,
+    WebsocketRequest, Workspace, WorkspaceMeta,
 };
+use yaak_http::data_file::parse_data_file;
+use yaak_http::ndjson::NdjsonParser;
+use yaak_http::openapi::openapi_operation;
+use yaak_http::replay::replay_request;
+use yaak_http::trace::format_http_trace;
 use yaak_models::query_manager::QueryManagerExt;
 use yaak_models::util::{
-    BatchUpsertResult, UpdateSource, get_workspace_export_resources, maybe_gen_id, maybe_gen_id_opt,
+    BatchUpsertResult, UpdateSource, get_workspace_export_resources, json_merge_patch,
+    maybe_gen_id, maybe_gen_id_opt,
 };
 use yaak_plugins::events::{
     BootResponse, CallHttpAuthenticationRequest, CallHttpRequestActionRequest, FilterResponse,
@@ -57,12 +73,15 @@ mod grpc;
 mod history;
 mod http_request;
 mod notifications;
+mod oauth2;
 mod plugin_events;
 mod render;
+mod runner;
 #[cfg(target_os = "macos")]
 mod tauri_plugin_mac_window;
 mod updates;
 mod uri_scheme;
+mod variable_usage;
 mod window;
 mod window_menu;
 
@@ -126,6 +145,43 @@ async fn cmd_render_template<R: Runtime>(
     Ok(result)
 }
 
+/// Preview the document that results from applying a request's JSON body, as a merge patch
+/// (RFC 7386), to `base`
+#[tauri::command]
+async fn cmd_preview_json_merge_patch<R: Runtime>(
+    window: WebviewWindow<R>,
+    app_handle: AppHandle<R>,
+    base: &str,
+    request: HttpRequest,
+    environment_id: Option<&str>,
+) -> YaakResult<String> {
+    let environment = match environment_id {
+        Some(id) => app_handle.db().get_environment(id).ok(),
+        None => None,
+    };
+    let base_environment = app_handle.db().get_base_environment(&request.workspace_id)?;
+    let request = render_http_request(
+        &request,
+        &base_environment,
+        environment.as_ref(),
+        &PluginTemplateCallback::new(
+            &app_handle,
+            &WindowContext::from_window(&window),
+            RenderPurpose::Preview,
+        ),
+    )
+    .await?;
+
+    let patch = TextBody::try_from(&request.body).map(|b| b.text).unwrap_or_default();
+    let patch: serde_json::Value = serde_json::from_str(&patch)
+        .map_err(|e| GenericError(format!("Request body is not valid JSON: {e}")))?;
+    let base: serde_json::Value = serde_json::from_str(base)
+        .map_err(|e| GenericError(format!("Base document is not valid JSON: {e}")))?;
+
+    serde_json::to_string_pretty(&json_merge_patch(&base, &patch))
+        .map_err(|e| GenericError(e.to_string()))
+}
+
 #[tauri::command]
 async fn cmd_dismiss_notification<R: Runtime>(
     window: WebviewWindow<R>,
@@ -164,6 +220,8 @@ async fn cmd_grpc_reflect<R: Runtime>(
         .await?;
 
     let uri = safe_uri(&req.url);
+    let workspace = app_handle.db().get_workspace(&req.workspace_id)?;
+    let tls_config = get_tls_config(&tls_certificates(&workspace)).map_err(GenericError)?;
 
     Ok(grpc_handle
         .lock()
@@ -172,6 +230,7 @@ async fn cmd_grpc_reflect<R: Runtime>(
             &req.id,
             &uri,
             &proto_files.iter().map(|p| PathBuf::from_str(p).unwrap()).collect(),
+            &tls_config,
         )
         .await
         .map_err(|e| GenericError(e.to_string()))?)
@@ -243,6 +302,9 @@ async fn cmd_grpc_go<R: Runtime>(
         }
     }
 
+    let workspace = app_handle.db().get_workspace(&request.workspace_id)?;
+    let tls_config = get_tls_config(&tls_certificates(&workspace)).map_err(GenericError)?;
+
     let conn = app_handle.db().upsert_grpc_connection(
         &GrpcConnection {
             workspace_id: request.workspace_id.clone(),
@@ -289,6 +351,7 @@ async fn cmd_grpc_go<R: Runtime>(
             &request.clone().id,
             uri.as_str(),
             &proto_files.iter().map(|p| PathBuf::from_str(p).unwrap()).collect(),
+            &tls_config,
         )
         .await;
 
@@ -738,7 +801,58 @@ async fn cmd_send_ephemeral_request<R: Runtime>(
         }
     });
 
-    send_http_request(&window, &request, &response, environment, cookie_jar, &mut cancel_rx).await
+    let options = SendOptions::default();
+    send_http_request(
+        &window,
+        &request,
+        &response,
+        environment,
+        cookie_jar,
+        options,
+        &mut cancel_rx,
+    )
+    .await
+}
+
+/// Render and build a request without sending it, to check that it's valid while offline. The
+/// response has the URL and headers that would have been sent, or the error that stopped it.
+#[tauri::command]
+async fn cmd_dry_run_http_request<R: Runtime>(
+    request: HttpRequest,
+    environment_id: Option<&str>,
+    cookie_jar_id: Option<&str>,
+    skip_auth: bool,
+    window: WebviewWindow<R>,
+    app_handle: AppHandle<R>,
+) -> YaakResult<HttpResponse> {
+    // Without an ID, the response is never stored
+    let response = HttpResponse::default();
+    let environment = match environment_id {
+        Some(id) => Some(app_handle.db().get_environment(id)?),
+        None => None,
+    };
+    let cookie_jar = match cookie_jar_id {
+        Some(id) => Some(app_handle.db().get_cookie_jar(id)?),
+        None => None,
+    };
+
+    // Nothing is sent, so there's nothing to cancel
+    let (_cancel_tx, mut cancel_rx) = tokio::sync::watch::channel(false);
+    let options = SendOptions {
+        dry_run: true,
+        skip_auth,
+        ..Default::default()
+    };
+    send_http_request(
+        &window,
+        &request,
+        &response,
+        environment,
+        cookie_jar,
+        options,
+        &mut cancel_rx,
+    )
+    .await
 }
 
 #[tauri::command]
@@ -797,6 +911,18 @@ async fn cmd_get_sse_events(file_path: &str) -> Result<Vec<ServerSentEvent>, Str
     Ok(events)
 }
 
+#[tauri::command]
+async fn cmd_get_ndjson_events(
+    response_id: &str,
+    file_path: &str,
+) -> Result<Vec<NdjsonEvent>, String> {
+    let body = fs::read(file_path).map_err(|e| e.to_string())?;
+    let mut parser = NdjsonParser::default();
+    let mut lines = parser.push(&body);
+    lines.extend(parser.finish());
+    Ok(lines.into_iter().map(|l| NdjsonEvent::new(response_id, l)).collect())
+}
+
 #[tauri::command]
 async fn cmd_import_data<R: Runtime>(
     window: WebviewWindow<R>,
@@ -1021,6 +1147,31 @@ async fn cmd_save_response<R: Runtime>(
     Ok(())
 }
 
+#[tauri::command]
+async fn cmd_save_http_trace<R: Runtime>(
+    app_handle: AppHandle<R>,
+    response_id: &str,
+    filepath: &str,
+) -> YaakResult<()> {
+    let response = app_handle.db().get_http_response(response_id)?;
+    let request = app_handle.db().get_http_request(&response.request_id)?;
+
+    let trace = format_http_trace(&request, &response);
+    fs::write(filepath, trace).map_err(|e| GenericError(e.to_string()))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn cmd_http_request_openapi<R: Runtime>(
+    app_handle: AppHandle<R>,
+    request_id: &str,
+) -> YaakResult<String> {
+    let request = app_handle.db().get_http_request(request_id)?;
+    let operation = openapi_operation(&request);
+    serde_json::to_string_pretty(&operation).map_err(|e| GenericError(e.to_string()))
+}
+
 #[tauri::command]
 async fn cmd_send_http_request<R: Runtime>(
     app_handle: AppHandle<R>,
@@ -1031,6 +1182,8 @@ async fn cmd_send_http_request<R: Runtime>(
     //   condition where the user may have just edited a field before sending
     //   that has not yet been saved in the DB.
     request: HttpRequest,
+    seed: Option<u32>,
+    download_path: Option<String>,
 ) -> YaakResult<HttpResponse> {
     let response = app_handle.db().upsert_http_response(
         &HttpResponse {
@@ -1064,7 +1217,261 @@ async fn cmd_send_http_request<R: Runtime>(
         None => None,
     };
 
-    send_http_request(&window, &request, &response, environment, cookie_jar, &mut cancel_rx).await
+    let options = SendOptions {
+        seed,
+        download_path: download_path.map(PathBuf::from),
+        ..Default::default()
+    };
+    send_http_request(
+        &window,
+        &request,
+        &response,
+        environment,
+        cookie_jar,
+        options,
+        &mut cancel_rx,
+    )
+    .await
+}
+
+/// Send a request and save its body to a file, for requests that are always downloads
+#[tauri::command]
+async fn cmd_send_http_request_to_file<R: Runtime>(
+    app_handle: AppHandle<R>,
+    window: WebviewWindow<R>,
+    environment_id: Option<&str>,
+    cookie_jar_id: Option<&str>,
+    request: HttpRequest,
+    filepath: &str,
+) -> YaakResult<SavedHttpResponse> {
+    let response = app_handle.db().upsert_http_response(
+        &HttpResponse {
+            request_id: request.id.clone(),
+            workspace_id: request.workspace_id.clone(),
+            ..Default::default()
+        },
+        &UpdateSource::from_window(&window),
+    )?;
+
+    let (cancel_tx, mut cancel_rx) = tokio::sync::watch::channel(false);
+    app_handle.listen_any(format!("cancel_http_response_{}", response.id), move |_event| {
+        if let Err(e) = cancel_tx.send(true) {
+            warn!("Failed to send cancel event for request {e:?}");
+        }
+    });
+
+    let environment = match environment_id {
+        Some(id) => Some(app_handle.db().get_environment(id)?),
+        None => None,
+    };
+    let cookie_jar = match cookie_jar_id {
+        Some(id) => Some(app_handle.db().get_cookie_jar(id)?),
+        None => None,
+    };
+
+    send_http_request_to_file(
+        &window,
+        &request,
+        &response,
+        environment,
+        cookie_jar,
+        Path::new(filepath),
+        &mut cancel_rx,
+    )
+    .await
+}
+
+/// Send the request that produced a response again, as it was sent then. The environment is only
+/// needed to render the body, since the stored URL and headers are already rendered.
+#[tauri::command]
+async fn cmd_resend_http_response<R: Runtime>(
+    app_handle: AppHandle<R>,
+    window: WebviewWindow<R>,
+    response_id: &str,
+    environment_id: Option<&str>,
+) -> YaakResult<HttpResponse> {
+    let (request, response) = {
+        let db = app_handle.db();
+        let old_response = db.get_http_response(response_id)?;
+        let request = db.get_http_request(&old_response.request_id)?;
+        let request = replay_request(&request, &old_response);
+        let response = db.upsert_http_response(
+            &HttpResponse {
+                request_id: request.id.clone(),
+                workspace_id: request.workspace_id.clone(),
+                ..Default::default()
+            },
+            &UpdateSource::from_window(&window),
+        )?;
+        (request, response)
+    };
+
+    let (cancel_tx, mut cancel_rx) = tokio::sync::watch::channel(false);
+    app_handle.listen_any(format!("cancel_http_response_{}", response.id), move |_event| {
+        if let Err(e) = cancel_tx.send(true) {
+            warn!("Failed to send cancel event for request {e:?}");
+        }
+    });
+
+    let environment = match environment_id {
+        Some(id) => Some(app_handle.db().get_environment(id)?),
+        None => None,
+    };
+
+    // The stored headers already have the auth and cookies that were sent
+    let options = SendOptions {
+        skip_auth: true,
+        ..Default::default()
+    };
+    send_http_request(&window, &request, &response, environment, None, options, &mut cancel_rx)
+        .await
+}
+
+#[tauri::command]
+async fn cmd_ping_url<R: Runtime>(
+    app_handle: AppHandle<R>,
+    window: WebviewWindow<R>,
+    workspace_id: &str,
+    environment_id: Option<&str>,
+    url: &str,
+) -> YaakResult<PingResult> {
+    let (settings, workspace, base_environment) = {
+        let db = app_handle.db();
+        (db.get_settings(), db.get_workspace(workspace_id)?, db.get_base_environment(workspace_id)?)
+    };
+    let environment = match environment_id {
+        Some(id) => Some(app_handle.db().get_environment(id)?),
+        None => None,
+    };
+    // Pinged through the same proxy as a send, so the proxy is rendered for the environment
+    let settings =
+        render_proxy_settings(&window, settings, &base_environment, environment.as_ref()).await?;
+    ping_url(&workspace, &settings, url).await
+}
+
+#[tauri::command]
+async fn cmd_run_chain<R: Runtime>(
+    app_handle: AppHandle<R>,
+    window: WebviewWindow<R>,
+    request_id: &str,
+    environment_id: Option<&str>,
+    cookie_jar_id: Option<&str>,
+) -> YaakResult<ChainRunResult> {
+    let (cancel_tx, mut cancel_rx) = tokio::sync::watch::channel(false);
+    app_handle.listen_any(format!("cancel_chain_run_{}", request_id), move |_event| {
+        if let Err(e) = cancel_tx.send(true) {
+            warn!("Failed to send cancel event for chain run {e:?}");
+        }
+    });
+
+    let environment = match environment_id {
+        Some(id) => Some(app_handle.db().get_environment(id)?),
+        None => None,
+    };
+
+    let cookie_jar = match cookie_jar_id {
+        Some(id) => Some(app_handle.db().get_cookie_jar(id)?),
+        None => None,
+    };
+
+    run_chain(&window, request_id, environment, cookie_jar, &mut cancel_rx).await
+}
+
+#[tauri::command]
+async fn cmd_run_folder<R: Runtime>(
+    app_handle: AppHandle<R>,
+    window: WebviewWindow<R>,
+    folder_id: &str,
+    environment_id: Option<&str>,
+    cookie_jar_id: Option<&str>,
+    stop_on_error: bool,
+) -> YaakResult<FolderRunResult> {
+    let (cancel_tx, mut cancel_rx) = tokio::sync::watch::channel(false);
+    app_handle.listen_any(format!("cancel_folder_run_{}", folder_id), move |_event| {
+        if let Err(e) = cancel_tx.send(true) {
+            warn!("Failed to send cancel event for folder run {e:?}");
+        }
+    });
+
+    let environment = match environment_id {
+        Some(id) => Some(app_handle.db().get_environment(id)?),
+        None => None,
+    };
+
+    let cookie_jar = match cookie_jar_id {
+        Some(id) => Some(app_handle.db().get_cookie_jar(id)?),
+        None => None,
+    };
+
+    run_folder(&window, folder_id, environment, cookie_jar, stop_on_error, &mut cancel_rx).await
+}
+
+#[tauri::command]
+async fn cmd_run_with_data_file<R: Runtime>(
+    app_handle: AppHandle<R>,
+    window: WebviewWindow<R>,
+    file_path: &str,
+    environment_id: Option<&str>,
+    cookie_jar_id: Option<&str>,
+    request: HttpRequest,
+) -> YaakResult<Vec<HttpResponse>> {
+    let contents = read_to_string(file_path)
+        .await
+        .map_err(|e| GenericError(format!("Failed to read data file {file_path}: {e}")))?;
+    let rows = parse_data_file(file_path, &contents).map_err(GenericError)?;
+
+    let (cancel_tx, mut cancel_rx) = tokio::sync::watch::channel(false);
+    app_handle.listen_any(format!("cancel_data_run_{}", request.id), move |_event| {
+        if let Err(e) = cancel_tx.send(true) {
+            warn!("Failed to send cancel event for data file run {e:?}");
+        }
+    });
+
+    let environment = match environment_id {
+        Some(id) => Some(app_handle.db().get_environment(id)?),
+        None => None,
+    };
+
+    let cookie_jar = match cookie_jar_id {
+        Some(id) => Some(app_handle.db().get_cookie_jar(id)?),
+        None => None,
+    };
+
+    run_with_data(&window, &request, rows, environment, cookie_jar, &mut cancel_rx).await
+}
+
+#[tauri::command]
+async fn cmd_variable_usage<R: Runtime>(
+    app_handle: AppHandle<R>,
+    workspace_id: &str,
+) -> YaakResult<VariableUsageReport> {
+    let db = app_handle.db();
+    let http_requests = db.list_http_requests(workspace_id)?;
+    let environments = db.list_environments(workspace_id)?;
+    Ok(analyze_variable_usage(&http_requests, &environments))
+}
+
+#[tauri::command]
+async fn cmd_send_to_environments<R: Runtime>(
+    app_handle: AppHandle<R>,
+    window: WebviewWindow<R>,
+    environment_ids: Vec<String>,
+    cookie_jar_id: Option<&str>,
+    request: HttpRequest,
+) -> YaakResult<BTreeMap<String, HttpResponse>> {
+    let (cancel_tx, mut cancel_rx) = tokio::sync::watch::channel(false);
+    app_handle.listen_any(format!("cancel_environments_send_{}", request.id), move |_event| {
+        if let Err(e) = cancel_tx.send(true) {
+            warn!("Failed to send cancel event for environments send {e:?}");
+        }
+    });
+
+    let cookie_jar = match cookie_jar_id {
+        Some(id) => Some(app_handle.db().get_cookie_jar(id)?),
+        None => None,
+    };
+
+    send_to_environments(&window, &request, &environment_ids, cookie_jar, &mut cancel_rx).await
 }
 
 fn response_err<R: Runtime>(
@@ -1072,11 +1479,39 @@ fn response_err<R: Runtime>(
     response: &HttpResponse,
     error: String,
     update_source: &UpdateSource,
+) -> HttpResponse {
+    // Once the response has started, anything that goes wrong is with reading the body
+    let kind = match response.state {
+        HttpResponseState::Initialized => HttpResponseErrorKind::Connection,
+        _ => HttpResponseErrorKind::Body,
+    };
+    close_response_with_error(app_handle, response, error, kind, update_source)
+}
+
+/// Like `response_err`, but for failures before the request was sent, like a URL that doesn't
+/// parse, so they can be told apart from ones caused by the network or server
+fn preflight_err<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    response: &HttpResponse,
+    error: String,
+    update_source: &UpdateSource,
+) -> HttpResponse {
+    let kind = HttpResponseErrorKind::NotSent;
+    close_response_with_error(app_handle, response, error, kind, update_source)
+}
+
+fn close_response_with_error<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    response: &HttpResponse,
+    error: String,
+    kind: HttpResponseErrorKind,
+    update_source: &UpdateSource,
 ) -> HttpResponse {
     warn!("Failed to send request: {error:?}");
     let mut response = response.clone();
     response.state = HttpResponseState::Closed;
     response.error = Some(error.clone());
+    response.error_kind = Some(kind);
     response = app_handle
         .db()
         .update_http_response_if_id(&response, update_source)
@@ -1209,6 +1644,29 @@ async fn cmd_delete_all_http_responses<R: Runtime>(
         .delete_all_http_responses_for_request(request_id, &UpdateSource::from_window(&window))?)
 }
 
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeletedHttpResponses {
+    count: usize,
+    bytes: u64,
+}
+
+#[tauri::command]
+async fn cmd_delete_old_http_responses<R: Runtime>(
+    workspace_id: &str,
+    max_age_days: u32,
+    app_handle: AppHandle<R>,
+    window: WebviewWindow<R>,
+) -> YaakResult<DeletedHttpResponses> {
+    let before = Utc::now().naive_utc() - chrono::Duration::days(max_age_days as i64);
+    let (count, bytes) = app_handle.db().delete_http_responses_before(
+        workspace_id,
+        before,
+        &UpdateSource::from_window(&window),
+    )?;
+    Ok(DeletedHttpResponses { count, bytes })
+}
+
 #[tauri::command]
 async fn cmd_get_workspace_meta<R: Runtime>(
     app_handle: AppHandle<R>,
@@ -1335,33 +1793,47 @@ pub fn run() {
             cmd_curl_to_request,
             cmd_delete_all_grpc_connections,
             cmd_delete_all_http_responses,
+            cmd_delete_old_http_responses,
             cmd_delete_send_history,
             cmd_dismiss_notification,
+            cmd_dry_run_http_request,
             cmd_export_data,
             cmd_filter_response,
             cmd_format_json,
             cmd_get_http_authentication_summaries,
             cmd_get_http_authentication_config,
+            cmd_get_ndjson_events,
             cmd_get_sse_events,
             cmd_get_workspace_meta,
             cmd_grpc_go,
             cmd_grpc_reflect,
             cmd_http_request_actions,
+            cmd_http_request_openapi,
             cmd_import_data,
             cmd_install_plugin,
             cmd_metadata,
             cmd_new_child_window,
             cmd_new_main_window,
             cmd_parse_template,
+            cmd_ping_url,
             cmd_plugin_info,
+            cmd_preview_json_merge_patch,
             cmd_reload_plugins,
             cmd_render_template,
+            cmd_resend_http_response,
+            cmd_run_chain,
+            cmd_run_folder,
+            cmd_run_with_data_file,
+            cmd_save_http_trace,
             cmd_save_response,
             cmd_send_ephemeral_request,
             cmd_send_http_request,
+            cmd_send_http_request_to_file,
+            cmd_send_to_environments,
             cmd_template_functions,
             cmd_template_tokens_to_string,
             cmd_uninstall_plugin,
+            cmd_variable_usage,
         ])
         .register_uri_scheme_protocol("yaak", handle_uri_scheme)
         .build(tauri::generate_context!())
@@ -1384,6 +1856,12 @@ pub fn run() {
                         let _ = db.cancel_pending_grpc_connections();
                         let _ = db.cancel_pending_websocket_connections();
                     });
+
+                    // Purge models that were deleted too long ago to restore
+                    let db = app_handle.db();
+                    if let Err(e) = db.purge_deleted_models(&UpdateSource::Background) {
+                        warn!("Failed to purge deleted models {e:?}");
+                    }
                 }
                 RunEvent::WindowEvent {
                     event: WindowEvent::Focused(true),
@@ -0,0 +1,347 @@
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use crate::window::{CreateWindowConfig, create_window};
+use chrono::Utc;
+use http::header::{ACCEPT, USER_AGENT};
+use log::{info, warn};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use tauri::{Manager, Runtime, WebviewWindow};
+use tokio::sync::mpsc;
+use yaak_http::oauth2::{
+    PKCE_S256, append_query, authorization_code_from_redirect, code_challenge,
+    generate_code_verifier, parse_form_urlencoded,
+};
+use yaak_models::query_manager::QueryManagerExt;
+
+/// Name of the bundled OAuth 2.0 auth plugin, whose store the tokens are shared with so that its
+/// actions (copy token, delete token) keep working for flows handled here.
+const OAUTH2_PLUGIN_NAME: &str = "@yaakapp/auth-oauth2";
+pub(crate) const OAUTH2_AUTH_NAME: &str = "oauth2";
+
+/// Token as stored by the OAuth 2.0 plugin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredToken {
+    response: TokenResponse,
+    /// Unix time in milliseconds
+    expires_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    refresh_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_in: Option<i64>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+struct PkceConfig {
+    authorization_url: String,
+    access_token_url: String,
+    client_id: String,
+    client_secret: String,
+    redirect_uri: Option<String>,
+    scope: Option<String>,
+    state: Option<String>,
+    challenge_method: String,
+    code_verifier: Option<String>,
+    credentials_in_body: bool,
+    header_prefix: String,
+}
+
+/// Whether the OAuth 2.0 auth values describe an authorization code flow with PKCE, which is
+/// handled here instead of by the plugin.
+pub(crate) fn is_pkce_flow(auth_name: &str, values: &BTreeMap<String, Value>) -> bool {
+    let grant_type = string_arg(values, "grantType").unwrap_or("authorization_code".to_string());
+    auth_name == OAUTH2_AUTH_NAME
+        && grant_type == "authorization_code"
+        && values.get("usePkce").and_then(|v| v.as_bool()).unwrap_or(false)
+        && !values.get("disabled").and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+/// Authorization header value for an authorization code flow with PKCE. A cached token is used
+/// while it is valid, then refreshed if possible, and otherwise the user is sent through the
+/// authorization window again. Flow state is keyed by `context_id`, so each request has its own.
+pub(crate) async fn pkce_authorization_header<R: Runtime>(
+    window: &WebviewWindow<R>,
+    client: &reqwest::Client,
+    context_id: &str,
+    values: &BTreeMap<String, Value>,
+) -> Result<String> {
+    let config = PkceConfig::from_values(values);
+    if config.client_id.is_empty() {
+        return Err(GenericError("OAuth 2.0 client ID is required".to_string()));
+    }
+
+    let token = match get_token(window, context_id) {
+        Some(t) if !is_expired(&t) => t,
+        Some(t) if t.response.refresh_token.is_some() => {
+            match refresh_token(client, &config, &t).await? {
+                Some(t) => store_token(window, context_id, t)?,
+                None => {
+                    delete_token(window, context_id);
+                    authorize(window, client, context_id, &config).await?
+                }
+            }
+        }
+        _ => authorize(window, client, context_id, &config).await?,
+    };
+
+    Ok(format!("{} {}", config.header_prefix, token.response.access_token).trim().to_string())
+}
+
+async fn authorize<R: Runtime>(
+    window: &WebviewWindow<R>,
+    client: &reqwest::Client,
+    context_id: &str,
+    config: &PkceConfig,
+) -> Result<StoredToken> {
+    let verifier = config.code_verifier.clone().unwrap_or_else(generate_code_verifier);
+    let challenge = code_challenge(&verifier, &config.challenge_method);
+
+    let mut params = vec![
+        ("response_type", "code"),
+        ("client_id", config.client_id.as_str()),
+    ];
+    if let Some(redirect_uri) = &config.redirect_uri {
+        params.push(("redirect_uri", redirect_uri.as_str()));
+    }
+    if let Some(scope) = &config.scope {
+        params.push(("scope", scope.as_str()));
+    }
+    if let Some(state) = &config.state {
+        params.push(("state", state.as_str()));
+    }
+    params.push(("code_challenge", challenge.as_str()));
+    params.push(("code_challenge_method", config.challenge_method.as_str()));
+    let url = append_query(&config.authorization_url, &params);
+    info!("Authorizing {url}");
+
+    let (navigation_tx, mut navigation_rx) = mpsc::channel(128);
+    let (close_tx, mut close_rx) = mpsc::channel(128);
+    let label = format!("oauth2_{context_id}");
+    let auth_window = create_window(
+        window.app_handle(),
+        CreateWindowConfig {
+            url: &url,
+            label: &label,
+            title: "Authorize",
+            navigation_tx: Some(navigation_tx),
+            close_tx: Some(close_tx),
+            data_dir_key: Some(data_dir_key(window, context_id)),
+            ..Default::default()
+        },
+    );
+
+    let redirect_uri = config.redirect_uri.as_deref();
+    let state = config.state.as_deref();
+    let code = loop {
+        tokio::select! {
+            Some(url) = navigation_rx.recv() => {
+                match authorization_code_from_redirect(&url, redirect_uri, state) {
+                    Some(r) => break r.map_err(GenericError),
+                    None => continue,
+                }
+            }
+            _ = close_rx.recv() => {
+                break Err(GenericError("Authorization window closed".to_string()));
+            }
+        }
+    };
+    if let Err(e) = auth_window.close() {
+        warn!("Failed to close authorization window: {e}");
+    }
+
+    // The window's event handlers panic if nothing is receiving, so drain until it's gone
+    tauri::async_runtime::spawn(async move {
+        while navigation_rx.recv().await.is_some() {}
+        while close_rx.recv().await.is_some() {}
+    });
+
+    let mut form = vec![
+        ("grant_type", "authorization_code".to_string()),
+        ("code", code?),
+    ];
+    if let Some(redirect_uri) = &config.redirect_uri {
+        form.push(("redirect_uri", redirect_uri.to_string()));
+    }
+    form.push(("code_verifier", verifier));
+    let response = request_token(client, config, form)
+        .await?
+        .ok_or(GenericError("Failed to fetch access token with status=401".to_string()))?;
+    store_token(window, context_id, response)
+}
+
+/// Exchange a refresh token for a new access token. Returns `None` if the refresh token was
+/// rejected, in which case the user needs to authorize again.
+async fn refresh_token(
+    client: &reqwest::Client,
+    config: &PkceConfig,
+    token: &StoredToken,
+) -> Result<Option<TokenResponse>> {
+    let refresh_token = token.response.refresh_token.clone().unwrap_or_default();
+    let form = vec![
+        ("grant_type", "refresh_token".to_string()),
+        ("refresh_token", refresh_token.clone()),
+    ];
+    let response = request_token(client, config, form).await?;
+    Ok(response.map(|mut r| {
+        // Providers don't always rotate the refresh token, so keep the old one
+        r.refresh_token = r.refresh_token.or(Some(refresh_token));
+        r
+    }))
+}
+
+/// Send a token request, returning `None` if the token endpoint responds with a 401
+async fn request_token(
+    client: &reqwest::Client,
+    config: &PkceConfig,
+    mut form: Vec<(&str, String)>,
+) -> Result<Option<TokenResponse>> {
+    if let Some(scope) = &config.scope {
+        form.push(("scope", scope.to_string()));
+    }
+
+    let mut request = client
+        .post(&config.access_token_url)
+        .header(USER_AGENT, "yaak")
+        .header(ACCEPT, "application/x-www-form-urlencoded, application/json");
+    if config.credentials_in_body {
+        form.push(("client_id", config.client_id.to_string()));
+        form.push(("client_secret", config.client_secret.to_string()));
+    } else {
+        request = request.basic_auth(&config.client_id, Some(&config.client_secret));
+    }
+
+    let response = request.form(&form).send().await?;
+    let status = response.status();
+    if status == StatusCode::UNAUTHORIZED {
+        return Ok(None);
+    }
+    let body = response.text().await?;
+    if !status.is_success() {
+        return Err(GenericError(format!(
+            "Failed to fetch access token with status={}",
+            status.as_u16()
+        )));
+    }
+
+    parse_token_response(&body).map(Some).map_err(GenericError)
+}
+
+/// Token endpoints are supposed to return JSON, but some (like GitHub's) return a form
+fn parse_token_response(body: &str) -> std::result::Result<TokenResponse, String> {
+    let value = match serde_json::from_str::<Value>(body) {
+        Ok(v) => v,
+        Err(_) => Value::Object(
+            parse_form_urlencoded(body)
+                .into_iter()
+                .map(|(n, v)| match v.parse::<i64>() {
+                    Ok(i) => (n, Value::from(i)),
+                    Err(_) => (n, Value::String(v)),
+                })
+                .collect(),
+        ),
+    };
+
+    if let Some(error) = value.get("error").and_then(|e| e.as_str()) {
+        let description = value.get("error_description").and_then(|e| e.as_str());
+        return Err(format!(
+            "Failed to fetch access token with {error} -> {}",
+            description.unwrap_or_default()
+        ));
+    }
+
+    serde_json::from_value(value).map_err(|_| "Token not found in response".to_string())
+}
+
+fn is_expired(token: &StoredToken) -> bool {
+    match token.expires_at {
+        Some(expires_at) => Utc::now().timestamp_millis() > expires_at,
+        None => false,
+    }
+}
+
+fn get_token<R: Runtime>(window: &WebviewWindow<R>, context_id: &str) -> Option<StoredToken> {
+    let kv = window.db().get_plugin_key_value(OAUTH2_PLUGIN_NAME, &token_key(context_id))?;
+    serde_json::from_str(&kv.value).ok()
+}
+
+fn store_token<R: Runtime>(
+    window: &WebviewWindow<R>,
+    context_id: &str,
+    response: TokenResponse,
+) -> Result<StoredToken> {
+    let expires_at = response.expires_in.map(|s| Utc::now().timestamp_millis() + s * 1000);
+    let token = StoredToken {
+        response,
+        expires_at,
+    };
+    let value = serde_json::to_string(&token).map_err(|e| GenericError(e.to_string()))?;
+    window.db().set_plugin_key_value(OAUTH2_PLUGIN_NAME, &token_key(context_id), &value);
+    Ok(token)
+}
+
+fn delete_token<R: Runtime>(window: &WebviewWindow<R>, context_id: &str) {
+    let key = token_key(context_id);
+    if let Err(e) = window.db().delete_plugin_key_value(OAUTH2_PLUGIN_NAME, &key) {
+        warn!("Failed to delete OAuth 2.0 token: {e}");
+    }
+}
+
+/// Session key for the authorization window, which the plugin's "Clear Window Session" action
+/// resets so that the user can log in as someone else.
+fn data_dir_key<R: Runtime>(window: &WebviewWindow<R>, context_id: &str) -> String {
+    let key = window
+        .db()
+        .get_plugin_key_value(OAUTH2_PLUGIN_NAME, &format!("data_dir::{context_id}"))
+        .and_then(|kv| serde_json::from_str::<String>(&kv.value).ok())
+        .unwrap_or("default".to_string());
+    format!("{context_id}::{key}")
+}
+
+fn token_key(context_id: &str) -> String {
+    format!("token::{context_id}")
+}
+
+impl PkceConfig {
+    fn from_values(values: &BTreeMap<String, Value>) -> PkceConfig {
+        let authorization_url = string_arg(values, "authorizationUrl").unwrap_or_default();
+        let access_token_url = string_arg(values, "accessTokenUrl").unwrap_or_default();
+        PkceConfig {
+            authorization_url: with_https(&authorization_url),
+            access_token_url: with_https(&access_token_url),
+            client_id: string_arg(values, "clientId").unwrap_or_default(),
+            client_secret: string_arg(values, "clientSecret").unwrap_or_default(),
+            redirect_uri: string_arg(values, "redirectUri"),
+            scope: string_arg(values, "scope"),
+            state: string_arg(values, "state"),
+            challenge_method: string_arg(values, "pkceChallengeMethod")
+                .unwrap_or(PKCE_S256.to_string()),
+            code_verifier: string_arg(values, "pkceCodeVerifier"),
+            credentials_in_body: string_arg(values, "credentials").as_deref() != Some("basic"),
+            header_prefix: string_arg(values, "headerPrefix").unwrap_or("Bearer".to_string()),
+        }
+    }
+}
+
+fn with_https(url: &str) -> String {
+    match url.starts_with("http://") || url.starts_with("https://") {
+        true => url.to_string(),
+        false => format!("https://{url}"),
+    }
+}
+
+fn string_arg(values: &BTreeMap<String, Value>, name: &str) -> Option<String> {
+    match values.get(name) {
+        Some(Value::String(s)) if !s.is_empty() => Some(s.to_string()),
+        Some(Value::Number(n)) => Some(n.to_string()),
+        _ => None,
+    }
+}
@@ -1,5 +1,5 @@
 use crate::http_request::send_http_request;
-use crate::render::{render_http_request, render_json_value};
+use crate::render::{redact_secrets, render_http_request, render_json_value};
 use crate::window::{create_window, CreateWindowConfig};
 use crate::{
     call_frontend, cookie_jar_from_window, environment_from_window, get_window_from_window_context,
@@ -15,8 +15,8 @@ use yaak_models::util::UpdateSource;
 use yaak_plugins::events::{
     Color, DeleteKeyValueResponse, EmptyPayload, FindHttpResponsesResponse,
     GetHttpRequestByIdResponse, GetKeyValueResponse, Icon, InternalEvent, InternalEventPayload,
-    RenderHttpRequestResponse, SendHttpRequestResponse, SetKeyValueResponse, ShowToastRequest,
-    TemplateRenderResponse, WindowContext, WindowNavigateEvent,
+    RenderHttpRequestResponse, RenderPurpose, SendHttpRequestResponse, SetKeyValueResponse,
+    ShowToastRequest, TemplateRenderResponse, WindowContext, WindowNavigateEvent,
 };
 use yaak_plugins::manager::PluginManager;
 use yaak_plugins::plugin_handle::PluginHandle;
@@ -80,6 +80,12 @@ pub(crate) async fn handle_plugin_event<R: Runtime>(
                 .db()
                 .get_base_environment(&workspace.id)
                 .expect("Failed to get base environment");
+            let (base_environment, environment) = match req.purpose {
+                RenderPurpose::Export => {
+                    (redact_secrets(&base_environment), environment.as_ref().map(redact_secrets))
+                }
+                _ => (base_environment, environment),
+            };
             let cb = PluginTemplateCallback::new(app_handle, &window_context, req.purpose);
             let http_request = render_http_request(
                 &req.http_request,
@@ -104,6 +110,12 @@ pub(crate) async fn handle_plugin_event<R: Runtime>(
                 .db()
                 .get_base_environment(&workspace.id)
                 .expect("Failed to get base environment");
+            let (base_environment, environment) = match req.purpose {
+                RenderPurpose::Export => {
+                    (redact_secrets(&base_environment), environment.as_ref().map(redact_secrets))
+                }
+                _ => (base_environment, environment),
+            };
             let cb = PluginTemplateCallback::new(app_handle, &window_context, req.purpose);
             let data = render_json_value(req.data, &base_environment, environment.as_ref(), &cb)
                 .await
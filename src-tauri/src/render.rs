@@ -1,8 +1,12 @@
 use serde_json::Value;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::sync::Mutex;
 use yaak_http::apply_path_placeholders;
+use yaak_http::url::resolve_base_url;
 use yaak_models::models::{
-    Environment, GrpcMetadataEntry, GrpcRequest, HttpRequest, HttpRequestHeader, HttpUrlParameter,
+    CookieJar, Environment, EnvironmentVariable, GrpcMetadataEntry, GrpcRequest, HttpRequest,
+    HttpRequestHeader, HttpUrlParameter, ProxySetting, ProxySettingAuth,
 };
 use yaak_models::render::make_vars_hashmap;
 use yaak_templates::{parse_and_render, render_json_value_raw, TemplateCallback};
@@ -75,18 +79,12 @@ pub async fn render_http_request<T: TemplateCallback>(
             name: render(p.name.as_str(), vars, cb).await?,
             value: render(p.value.as_str(), vars, cb).await?,
             id: p.id,
+            notation: p.notation,
         })
     }
 
-    let mut headers = Vec::new();
-    for p in r.headers.clone() {
-        headers.push(HttpRequestHeader {
-            enabled: p.enabled,
-            name: render(p.name.as_str(), vars, cb).await?,
-            value: render(p.value.as_str(), vars, cb).await?,
-            id: p.id,
-        })
-    }
+    let headers = render_headers(&r.headers, vars, cb).await?;
+    let trailers = render_headers(&r.trailers, vars, cb).await?;
 
     let mut body = BTreeMap::new();
     for (k, v) in r.body.clone() {
@@ -99,6 +97,14 @@ pub async fn render_http_request<T: TemplateCallback>(
     }
 
     let url = render(r.url.clone().as_str(), vars, cb).await?;
+    let base_url = environment
+        .and_then(|e| e.base_url.as_deref())
+        .filter(|u| !u.trim().is_empty())
+        .or(base_environment.base_url.as_deref());
+    let url = match base_url {
+        Some(base_url) => resolve_base_url(&render(base_url, vars, cb).await?, &url),
+        None => url,
+    };
 
     // This doesn't fit perfectly with the concept of "rendering" but it kind of does
     let (url, url_parameters) = apply_path_placeholders(&url, url_parameters);
@@ -107,12 +113,193 @@ pub async fn render_http_request<T: TemplateCallback>(
         url,
         url_parameters,
         headers,
+        trailers,
         body,
         authentication,
         ..r.to_owned()
     })
 }
 
+/// Render the default headers a request inherits, like those of the folders it's in. They're
+/// rendered with the same environments and callback as the request, so a default like
+/// `Authorization: Bearer ${[ token ]}` expands the same way it would on the request itself.
+pub async fn render_default_headers<T: TemplateCallback>(
+    headers: &[HttpRequestHeader],
+    base_environment: &Environment,
+    environment: Option<&Environment>,
+    cb: &T,
+) -> yaak_templates::error::Result<Vec<HttpRequestHeader>> {
+    let vars = &make_vars_hashmap(base_environment, environment);
+    render_headers(headers, vars, cb).await
+}
+
+async fn render_headers<T: TemplateCallback>(
+    headers: &[HttpRequestHeader],
+    vars: &HashMap<String, String>,
+    cb: &T,
+) -> yaak_templates::error::Result<Vec<HttpRequestHeader>> {
+    let mut rendered = Vec::new();
+    for h in headers {
+        rendered.push(HttpRequestHeader {
+            name: render(h.name.as_str(), vars, cb).await?,
+            value: render(h.value.as_str(), vars, cb).await?,
+            ..h.to_owned()
+        })
+    }
+    Ok(rendered)
+}
+
+/// Copy of a cookie jar with its cookie values rendered, like a session token from a variable
+pub async fn render_cookie_jar<T: TemplateCallback>(
+    cookie_jar: &CookieJar,
+    base_environment: &Environment,
+    environment: Option<&Environment>,
+    cb: &T,
+) -> yaak_templates::error::Result<CookieJar> {
+    let vars = &make_vars_hashmap(base_environment, environment);
+    let mut cookies = Vec::new();
+    for c in &cookie_jar.cookies {
+        let value = render(c.value(), vars, cb).await?;
+        cookies.push(match value == c.value() {
+            true => c.clone(),
+            false => c.with_value(&value),
+        });
+    }
+    Ok(CookieJar {
+        cookies,
+        ..cookie_jar.to_owned()
+    })
+}
+
+/// Render the proxy URLs and credentials, so each environment can use its own proxy
+pub async fn render_proxy_setting<T: TemplateCallback>(
+    proxy: &ProxySetting,
+    base_environment: &Environment,
+    environment: Option<&Environment>,
+    cb: &T,
+) -> yaak_templates::error::Result<ProxySetting> {
+    let vars = &make_vars_hashmap(base_environment, environment);
+    let (http, https, auth, always_connect) = match proxy {
+        ProxySetting::Enabled {
+            http,
+            https,
+            auth,
+            always_connect,
+        } => (http, https, auth, *always_connect),
+        ProxySetting::Disabled => return Ok(ProxySetting::Disabled),
+    };
+
+    let auth = match auth {
+        Some(a) => Some(ProxySettingAuth {
+            user: render(a.user.as_str(), vars, cb).await?,
+            password: render(a.password.as_str(), vars, cb).await?,
+        }),
+        None => None,
+    };
+
+    Ok(ProxySetting::Enabled {
+        http: render(http.as_str(), vars, cb).await?,
+        https: render(https.as_str(), vars, cb).await?,
+        auth,
+        always_connect,
+    })
+}
+
+/// Environment with extra variables layered over it, which take precedence over its own. Used to
+/// render with one-off values, like a row of a data file.
+pub fn with_variables(
+    environment: Option<&Environment>,
+    variables: &BTreeMap<String, String>,
+) -> Option<Environment> {
+    if variables.is_empty() {
+        return environment.cloned();
+    }
+
+    let mut environment = environment.cloned().unwrap_or_default();
+    environment.variables.extend(variables.iter().map(|(name, value)| EnvironmentVariable {
+        enabled: true,
+        name: name.to_owned(),
+        value: value.to_owned(),
+        ..Default::default()
+    }));
+    Some(environment)
+}
+
+/// Copy of an environment with secret values swapped for `{{ name }}` placeholders, so they can
+/// be filled in by whoever receives the export
+pub fn redact_secrets(environment: &Environment) -> Environment {
+    let variables = environment
+        .variables
+        .iter()
+        .map(|v| match v.secret {
+            true => EnvironmentVariable {
+                value: format!("{{{{ {} }}}}", v.name),
+                ..v.to_owned()
+            },
+            false => v.to_owned(),
+        })
+        .collect();
+    Environment {
+        variables,
+        ..environment.to_owned()
+    }
+}
+
+/// Template callback that wraps another, and remembers what each secret variable rendered to, so
+/// those values can be masked anywhere the rendered request is logged
+pub struct SecretTracker<'a, T: TemplateCallback> {
+    cb: &'a T,
+    names: HashSet<String>,
+    values: Mutex<Vec<String>>,
+}
+
+impl<'a, T: TemplateCallback> SecretTracker<'a, T> {
+    pub fn new(
+        cb: &'a T,
+        base_environment: &Environment,
+        environment: Option<&Environment>,
+    ) -> Self {
+        // Later environments win, like when rendering, so a secret can be overridden by a plain
+        // variable of the same name and the other way around
+        let mut names = HashSet::new();
+        let variables =
+            base_environment.variables.iter().chain(environment.iter().flat_map(|e| &e.variables));
+        for v in variables.filter(|v| v.enabled && !v.value.is_empty()) {
+            match v.secret {
+                true => names.insert(v.name.to_owned()),
+                false => names.remove(&v.name),
+            };
+        }
+
+        SecretTracker {
+            cb,
+            names,
+            values: Default::default(),
+        }
+    }
+
+    /// Rendered values of the secret variables used so far
+    pub fn values(&self) -> Vec<String> {
+        self.values.lock().unwrap().clone()
+    }
+}
+
+impl<T: TemplateCallback> TemplateCallback for SecretTracker<'_, T> {
+    fn run(
+        &self,
+        fn_name: &str,
+        args: HashMap<String, String>,
+    ) -> impl Future<Output = yaak_templates::error::Result<String>> + Send {
+        self.cb.run(fn_name, args)
+    }
+
+    fn on_variable(&self, name: &str, value: &str) {
+        if self.names.contains(name) {
+            self.values.lock().unwrap().push(value.to_string());
+        }
+    }
+}
+
 pub async fn render<T: TemplateCallback>(
     template: &str,
     vars: &HashMap<String, String>,
@@ -120,3 +307,71 @@ pub async fn render<T: TemplateCallback>(
 ) -> yaak_templates::error::Result<String> {
     parse_and_render(template, vars, cb).await
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::render::{SecretTracker, render_default_headers};
+    use std::collections::HashMap;
+    use tauri::async_runtime::block_on;
+    use yaak_models::models::{Environment, EnvironmentVariable, HttpRequestHeader};
+    use yaak_templates::TemplateCallback;
+
+    struct CB {}
+
+    impl TemplateCallback for CB {
+        async fn run(
+            &self,
+            fn_name: &str,
+            _args: HashMap<String, String>,
+        ) -> yaak_templates::error::Result<String> {
+            Ok(fn_name.to_uppercase())
+        }
+    }
+
+    fn environment(token: &str, secret: bool) -> Environment {
+        Environment {
+            variables: vec![EnvironmentVariable {
+                enabled: true,
+                name: "token".to_string(),
+                value: token.to_string(),
+                secret,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn header(name: &str, value: &str) -> HttpRequestHeader {
+        HttpRequestHeader {
+            enabled: true,
+            name: name.to_string(),
+            value: value.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn renders_default_headers_with_active_environment() {
+        let base = environment("base-token", false);
+        let active = environment("active-token", true);
+        let headers = vec![
+            header("Authorization", "Bearer ${[ token ]}"),
+            header("X-${[ name() ]}", "static"),
+        ];
+
+        let cb = CB {};
+        let tracker = SecretTracker::new(&cb, &base, Some(&active));
+        let rendered = block_on(render_default_headers(&headers, &base, Some(&active), &tracker));
+        assert_eq!(
+            rendered.unwrap(),
+            vec![
+                header("Authorization", "Bearer active-token"),
+                header("X-NAME", "static")
+            ]
+        );
+        assert_eq!(tracker.values(), vec!["active-token"]);
+
+        let rendered = block_on(render_default_headers(&headers, &base, None, &cb)).unwrap();
+        assert_eq!(rendered[0].value, "Bearer base-token");
+    }
+}
@@ -0,0 +1,367 @@
+use crate::error::Error::GenericError;
+use crate::error::{Error, Result};
+use crate::http_request::{
+    SendOptions, build_client, render_cookies, render_proxy_settings, send_http_request,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{Runtime, WebviewWindow};
+use tokio::sync::Semaphore;
+use tokio::sync::watch::Receiver;
+use yaak_http::chain::{dependency_order, folder_stopped_at, is_chain_failure};
+use yaak_http::data_file::DataRow;
+use yaak_models::models::{CookieJar, Environment, Folder, HttpRequest, HttpResponse};
+use yaak_models::query_manager::QueryManagerExt;
+use yaak_models::util::UpdateSource;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FolderRunResult {
+    pub responses: Vec<HttpResponse>,
+    /// Index of the request that stopped the run, if `stop_on_error` was set and one failed
+    pub stopped_at: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ChainRunResult {
+    /// Responses of the requests that were sent, in the order they were sent, ending with the
+    /// requested one's if the whole chain succeeded
+    pub responses: Vec<HttpResponse>,
+    /// Index of the request that failed and stopped the chain, if one did
+    pub stopped_at: Option<usize>,
+}
+
+/// Send every HTTP request in a folder (including sub-folders) in sidebar order. All sends share
+/// a single client and cookie store, so cookies set by an earlier request (eg. a login) are sent
+/// by the ones that follow.
+///
+/// Up to the workspace's `setting_max_concurrency` requests are in flight at once. The default of
+/// 1 sends them one after the other, which keeps cookie ordering deterministic.
+pub async fn run_folder<R: Runtime>(
+    window: &WebviewWindow<R>,
+    folder_id: &str,
+    environment: Option<Environment>,
+    cookie_jar: Option<CookieJar>,
+    stop_on_error: bool,
+    cancelled_rx: &mut Receiver<bool>,
+) -> Result<FolderRunResult> {
+    let (requests, settings, workspace) = {
+        let db = window.db();
+        let folder = db.get_folder(folder_id)?;
+        let folders = db.list_folders(&folder.workspace_id)?;
+        let http_requests = db.list_http_requests(&folder.workspace_id)?;
+        let requests = collect_folder_requests(&folder.id, &folders, &http_requests);
+        (requests, db.get_settings(), db.get_workspace(&folder.workspace_id)?)
+    };
+
+    // Every request shares the client, so the proxy and cookies are rendered once for the run's
+    // environment
+    let base_environment = window.db().get_base_environment(&workspace.id)?;
+    let settings =
+        render_proxy_settings(window, settings, &base_environment, environment.as_ref()).await?;
+    let rendered_jar =
+        render_cookies(window, cookie_jar.as_ref(), &base_environment, environment.as_ref())
+            .await?;
+    let client = build_client(&workspace, &settings, rendered_jar.as_ref(), Default::default())?;
+    let semaphore = Arc::new(Semaphore::new(workspace.setting_max_concurrency.max(1) as usize));
+    let failed = Arc::new(AtomicBool::new(false));
+
+    let mut handles = Vec::new();
+    for request in requests {
+        // Acquire permits in order, so requests start in sidebar order regardless of how many
+        // are allowed to run at once
+        let permit =
+            semaphore.clone().acquire_owned().await.map_err(|e| GenericError(e.to_string()))?;
+        if *cancelled_rx.borrow() || (stop_on_error && failed.load(Ordering::SeqCst)) {
+            break;
+        }
+
+        let window = window.clone();
+        let environment = environment.clone();
+        let cookie_jar = cookie_jar.clone();
+        let client = client.clone();
+        let failed = failed.clone();
+        let mut cancelled_rx = cancelled_rx.clone();
+        handles.push(tokio::spawn(async move {
+            let _permit = permit;
+            let response = window.db().upsert_http_response(
+                &HttpResponse {
+                    request_id: request.id.clone(),
+                    workspace_id: request.workspace_id.clone(),
+                    ..Default::default()
+                },
+                &UpdateSource::from_window(&window),
+            )?;
+
+            let response = send_http_request(
+                &window,
+                &request,
+                &response,
+                environment,
+                cookie_jar,
+                SendOptions {
+                    shared_client: Some(&client),
+                    ..Default::default()
+                },
+                &mut cancelled_rx,
+            )
+            .await?;
+
+            if is_chain_failure(&response) {
+                failed.store(true, Ordering::SeqCst);
+            }
+            Ok::<_, Error>(response)
+        }));
+    }
+
+    let mut result = FolderRunResult::default();
+    for handle in handles {
+        let response = handle.await.map_err(|e| GenericError(e.to_string()))??;
+        result.responses.push(response);
+    }
+    result.stopped_at = folder_stopped_at(&result.responses, stop_on_error);
+
+    Ok(result)
+}
+
+/// Send a request after the requests it depends on, and the ones those depend on, each after its
+/// own dependencies. The chain stops at the first request that fails, since the ones after it
+/// may need its response, like a call that needs the token from a login. Later requests get the
+/// responses of earlier ones through response template functions, and their cookies through the
+/// client they share.
+pub async fn run_chain<R: Runtime>(
+    window: &WebviewWindow<R>,
+    request_id: &str,
+    environment: Option<Environment>,
+    cookie_jar: Option<CookieJar>,
+    cancelled_rx: &mut Receiver<bool>,
+) -> Result<ChainRunResult> {
+    let (requests, settings, workspace) = {
+        let db = window.db();
+        let request = db.get_http_request(request_id)?;
+        let http_requests = db.list_http_requests(&request.workspace_id)?;
+        let requests = dependency_order(&request.id, &http_requests).map_err(GenericError)?;
+        (requests, db.get_settings(), db.get_workspace(&request.workspace_id)?)
+    };
+
+    let base_environment = window.db().get_base_environment(&workspace.id)?;
+    let settings =
+        render_proxy_settings(window, settings, &base_environment, environment.as_ref()).await?;
+    let rendered_jar =
+        render_cookies(window, cookie_jar.as_ref(), &base_environment, environment.as_ref())
+            .await?;
+    let client = build_client(&workspace, &settings, rendered_jar.as_ref(), Default::default())?;
+
+    let mut result = ChainRunResult::default();
+    for (i, request) in requests.iter().enumerate() {
+        if *cancelled_rx.borrow() {
+            break;
+        }
+
+        let response = window.db().upsert_http_response(
+            &HttpResponse {
+                request_id: request.id.clone(),
+                workspace_id: request.workspace_id.clone(),
+                ..Default::default()
+            },
+            &UpdateSource::from_window(window),
+        )?;
+
+        let response = send_http_request(
+            window,
+            request,
+            &response,
+            environment.clone(),
+            cookie_jar.clone(),
+            SendOptions {
+                shared_client: Some(&client),
+                ..Default::default()
+            },
+            cancelled_rx,
+        )
+        .await?;
+
+        let failed = is_chain_failure(&response);
+        result.responses.push(response);
+        if failed {
+            result.stopped_at = Some(i);
+            break;
+        }
+    }
+
+    Ok(result)
+}
+
+/// Send a request once per row of a data file, with the row's columns available as template
+/// variables. Like folder runs, sends share a client and run up to the workspace's
+/// `setting_max_concurrency` at once. Responses are in row order.
+pub async fn run_with_data<R: Runtime>(
+    window: &WebviewWindow<R>,
+    request: &HttpRequest,
+    rows: Vec<DataRow>,
+    environment: Option<Environment>,
+    cookie_jar: Option<CookieJar>,
+    cancelled_rx: &mut Receiver<bool>,
+) -> Result<Vec<HttpResponse>> {
+    let (settings, workspace) = {
+        let db = window.db();
+        (db.get_settings(), db.get_workspace(&request.workspace_id)?)
+    };
+
+    let base_environment = window.db().get_base_environment(&workspace.id)?;
+    let settings =
+        render_proxy_settings(window, settings, &base_environment, environment.as_ref()).await?;
+    let rendered_jar =
+        render_cookies(window, cookie_jar.as_ref(), &base_environment, environment.as_ref())
+            .await?;
+    let client = build_client(&workspace, &settings, rendered_jar.as_ref(), Default::default())?;
+    let semaphore = Arc::new(Semaphore::new(workspace.setting_max_concurrency.max(1) as usize));
+
+    let mut handles = Vec::new();
+    for row in rows {
+        let permit =
+            semaphore.clone().acquire_owned().await.map_err(|e| GenericError(e.to_string()))?;
+        if *cancelled_rx.borrow() {
+            break;
+        }
+
+        let window = window.clone();
+        let request = request.clone();
+        let environment = environment.clone();
+        let cookie_jar = cookie_jar.clone();
+        let client = client.clone();
+        let mut cancelled_rx = cancelled_rx.clone();
+        handles.push(tokio::spawn(async move {
+            let _permit = permit;
+            let response = window.db().upsert_http_response(
+                &HttpResponse {
+                    request_id: request.id.clone(),
+                    workspace_id: request.workspace_id.clone(),
+                    ..Default::default()
+                },
+                &UpdateSource::from_window(&window),
+            )?;
+
+            send_http_request(
+                &window,
+                &request,
+                &response,
+                environment,
+                cookie_jar,
+                SendOptions {
+                    shared_client: Some(&client),
+                    variables: Some(&row),
+                    ..Default::default()
+                },
+                &mut cancelled_rx,
+            )
+            .await
+        }));
+    }
+
+    let mut responses = Vec::new();
+    for handle in handles {
+        responses.push(handle.await.map_err(|e| GenericError(e.to_string()))??);
+    }
+
+    Ok(responses)
+}
+
+/// Send one request once per environment, all at the same time, so the same endpoint can be
+/// compared across eg. dev, staging, and prod. Responses are keyed by environment ID.
+pub async fn send_to_environments<R: Runtime>(
+    window: &WebviewWindow<R>,
+    request: &HttpRequest,
+    environment_ids: &[String],
+    cookie_jar: Option<CookieJar>,
+    cancelled_rx: &mut Receiver<bool>,
+) -> Result<BTreeMap<String, HttpResponse>> {
+    let environments = environment_ids
+        .iter()
+        .map(|id| window.db().get_environment(id))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut handles = Vec::new();
+    for environment in environments {
+        let window = window.clone();
+        let request = request.clone();
+        let cookie_jar = cookie_jar.clone();
+        let mut cancelled_rx = cancelled_rx.clone();
+        handles.push(tokio::spawn(async move {
+            let response = window.db().upsert_http_response(
+                &HttpResponse {
+                    request_id: request.id.clone(),
+                    workspace_id: request.workspace_id.clone(),
+                    ..Default::default()
+                },
+                &UpdateSource::from_window(&window),
+            )?;
+
+            let environment_id = environment.id.clone();
+            let response = send_http_request(
+                &window,
+                &request,
+                &response,
+                Some(environment),
+                cookie_jar,
+                SendOptions::default(),
+                &mut cancelled_rx,
+            )
+            .await?;
+            Ok::<_, Error>((environment_id, response))
+        }));
+    }
+
+    let mut responses = BTreeMap::new();
+    for handle in handles {
+        let (environment_id, response) = handle.await.map_err(|e| GenericError(e.to_string()))??;
+        responses.insert(environment_id, response);
+    }
+
+    Ok(responses)
+}
+
+enum FolderChild<'a> {
+    Folder(&'a Folder),
+    HttpRequest(&'a HttpRequest),
+}
+
+impl FolderChild<'_> {
+    fn sort_priority(&self) -> f32 {
+        match self {
+            FolderChild::Folder(f) => f.sort_priority,
+            FolderChild::HttpRequest(r) => r.sort_priority,
+        }
+    }
+}
+
+/// Flatten a folder into its HTTP requests, depth-first, ordered by `sort_priority`
+fn collect_folder_requests(
+    folder_id: &str,
+    folders: &[Folder],
+    http_requests: &[HttpRequest],
+) -> Vec<HttpRequest> {
+    let mut children = Vec::new();
+    for f in folders.iter().filter(|f| f.folder_id.as_deref() == Some(folder_id)) {
+        children.push(FolderChild::Folder(f));
+    }
+    for r in http_requests.iter().filter(|r| r.folder_id.as_deref() == Some(folder_id)) {
+        children.push(FolderChild::HttpRequest(r));
+    }
+    children.sort_by(|a, b| a.sort_priority().total_cmp(&b.sort_priority()));
+
+    let mut requests = Vec::new();
+    for child in children {
+        match child {
+            FolderChild::Folder(f) => {
+                requests.append(&mut collect_folder_requests(&f.id, folders, http_requests))
+            }
+            FolderChild::HttpRequest(r) => requests.push(r.clone()),
+        }
+    }
+
+    requests
+}
@@ -0,0 +1,116 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use yaak_models::models::{Environment, HttpRequest};
+use yaak_templates::usage::referenced_variables;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct VariableUsage {
+    pub name: String,
+    /// IDs of the environments that define this variable
+    pub environment_ids: Vec<String>,
+    /// IDs of the HTTP requests that reference this variable
+    pub request_ids: Vec<String>,
+    /// Names of other variables whose values reference this one
+    pub variable_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct VariableUsageReport {
+    /// Every variable that is defined or referenced, sorted by name
+    pub variables: Vec<VariableUsage>,
+    /// Defined variables that nothing references
+    pub unused: Vec<String>,
+    /// Referenced variables that no environment defines
+    pub undefined: Vec<String>,
+}
+
+/// Work out which environment variables are referenced by which requests. This is purely static,
+/// so a variable only counts as used if its name appears in a template tag.
+pub fn analyze_variable_usage(
+    http_requests: &[HttpRequest],
+    environments: &[Environment],
+) -> VariableUsageReport {
+    let mut usages: BTreeMap<String, VariableUsage> = BTreeMap::new();
+
+    for e in environments {
+        for v in &e.variables {
+            if v.name.is_empty() {
+                continue;
+            }
+            let u = usage(&mut usages, &v.name);
+            if !u.environment_ids.contains(&e.id) {
+                u.environment_ids.push(e.id.to_string());
+            }
+        }
+    }
+
+    for e in environments {
+        for v in &e.variables {
+            for name in referenced_variables(&v.value) {
+                let u = usage(&mut usages, &name);
+                if name != v.name && !u.variable_names.contains(&v.name) {
+                    u.variable_names.push(v.name.to_string());
+                }
+            }
+        }
+    }
+
+    for r in http_requests {
+        for name in request_variables(r) {
+            usage(&mut usages, &name).request_ids.push(r.id.to_string());
+        }
+    }
+
+    let variables: Vec<VariableUsage> = usages.into_values().collect();
+    let unused = variables
+        .iter()
+        .filter(|u| u.request_ids.is_empty() && u.variable_names.is_empty())
+        .map(|u| u.name.to_string())
+        .collect();
+    let undefined = variables
+        .iter()
+        .filter(|u| u.environment_ids.is_empty())
+        .map(|u| u.name.to_string())
+        .collect();
+
+    VariableUsageReport {
+        variables,
+        unused,
+        undefined,
+    }
+}
+
+fn usage<'a>(usages: &'a mut BTreeMap<String, VariableUsage>, name: &str) -> &'a mut VariableUsage {
+    usages.entry(name.to_string()).or_insert_with(|| VariableUsage {
+        name: name.to_string(),
+        ..Default::default()
+    })
+}
+
+fn request_variables(r: &HttpRequest) -> BTreeSet<String> {
+    let mut names = referenced_variables(&r.url);
+    for p in &r.url_parameters {
+        names.extend(referenced_variables(&p.name));
+        names.extend(referenced_variables(&p.value));
+    }
+    for h in &r.headers {
+        names.extend(referenced_variables(&h.name));
+        names.extend(referenced_variables(&h.value));
+    }
+    for v in r.body.values().chain(r.authentication.values()) {
+        collect_json_variables(v, &mut names);
+    }
+    names
+}
+
+fn collect_json_variables(v: &Value, names: &mut BTreeSet<String>) {
+    match v {
+        Value::String(s) => names.extend(referenced_variables(s)),
+        Value::Array(a) => a.iter().for_each(|v| collect_json_variables(v, names)),
+        Value::Object(o) => o.values().for_each(|v| collect_json_variables(v, names)),
+        _ => {}
+    }
+}
@@ -30,7 +30,7 @@ var plugin = {
     label: "Copy as Curl",
     icon: "copy",
     async onSelect(ctx, args) {
-      const rendered_request = await ctx.httpRequest.render({ httpRequest: args.httpRequest, purpose: "preview" });
+      const rendered_request = await ctx.httpRequest.render({ httpRequest: args.httpRequest, purpose: "export" });
       const data = await convertToCurl(rendered_request);
       await ctx.clipboard.copyText(data);
       await ctx.toast.show({ message: "Curl copied to clipboard", icon: "copy", color: "success" });
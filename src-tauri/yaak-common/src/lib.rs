@@ -1 +1,2 @@
-pub mod window;
\ No newline at end of file
+pub mod tls;
+pub mod window;
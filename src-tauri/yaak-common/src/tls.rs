@@ -0,0 +1,513 @@
+use rustls::client::WebPkiServerVerifier;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::ring;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use rustls_platform_verifier::Verifier;
+use std::collections::HashMap;
+use std::fs;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+
+/// Why each server's certificate failed validation, by server name, like `example.com` or
+/// `::1`. Servers whose certificate passed aren't in it.
+pub type CertificateErrors = Arc<Mutex<HashMap<String, String>>>;
+
+/// Certificate files and hosts to use for TLS connections, in addition to the defaults
+#[derive(Debug, Clone, Default)]
+pub struct TlsCertificates {
+    /// PEM file of CA certificates to trust instead of the platform's roots
+    pub ca_bundle_path: Option<String>,
+    /// PEM text of CA certificates to trust in addition to the others, like one pasted onto a
+    /// single request
+    pub ca_cert_pem: Option<String>,
+    /// PEM file of the client certificate (chain), for mutual TLS
+    pub client_cert_path: Option<String>,
+    /// PEM file of the client certificate's private key
+    pub client_key_path: Option<String>,
+    /// Hosts whose certificates aren't validated, like `localhost` or `*.internal.example.com`
+    pub insecure_hosts: Vec<String>,
+    /// Accept certificates that fail validation, but record why they failed here, so a request
+    /// can still be sent while showing what's wrong with the certificate
+    pub report_errors: Option<CertificateErrors>,
+}
+
+/// Build a TLS client config that validates server certificates, except those of the insecure
+/// hosts. This uses the platform-native verifier unless a CA bundle is given, in which case only
+/// the bundle's certificates are trusted.
+pub fn get_tls_config(certs: &TlsCertificates) -> Result<ClientConfig, String> {
+    let arc_crypto_provider = Arc::new(ring::default_provider());
+    let builder = ClientConfig::builder_with_provider(arc_crypto_provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| e.to_string())?;
+
+    let verifier: Arc<dyn ServerCertVerifier> = match non_empty(&certs.ca_bundle_path) {
+        Some(path) => {
+            let mut roots = RootCertStore::empty();
+            for cert in read_certs(path)? {
+                roots.add(cert).map_err(|e| format!("Invalid CA certificate in {path}: {e}"))?;
+            }
+            let provider = arc_crypto_provider.clone();
+            WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider)
+                .build()
+                .map_err(|e| format!("Invalid CA bundle {path}: {e}"))?
+        }
+        None => Arc::new(Verifier::new().with_provider(arc_crypto_provider.clone())),
+    };
+
+    let verifier = match non_empty(&certs.ca_cert_pem) {
+        None => verifier,
+        Some(pem) => {
+            let mut roots = RootCertStore::empty();
+            for cert in parse_certs(pem.as_bytes(), "inline CA certificate")? {
+                roots.add(cert).map_err(|e| format!("Invalid inline CA certificate: {e}"))?;
+            }
+            let extra =
+                WebPkiServerVerifier::builder_with_provider(Arc::new(roots), arc_crypto_provider)
+                    .build()
+                    .map_err(|e| format!("Invalid inline CA certificate: {e}"))?;
+            Arc::new(ExtraRootsVerifier {
+                inner: verifier,
+                extra,
+            })
+        }
+    };
+
+    let verifier = match certs.insecure_hosts.is_empty() {
+        true => verifier,
+        false => Arc::new(InsecureHostsVerifier {
+            inner: verifier,
+            hosts: certs.insecure_hosts.clone(),
+        }),
+    };
+    let verifier = match &certs.report_errors {
+        None => verifier,
+        Some(errors) => Arc::new(ReportingVerifier {
+            inner: verifier,
+            errors: errors.clone(),
+        }),
+    };
+    let builder = builder.dangerous().with_custom_certificate_verifier(verifier);
+
+    match (non_empty(&certs.client_cert_path), non_empty(&certs.client_key_path)) {
+        (Some(cert_path), Some(key_path)) => {
+            let chain = read_certs(cert_path)?;
+            let key = PrivateKeyDer::from_pem_slice(&read_file(key_path)?)
+                .map_err(|e| format!("Failed to read private key from {key_path}: {e:?}"))?;
+            builder
+                .with_client_auth_cert(chain, key)
+                .map_err(|e| format!("Invalid client certificate: {e}"))
+        }
+        (None, None) => Ok(builder.with_no_client_auth()),
+        _ => Err("Client certificate and key must be set together".to_string()),
+    }
+}
+
+fn non_empty(path: &Option<String>) -> Option<&str> {
+    path.as_deref().map(|p| p.trim()).filter(|p| !p.is_empty())
+}
+
+fn read_file(path: &str) -> Result<Vec<u8>, String> {
+    fs::read(path).map_err(|e| format!("Failed to read {path}: {e}"))
+}
+
+fn read_certs(path: &str) -> Result<Vec<CertificateDer<'static>>, String> {
+    parse_certs(&read_file(path)?, path)
+}
+
+fn parse_certs(pem: &[u8], source: &str) -> Result<Vec<CertificateDer<'static>>, String> {
+    let certs = CertificateDer::pem_slice_iter(pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read certificates from {source}: {e:?}"))?;
+    if certs.is_empty() {
+        return Err(format!("No certificates found in {source}"));
+    }
+    Ok(certs)
+}
+
+/// Verifier that also trusts certificates issued by extra roots, for when the wrapped verifier
+/// can't be given more roots itself, like the platform's
+#[derive(Debug)]
+struct ExtraRootsVerifier {
+    inner: Arc<dyn ServerCertVerifier>,
+    extra: Arc<WebPkiServerVerifier>,
+}
+
+impl ServerCertVerifier for ExtraRootsVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let result = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        );
+        match result {
+            Ok(v) => Ok(v),
+            // Report the usual error if the extra roots don't help either
+            Err(e) => self
+                .extra
+                .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+                .map_err(|_| e),
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Key of a server in [`CertificateErrors`]
+fn server_key(server_name: &ServerName<'_>) -> Option<String> {
+    match server_name {
+        ServerName::DnsName(name) => Some(name.as_ref().to_lowercase()),
+        ServerName::IpAddress(ip) => Some(IpAddr::from(*ip).to_string()),
+        _ => None,
+    }
+}
+
+/// Verifier that accepts every certificate, but still validates it with the wrapped verifier and
+/// records why it failed, if it did. Handshake signatures aren't checked either, the same as
+/// without validation at all.
+#[derive(Debug)]
+struct ReportingVerifier {
+    inner: Arc<dyn ServerCertVerifier>,
+    errors: CertificateErrors,
+}
+
+impl ReportingVerifier {
+    fn record(
+        &self,
+        server_name: &ServerName<'_>,
+        result: Result<ServerCertVerified, rustls::Error>,
+    ) {
+        let key = match server_key(server_name) {
+            Some(k) => k,
+            None => return,
+        };
+        let mut errors = self.errors.lock().unwrap();
+        match result {
+            Ok(_) => errors.remove(&key),
+            Err(e) => errors.insert(key, e.to_string()),
+        };
+    }
+}
+
+impl ServerCertVerifier for ReportingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let result = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        );
+        self.record(server_name, result);
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        _message: &[u8],
+        _cert: &CertificateDer<'_>,
+        _dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        Ok(HandshakeSignatureValid::assertion())
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        _message: &[u8],
+        _cert: &CertificateDer<'_>,
+        _dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        Ok(HandshakeSignatureValid::assertion())
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Verifier that accepts any certificate for the insecure hosts, and defers to the wrapped
+/// verifier for everything else. Handshake signatures are always checked, since they only prove
+/// the server holds the certificate's key, which says nothing about whether it's trusted.
+#[derive(Debug)]
+struct InsecureHostsVerifier {
+    inner: Arc<dyn ServerCertVerifier>,
+    hosts: Vec<String>,
+}
+
+impl InsecureHostsVerifier {
+    fn is_insecure(&self, server_name: &ServerName<'_>) -> bool {
+        let host = match server_key(server_name) {
+            Some(host) => host,
+            None => return false,
+        };
+        self.hosts.iter().any(|h| match h.strip_prefix("*.") {
+            Some(domain) => host.strip_suffix(domain).is_some_and(|sub| sub.ends_with('.')),
+            None => h == &host,
+        })
+    }
+}
+
+impl ServerCertVerifier for InsecureHostsVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        if self.is_insecure(server_name) {
+            return Ok(ServerCertVerified::assertion());
+        }
+        self.inner.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tls::{
+        CertificateErrors, ExtraRootsVerifier, InsecureHostsVerifier, ReportingVerifier,
+        TlsCertificates, get_tls_config, parse_certs, server_key,
+    };
+    use rustls::client::WebPkiServerVerifier;
+    use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+    use rustls::crypto::ring;
+    use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+    use rustls::{CertificateError, DigitallySignedStruct, RootCertStore, SignatureScheme};
+    use std::fs;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// CA that issued [`LEAF_PEM`], valid until 2126
+    const CA_PEM: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBlTCCATugAwIBAgIURW+gH7eSacQey3tOrZanKkh32gkwCgYIKoZIzj0EAwIw
+FzEVMBMGA1UEAwwMWWFhayBUZXN0IENBMCAXDTI2MTAxNjExMTgzN1oYDzIxMjYw
+OTIyMTExODM3WjAXMRUwEwYDVQQDDAxZYWFrIFRlc3QgQ0EwWTATBgcqhkjOPQIB
+BggqhkjOPQMBBwNCAAQRjmTv4SHg2tzERXE9GURUPAEwiTtkkdwT4GTtIDBZmRVf
+n/1zTFmbxwGVyjazwQnKfuJ697jiY/T6gkvrCjrYo2MwYTAdBgNVHQ4EFgQUUD7P
+Bk6myupTT+Q9k7RWb3otDTMwHwYDVR0jBBgwFoAUUD7PBk6myupTT+Q9k7RWb3ot
+DTMwDwYDVR0TAQH/BAUwAwEB/zAOBgNVHQ8BAf8EBAMCAgQwCgYIKoZIzj0EAwID
+SAAwRQIgLG+H2R3k5ia6fY1l63FpZCdEWdLKxy6BedfXdmkqHp0CIQCH9L85i7+f
+0dMvWhJFOBUJ/+eFMI/e9w8it/HVfqeHFA==
+-----END CERTIFICATE-----
+";
+
+    /// Certificate for `example.com`, issued by [`CA_PEM`]
+    const LEAF_PEM: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBwTCCAWagAwIBAgIUTm6dL2hFE5q0wcRP8A6h/IxMw1cwCgYIKoZIzj0EAwIw
+FzEVMBMGA1UEAwwMWWFhayBUZXN0IENBMCAXDTI2MTAxNjExMTgzN1oYDzIxMjYw
+OTIyMTExODM3WjAWMRQwEgYDVQQDDAtleGFtcGxlLmNvbTBZMBMGByqGSM49AgEG
+CCqGSM49AwEHA0IABEGg0lg6+JXmqbyDjUjB61OFSo7llWeBnveHZRt+vlDN5dBu
+ZMFJG2Y8mC0RfabkEH2gm66BKNLrkt26EEiINaajgY4wgYswFgYDVR0RBA8wDYIL
+ZXhhbXBsZS5jb20wDAYDVR0TAQH/BAIwADAOBgNVHQ8BAf8EBAMCB4AwEwYDVR0l
+BAwwCgYIKwYBBQUHAwEwHQYDVR0OBBYEFOJateYsXtSCmuaNYML0156GaFD2MB8G
+A1UdIwQYMBaAFFA+zwZOpsrqU0/kPZO0Vm96LQ0zMAoGCCqGSM49BAMCA0kAMEYC
+IQDYSYwpEcUedLHqZxXwpWwSqYLeQGn5rQTcWVeNFA/AMgIhAO6ECevYeNLDjqyz
+VYjYicYTqvOdoZr8UZQqkukmOmUA
+-----END CERTIFICATE-----
+";
+
+    /// Verifier that only trusts the certificates of some hosts, whatever the certificate is
+    #[derive(Debug)]
+    struct HostsVerifier(Vec<&'static str>);
+
+    impl ServerCertVerifier for HostsVerifier {
+        fn verify_server_cert(
+            &self,
+            _end_entity: &CertificateDer<'_>,
+            _intermediates: &[CertificateDer<'_>],
+            server_name: &ServerName<'_>,
+            _ocsp_response: &[u8],
+            _now: UnixTime,
+        ) -> Result<ServerCertVerified, rustls::Error> {
+            match server_key(server_name).is_some_and(|k| self.0.contains(&k.as_str())) {
+                true => Ok(ServerCertVerified::assertion()),
+                false => Err(CertificateError::UnknownIssuer.into()),
+            }
+        }
+
+        fn verify_tls12_signature(
+            &self,
+            _message: &[u8],
+            _cert: &CertificateDer<'_>,
+            _dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            Err(rustls::Error::General("not checked".to_string()))
+        }
+
+        fn verify_tls13_signature(
+            &self,
+            _message: &[u8],
+            _cert: &CertificateDer<'_>,
+            _dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            Err(rustls::Error::General("not checked".to_string()))
+        }
+
+        fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+            Vec::new()
+        }
+    }
+
+    fn verify(
+        verifier: &dyn ServerCertVerifier,
+        cert: &CertificateDer<'_>,
+        host: &str,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let name = ServerName::try_from(host.to_string()).unwrap();
+        let now = UnixTime::since_unix_epoch(Duration::from_secs(1_900_000_000));
+        verifier.verify_server_cert(cert, &[], &name, &[], now)
+    }
+
+    fn leaf() -> CertificateDer<'static> {
+        parse_certs(LEAF_PEM.as_bytes(), "leaf").unwrap().remove(0)
+    }
+
+    #[test]
+    fn trusts_extra_roots() {
+        let mut roots = RootCertStore::empty();
+        roots.add_parsable_certificates(parse_certs(CA_PEM.as_bytes(), "CA").unwrap());
+        let provider = Arc::new(ring::default_provider());
+        let verifier = ExtraRootsVerifier {
+            inner: Arc::new(HostsVerifier(vec!["trusted.com"])),
+            extra: WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider)
+                .build()
+                .unwrap(),
+        };
+
+        assert!(verify(&verifier, &leaf(), "example.com").is_ok());
+        assert!(verify(&verifier, &leaf(), "trusted.com").is_ok());
+
+        // The wrapped verifier's error is the one reported when neither trusts the certificate
+        assert_eq!(
+            verify(&verifier, &leaf(), "other.com").unwrap_err(),
+            CertificateError::UnknownIssuer.into()
+        );
+    }
+
+    #[test]
+    fn skips_insecure_hosts() {
+        let verifier = InsecureHostsVerifier {
+            inner: Arc::new(HostsVerifier(vec![])),
+            hosts: ["localhost", "::1", "*.internal.com"].map(|h| h.to_string()).to_vec(),
+        };
+
+        let cert = CertificateDer::from(Vec::new());
+        assert!(verify(&verifier, &cert, "localhost").is_ok());
+        assert!(verify(&verifier, &cert, "LocalHost").is_ok());
+        assert!(verify(&verifier, &cert, "::1").is_ok());
+        assert!(verify(&verifier, &cert, "api.internal.com").is_ok());
+        assert!(verify(&verifier, &cert, "a.b.internal.com").is_ok());
+        assert!(verify(&verifier, &cert, "internal.com").is_err());
+        assert!(verify(&verifier, &cert, "notinternal.com").is_err());
+        assert!(verify(&verifier, &cert, "127.0.0.1").is_err());
+    }
+
+    #[test]
+    fn reports_certificate_errors() {
+        let errors = CertificateErrors::default();
+        let verifier = ReportingVerifier {
+            inner: Arc::new(HostsVerifier(vec!["good.com"])),
+            errors: errors.clone(),
+        };
+
+        let cert = CertificateDer::from(Vec::new());
+        assert!(verify(&verifier, &cert, "good.com").is_ok());
+        assert!(verify(&verifier, &cert, "Bad.com").is_ok());
+        assert_eq!(errors.lock().unwrap().keys().collect::<Vec<_>>(), vec!["bad.com"]);
+        assert!(errors.lock().unwrap()["bad.com"].contains("UnknownIssuer"));
+
+        // A certificate that passes later clears the host's error
+        let verifier = ReportingVerifier {
+            inner: Arc::new(HostsVerifier(vec!["bad.com"])),
+            errors: errors.clone(),
+        };
+        assert!(verify(&verifier, &cert, "bad.com").is_ok());
+        assert!(errors.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn builds_config_with_bundle_and_inline_roots() {
+        let bundle = std::env::temp_dir().join("yaak-test-ca-bundle.pem");
+        fs::write(&bundle, CA_PEM).unwrap();
+        let certs = TlsCertificates {
+            ca_bundle_path: Some(bundle.to_string_lossy().to_string()),
+            ca_cert_pem: Some(CA_PEM.to_string()),
+            insecure_hosts: vec!["localhost".to_string()],
+            report_errors: Some(CertificateErrors::default()),
+            ..Default::default()
+        };
+        assert!(get_tls_config(&certs).is_ok());
+
+        let certs = TlsCertificates {
+            client_cert_path: Some(bundle.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            get_tls_config(&certs).unwrap_err(),
+            "Client certificate and key must be set together"
+        );
+        fs::remove_file(bundle).unwrap();
+    }
+}
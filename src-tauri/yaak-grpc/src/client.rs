@@ -4,6 +4,7 @@ use hyper_rustls::HttpsConnector;
 use hyper_util::client::legacy::connect::HttpConnector;
 use hyper_util::client::legacy::Client;
 use log::debug;
+use rustls::ClientConfig;
 use tokio_stream::StreamExt;
 use tonic::body::BoxBody;
 use tonic::transport::Uri;
@@ -24,13 +25,13 @@ pub struct AutoReflectionClient<T = Client<HttpsConnector<HttpConnector>, BoxBod
 }
 
 impl AutoReflectionClient {
-    pub fn new(uri: &Uri) -> Self {
+    pub fn new(uri: &Uri, tls_config: &ClientConfig) -> Self {
         let client_v1 = v1::server_reflection_client::ServerReflectionClient::with_origin(
-            get_transport(),
+            get_transport(tls_config.clone()),
             uri.clone(),
         );
         let client_v1alpha = v1alpha::server_reflection_client::ServerReflectionClient::with_origin(
-            get_transport(),
+            get_transport(tls_config.clone()),
             uri.clone(),
         );
         AutoReflectionClient {
@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use yaak_models::models::HttpResponseHeader;
+
+/// A single challenge from a `WWW-Authenticate` header (RFC 9110, section 11.6.1)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AuthChallenge {
+    /// Auth scheme, as sent by the server (eg. "Basic", "Bearer", "Digest")
+    pub scheme: String,
+    /// Auth params, keyed by lowercase name. Quoted values are unescaped.
+    pub params: BTreeMap<String, String>,
+    /// Opaque token sent instead of params (eg. for "Negotiate")
+    pub token68: Option<String>,
+}
+
+impl AuthChallenge {
+    pub fn realm(&self) -> Option<&str> {
+        self.params.get("realm").map(|r| r.as_str())
+    }
+}
+
+/// Parse every challenge from the `WWW-Authenticate` headers of a response. A header may contain
+/// several comma-separated challenges, and there may be several headers.
+pub fn parse_www_authenticate(headers: &[HttpResponseHeader]) -> Vec<AuthChallenge> {
+    headers
+        .iter()
+        .filter(|h| h.name.eq_ignore_ascii_case("www-authenticate"))
+        .flat_map(|h| parse_challenges(&h.value))
+        .collect()
+}
+
+fn parse_challenges(value: &str) -> Vec<AuthChallenge> {
+    let mut p = ChallengeParser {
+        chars: value.chars().collect(),
+        i: 0,
+    };
+    let mut challenges: Vec<AuthChallenge> = Vec::new();
+
+    loop {
+        let saw_comma = p.skip_separators();
+        if p.at_end() {
+            break;
+        }
+
+        let token = p.read_while(|c| !c.is_whitespace() && c != ',' && c != '=');
+        if token.is_empty() {
+            // Stray '=' or similar, so skip it
+            p.i += 1;
+            continue;
+        }
+
+        p.skip_whitespace();
+        let equals = p.read_while(|c| c == '=');
+        p.skip_whitespace();
+        let value_follows = !p.at_end() && p.peek() != Some(',');
+
+        match challenges.last_mut() {
+            // name=value (or name="value") is a param of the current challenge
+            Some(c) if equals.len() == 1 && value_follows => {
+                let value = match p.peek() {
+                    Some('"') => p.read_quoted(),
+                    _ => p.read_while(|c| !c.is_whitespace() && c != ','),
+                };
+                c.params.insert(token.to_lowercase(), value);
+            }
+            // A token directly after the scheme is a token68, possibly padded with '='
+            Some(c)
+                if !saw_comma && !value_follows && c.params.is_empty() && c.token68.is_none() =>
+            {
+                c.token68 = Some(format!("{token}{equals}"));
+            }
+            _ if equals.is_empty() => challenges.push(AuthChallenge {
+                scheme: token,
+                ..Default::default()
+            }),
+            _ => {
+                // Malformed param, so skip past its value
+                p.read_while(|c| c != ',');
+            }
+        }
+    }
+
+    challenges
+}
+
+struct ChallengeParser {
+    chars: Vec<char>,
+    i: usize,
+}
+
+impl ChallengeParser {
+    fn at_end(&self) -> bool {
+        self.i >= self.chars.len()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.i).copied()
+    }
+
+    fn read_while(&mut self, f: impl Fn(char) -> bool) -> String {
+        let start = self.i;
+        while self.peek().is_some_and(&f) {
+            self.i += 1;
+        }
+        self.chars[start..self.i].iter().collect()
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.read_while(|c| c.is_whitespace());
+    }
+
+    /// Skip whitespace and commas, returning whether a comma was skipped
+    fn skip_separators(&mut self) -> bool {
+        self.read_while(|c| c.is_whitespace() || c == ',').contains(',')
+    }
+
+    fn read_quoted(&mut self) -> String {
+        let mut value = String::new();
+        self.i += 1; // Opening quote
+        while let Some(c) = self.peek() {
+            self.i += 1;
+            match c {
+                '"' => break,
+                '\\' => {
+                    if let Some(escaped) = self.peek() {
+                        value.push(escaped);
+                        self.i += 1;
+                    }
+                }
+                c => value.push(c),
+            }
+        }
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::auth_challenge::{AuthChallenge, parse_challenges, parse_www_authenticate};
+    use std::collections::BTreeMap;
+    use yaak_models::models::HttpResponseHeader;
+
+    fn params(p: &[(&str, &str)]) -> BTreeMap<String, String> {
+        p.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn basic_with_realm() {
+        let challenges = parse_challenges(r#"Basic realm="Dev Server", charset="UTF-8""#);
+        assert_eq!(
+            challenges,
+            vec![AuthChallenge {
+                scheme: "Basic".into(),
+                params: params(&[("realm", "Dev Server"), ("charset", "UTF-8")]),
+                token68: None,
+            }]
+        );
+        assert_eq!(challenges[0].realm(), Some("Dev Server"));
+    }
+
+    #[test]
+    fn multiple_challenges() {
+        let challenges = parse_challenges(
+            r#"Bearer realm="api", error="invalid_token", error_description="The \"token\" expired", Basic realm=legacy"#,
+        );
+        assert_eq!(
+            challenges,
+            vec![
+                AuthChallenge {
+                    scheme: "Bearer".into(),
+                    params: params(&[
+                        ("realm", "api"),
+                        ("error", "invalid_token"),
+                        ("error_description", r#"The "token" expired"#),
+                    ]),
+                    token68: None,
+                },
+                AuthChallenge {
+                    scheme: "Basic".into(),
+                    params: params(&[("realm", "legacy")]),
+                    token68: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn token68_and_bare_schemes() {
+        let challenges = parse_challenges("Negotiate YIIBhgYGKwYBBQUCoIIB==, NTLM");
+        assert_eq!(
+            challenges,
+            vec![
+                AuthChallenge {
+                    scheme: "Negotiate".into(),
+                    params: BTreeMap::new(),
+                    token68: Some("YIIBhgYGKwYBBQUCoIIB==".into()),
+                },
+                AuthChallenge {
+                    scheme: "NTLM".into(),
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn from_response_headers() {
+        let headers = vec![
+            HttpResponseHeader {
+                name: "Content-Type".into(),
+                value: "text/plain".into(),
+            },
+            HttpResponseHeader {
+                name: "WWW-Authenticate".into(),
+                value: "Basic realm=a".into(),
+            },
+            HttpResponseHeader {
+                name: "www-authenticate".into(),
+                value: r#"Digest realm="b", qop="auth,auth-int", nonce="abc""#.into(),
+            },
+        ];
+        let challenges = parse_www_authenticate(&headers);
+        assert_eq!(challenges.len(), 2);
+        assert_eq!(challenges[0].scheme, "Basic");
+        assert_eq!(challenges[1].scheme, "Digest");
+        assert_eq!(challenges[1].params.get("qop").map(|s| s.as_str()), Some("auth,auth-int"));
+        assert_eq!(challenges[1].params.get("nonce").map(|s| s.as_str()), Some("abc"));
+    }
+
+    #[test]
+    fn empty_and_malformed() {
+        assert_eq!(parse_challenges(""), vec![]);
+        assert_eq!(parse_challenges(" , "), vec![]);
+        assert_eq!(parse_challenges("realm=orphaned"), vec![]);
+    }
+}
@@ -0,0 +1,249 @@
+use sha2::{Digest, Sha256};
+use yaak_models::models::{HttpRequest, HttpResponse, HttpResponseHeader, HttpResponseState};
+
+/// Whether a response's body can be reused when a later conditional request for the same
+/// resource gets `304 Not Modified`. It has to be a complete `200` with a body on disk, and
+/// something to revalidate it with.
+pub fn is_cacheable(response: &HttpResponse) -> bool {
+    matches!(response.state, HttpResponseState::Closed)
+        && response.status == 200
+        && response.error.is_none()
+        && response.body_path.is_some()
+        && !conditional_headers(&response.headers).is_empty()
+}
+
+/// Headers that ask the server to only send a response again if it changed, made from the
+/// `ETag` and `Last-Modified` of the previous one
+pub fn conditional_headers(headers: &[HttpResponseHeader]) -> Vec<(&'static str, String)> {
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|h| h.name.eq_ignore_ascii_case(name))
+            .map(|h| h.value.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+
+    let mut conditional = Vec::new();
+    if let Some(etag) = header("etag") {
+        conditional.push(("if-none-match", etag));
+    }
+    if let Some(last_modified) = header("last-modified") {
+        conditional.push(("if-modified-since", last_modified));
+    }
+    conditional
+}
+
+/// Seconds a response spent in a shared cache, like a CDN or proxy, from its `Age` header
+pub fn cache_age(headers: &[HttpResponseHeader]) -> Option<i32> {
+    let age = headers.iter().find(|h| h.name.eq_ignore_ascii_case("age"))?;
+    let age = age.value.trim().parse::<u64>().ok()?;
+    Some(age.min(i32::MAX as u64) as i32)
+}
+
+/// Headers that CDNs and proxies report a cache hit or miss in
+const CACHE_STATUS_HEADERS: [&str; 4] = [
+    "x-cache",
+    "x-cache-status",
+    "cf-cache-status",
+    "x-proxy-cache",
+];
+
+/// Whether a shared cache, rather than the origin server, answered with a response. That's the
+/// case when it has a nonzero `Age`, a hit in a header like `X-Cache` or `CF-Cache-Status`, or a
+/// `Warning` that only caches add, like `110 Response is Stale`.
+pub fn served_from_cache(headers: &[HttpResponseHeader]) -> bool {
+    let is_hit = |h: &HttpResponseHeader| {
+        CACHE_STATUS_HEADERS.iter().any(|n| h.name.eq_ignore_ascii_case(n))
+            && h.value.to_lowercase().contains("hit")
+    };
+    // Warn codes 110-113 are about a cached response being stale or unvalidated
+    let is_cache_warning = |h: &HttpResponseHeader| {
+        let mut codes = h.value.split(',').filter_map(|w| w.trim().get(..3));
+        h.name.eq_ignore_ascii_case("warning")
+            && codes.any(|c| matches!(c, "110" | "111" | "112" | "113"))
+    };
+    cache_age(headers).is_some_and(|a| a > 0)
+        || headers.iter().any(|h| is_hit(h) || is_cache_warning(h))
+}
+
+/// Headers left out of a cache key, since they're worked out again for each send
+const UNKEYED_HEADERS: [&str; 4] = [
+    "content-length",
+    "host",
+    "if-modified-since",
+    "if-none-match",
+];
+
+/// Key that's the same for any two sends of the same request, for telling whether a request was
+/// sent before or a stored response can answer it. `request` should already be rendered.
+///
+/// The key covers the method (ignoring case), the URL, enabled URL parameters, enabled headers
+/// sent in the environment (ignoring name case and order, and leaving out [`UNKEYED_HEADERS`])
+/// and the body type and body. The name, folder, auth settings and other options aren't part of
+/// it, and neither are cookies.
+pub fn request_cache_key(request: &HttpRequest, environment_id: Option<&str>) -> String {
+    let mut headers = request
+        .headers
+        .iter()
+        .filter(|h| h.enabled && !h.name.is_empty() && h.in_environment(environment_id))
+        .map(|h| (h.name.to_lowercase(), h.value.as_str()))
+        .filter(|(n, _)| !UNKEYED_HEADERS.contains(&n.as_str()))
+        .collect::<Vec<_>>();
+    headers.sort();
+
+    let mut hasher = Sha256::new();
+    hasher.update(request.method.to_uppercase());
+    hasher.update("\n");
+    hasher.update(&request.url);
+    hasher.update("\n");
+    for p in request.url_parameters.iter().filter(|p| p.enabled && !p.name.is_empty()) {
+        hasher.update(format!("{}={}\n", p.name, p.value));
+    }
+    for (name, value) in headers {
+        hasher.update(format!("{name}: {value}\n"));
+    }
+    hasher.update(request.body_type.as_deref().unwrap_or_default());
+    hasher.update("\n");
+    hasher.update(serde_json::to_string(&request.body).unwrap_or_default());
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cache::{
+        cache_age, conditional_headers, is_cacheable, request_cache_key, served_from_cache,
+    };
+    use yaak_models::models::{
+        HttpRequest, HttpRequestHeader, HttpResponse, HttpResponseHeader, HttpResponseState,
+    };
+
+    fn header(name: &str, value: &str) -> HttpResponseHeader {
+        HttpResponseHeader {
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn headers_from_validators() {
+        let headers = vec![
+            header("ETag", "\"abc\""),
+            header("last-modified", "Wed, 21 Oct 2015 07:28:00 GMT"),
+            header("content-type", "application/json"),
+        ];
+        assert_eq!(
+            conditional_headers(&headers),
+            vec![
+                ("if-none-match", "\"abc\"".to_string()),
+                ("if-modified-since", "Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+            ]
+        );
+        assert_eq!(conditional_headers(&[header("etag", " ")]), vec![]);
+        assert_eq!(conditional_headers(&[]), vec![]);
+    }
+
+    #[test]
+    fn cacheable_responses() {
+        let response = HttpResponse {
+            state: HttpResponseState::Closed,
+            status: 200,
+            body_path: Some("/tmp/body".to_string()),
+            headers: vec![header("etag", "W/\"1\"")],
+            ..Default::default()
+        };
+        assert!(is_cacheable(&response));
+        assert!(!is_cacheable(&HttpResponse {
+            status: 304,
+            ..response.clone()
+        }));
+        assert!(!is_cacheable(&HttpResponse {
+            headers: vec![],
+            ..response.clone()
+        }));
+        assert!(!is_cacheable(&HttpResponse {
+            error: Some("Failed".to_string()),
+            ..response.clone()
+        }));
+        assert!(!is_cacheable(&HttpResponse {
+            body_path: None,
+            ..response
+        }));
+    }
+
+    #[test]
+    fn age_header() {
+        assert_eq!(cache_age(&[header("Age", " 120 ")]), Some(120));
+        assert_eq!(cache_age(&[header("age", "-1")]), None);
+        assert_eq!(cache_age(&[header("age", "99999999999")]), Some(i32::MAX));
+        assert_eq!(cache_age(&[]), None);
+    }
+
+    #[test]
+    fn responses_from_cache() {
+        assert!(served_from_cache(&[header("age", "3")]));
+        assert!(served_from_cache(&[header("X-Cache", "Hit from cloudfront")]));
+        assert!(served_from_cache(&[header("cf-cache-status", "HIT")]));
+        assert!(served_from_cache(&[header("warning", "110 - \"Response is Stale\"")]));
+        assert!(!served_from_cache(&[header("age", "0")]));
+        assert!(!served_from_cache(&[header("x-cache", "Miss from cloudfront")]));
+        assert!(!served_from_cache(&[header("warning", "199 - \"Miscellaneous\"")]));
+        assert!(!served_from_cache(&[header("x-hit-count", "hit")]));
+    }
+
+    #[test]
+    fn cache_keys() {
+        let request_header = |name: &str, value: &str| HttpRequestHeader {
+            enabled: true,
+            name: name.to_string(),
+            value: value.to_string(),
+            ..Default::default()
+        };
+        let request = HttpRequest {
+            name: "Users".to_string(),
+            method: "get".to_string(),
+            url: "https://example.com/users".to_string(),
+            headers: vec![request_header("Accept", "*/*"), request_header("X-Id", "1")],
+            ..Default::default()
+        };
+        let key = request_cache_key(&request, None);
+        assert_eq!(key.len(), 64);
+
+        let same = HttpRequest {
+            name: "Renamed".to_string(),
+            method: "GET".to_string(),
+            headers: vec![
+                request_header("x-id", "1"),
+                request_header("accept", "*/*"),
+                request_header("If-None-Match", "\"abc\""),
+                HttpRequestHeader {
+                    enabled: false,
+                    ..request_header("X-Debug", "true")
+                },
+                HttpRequestHeader {
+                    environments: vec!["env_prod".to_string()],
+                    ..request_header("X-Prod", "true")
+                },
+            ],
+            ..request.clone()
+        };
+        assert_eq!(request_cache_key(&same, Some("env_dev")), key);
+        assert_ne!(request_cache_key(&same, Some("env_prod")), key);
+
+        let different = HttpRequest {
+            url: "https://example.com/users/1".to_string(),
+            ..request.clone()
+        };
+        assert_ne!(request_cache_key(&different, None), key);
+        let different = HttpRequest {
+            headers: vec![request_header("X-Id", "2")],
+            ..request.clone()
+        };
+        assert_ne!(request_cache_key(&different, None), key);
+        let different = HttpRequest {
+            body_type: Some("text/plain".to_string()),
+            body: [("text".to_string(), "hi".into())].into(),
+            ..request.clone()
+        };
+        assert_ne!(request_cache_key(&different, None), key);
+    }
+}
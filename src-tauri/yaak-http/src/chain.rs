@@ -0,0 +1,167 @@
+use crate::status::status_in_ranges;
+use std::ops::RangeInclusive;
+use yaak_models::models::{HttpRequest, HttpResponse};
+
+/// Statuses of a request in a chain that let the requests after it be sent
+const SUCCESS_STATUSES: RangeInclusive<u16> = 200..=299;
+
+/// Requests to send to run a request after everything it depends on, ending with the request
+/// itself. Each request comes after its dependencies, in the order they're listed, and is only
+/// sent once, even when several requests depend on it.
+pub fn dependency_order(
+    request_id: &str,
+    requests: &[HttpRequest],
+) -> Result<Vec<HttpRequest>, String> {
+    let mut order = Vec::new();
+    visit(request_id, requests, &mut Vec::new(), &mut order)?;
+    Ok(order)
+}
+
+/// Whether a request in a chain failed, so the requests after it shouldn't be sent. That's an
+/// error sending it, or a status outside 2xx, since a request that depends on a login can't use
+/// one that was refused.
+pub fn is_chain_failure(response: &HttpResponse) -> bool {
+    response.error.is_some() || !status_in_ranges(&[SUCCESS_STATUSES], response.status as u16)
+}
+
+/// Index of the request that stopped a folder run, which is the first one that failed by the
+/// same rule as a chain, if the run stops on errors
+pub fn folder_stopped_at(responses: &[HttpResponse], stop_on_error: bool) -> Option<usize> {
+    if !stop_on_error {
+        return None;
+    }
+    responses.iter().position(is_chain_failure)
+}
+
+/// Add a request to the order after its dependencies, where `path` is the chain of requests
+/// that led to it, to spot one that depends on itself
+fn visit<'a>(
+    request_id: &str,
+    requests: &'a [HttpRequest],
+    path: &mut Vec<&'a HttpRequest>,
+    order: &mut Vec<HttpRequest>,
+) -> Result<(), String> {
+    if order.iter().any(|r| r.id == request_id) {
+        return Ok(());
+    }
+
+    let request = match requests.iter().find(|r| r.id == request_id) {
+        Some(r) => r,
+        None => {
+            return Err(match path.last() {
+                Some(r) => format!("{} depends on a missing request {request_id}", label(r)),
+                None => format!("Request {request_id} not found"),
+            });
+        }
+    };
+
+    if let Some(i) = path.iter().position(|r| r.id == request_id) {
+        let cycle = path[i..].iter().chain([&request]).map(|r| label(r)).collect::<Vec<_>>();
+        return Err(format!("Requests depend on each other in a cycle: {}", cycle.join(" → ")));
+    }
+
+    path.push(request);
+    for id in &request.depends_on {
+        visit(id, requests, path, order)?;
+    }
+    path.pop();
+    order.push(request.clone());
+    Ok(())
+}
+
+/// Name of a request for errors, or its URL if it has no name
+fn label(request: &HttpRequest) -> String {
+    match request.name.trim() {
+        "" => request.url.clone(),
+        name => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chain::{dependency_order, folder_stopped_at, is_chain_failure};
+    use yaak_models::models::{HttpRequest, HttpResponse};
+
+    fn request(id: &str, depends_on: &[&str]) -> HttpRequest {
+        HttpRequest {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn ids(requests: Vec<HttpRequest>) -> Vec<String> {
+        requests.into_iter().map(|r| r.id).collect()
+    }
+
+    #[test]
+    fn orders_dependencies_first() {
+        let requests = vec![
+            request("call", &["login", "config"]),
+            request("config", &["login"]),
+            request("login", &[]),
+        ];
+        assert_eq!(
+            ids(dependency_order("call", &requests).unwrap()),
+            vec!["login", "config", "call"]
+        );
+        assert_eq!(ids(dependency_order("login", &requests).unwrap()), vec!["login"]);
+    }
+
+    #[test]
+    fn reports_cycles() {
+        let requests = vec![
+            request("a", &["b"]),
+            request("b", &["c"]),
+            request("c", &["b"]),
+        ];
+        assert_eq!(
+            dependency_order("a", &requests),
+            Err("Requests depend on each other in a cycle: B → C → B".to_string())
+        );
+
+        let requests = vec![request("a", &["a"])];
+        assert_eq!(
+            dependency_order("a", &requests),
+            Err("Requests depend on each other in a cycle: A → A".to_string())
+        );
+    }
+
+    #[test]
+    fn reports_missing_requests() {
+        let requests = vec![request("a", &["gone"])];
+        assert_eq!(
+            dependency_order("a", &requests),
+            Err("A depends on a missing request gone".to_string())
+        );
+        assert_eq!(dependency_order("nope", &requests), Err("Request nope not found".to_string()));
+    }
+
+    #[test]
+    fn fails_on_errors_and_unsuccessful_statuses() {
+        let response = |status: i32, error: Option<&str>| HttpResponse {
+            status,
+            error: error.map(|e| e.to_string()),
+            ..Default::default()
+        };
+        assert!(!is_chain_failure(&response(200, None)));
+        assert!(!is_chain_failure(&response(204, None)));
+        assert!(is_chain_failure(&response(302, None)));
+        assert!(is_chain_failure(&response(401, None)));
+        assert!(is_chain_failure(&response(503, None)));
+        assert!(is_chain_failure(&response(200, Some("Connection reset"))));
+        assert!(is_chain_failure(&response(0, Some("Connection refused"))));
+    }
+    #[test]
+    fn stops_folder_runs_at_first_failure() {
+        let response = |status: i32| HttpResponse {
+            status,
+            ..Default::default()
+        };
+        let responses = vec![response(200), response(401), response(500)];
+        assert_eq!(folder_stopped_at(&responses, true), Some(1));
+        assert_eq!(folder_stopped_at(&responses, false), None);
+        assert_eq!(folder_stopped_at(&responses[..1], true), None);
+    }
+}
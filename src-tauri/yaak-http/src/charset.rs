@@ -0,0 +1,90 @@
+use encoding_rs::{Encoding, UTF_8};
+
+/// Value of the `charset` parameter of a `Content-Type`, without quotes
+pub fn content_type_charset(content_type: &str) -> Option<&str> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        match name.trim().eq_ignore_ascii_case("charset") {
+            true => Some(value.trim().trim_matches('"')),
+            false => None,
+        }
+    })
+}
+
+/// `Content-Type` with `; charset=utf-8` added, for text and JSON types that don't name a charset
+/// yet. Returns `None` when the type should be left as it is.
+pub fn with_utf8_charset(content_type: &str) -> Option<String> {
+    let mime = content_type.split(';').next().unwrap_or_default().trim().to_lowercase();
+    let is_text = mime.starts_with("text/")
+        || mime == "application/json"
+        || mime.ends_with("+json")
+        || mime == "application/xml"
+        || mime.ends_with("+xml");
+    match is_text && content_type_charset(content_type).is_none() {
+        true => Some(format!("{}; charset=utf-8", content_type.trim_end_matches([';', ' ']))),
+        false => None,
+    }
+}
+
+/// Encode a body in a charset, like `ISO-8859-1`. Fails for charsets that are unknown or can only
+/// be decoded, and for bodies with characters the charset can't represent.
+pub fn encode_body(body: &str, charset: &str) -> Result<Vec<u8>, String> {
+    let encoding = match Encoding::for_label(charset.as_bytes()) {
+        Some(e) => e,
+        None => return Err(format!("Unknown charset {charset:?}")),
+    };
+    if encoding == UTF_8 {
+        return Ok(body.as_bytes().to_vec());
+    }
+
+    // UTF-16 and a few others only decode, so encoding_rs would silently produce UTF-8
+    if encoding.output_encoding() != encoding {
+        return Err(format!("Bodies can't be encoded as {charset}"));
+    }
+
+    let (bytes, _, unmappable) = encoding.encode(body);
+    if unmappable {
+        return Err(format!("Body has characters that can't be encoded as {charset}"));
+    }
+    Ok(bytes.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::charset::{content_type_charset, encode_body, with_utf8_charset};
+
+    #[test]
+    fn reads_charset() {
+        assert_eq!(content_type_charset("text/plain; charset=utf-8"), Some("utf-8"));
+        assert_eq!(content_type_charset("text/plain;Charset=\"latin1\""), Some("latin1"));
+        assert_eq!(content_type_charset("text/plain; format=flowed"), None);
+        assert_eq!(content_type_charset("text/plain"), None);
+    }
+
+    #[test]
+    fn adds_utf8_charset() {
+        assert_eq!(
+            with_utf8_charset("application/json"),
+            Some("application/json; charset=utf-8".to_string())
+        );
+        assert_eq!(
+            with_utf8_charset("text/plain; format=flowed"),
+            Some("text/plain; format=flowed; charset=utf-8".to_string())
+        );
+        assert_eq!(
+            with_utf8_charset("application/vnd.api+json"),
+            Some("application/vnd.api+json; charset=utf-8".to_string())
+        );
+        assert_eq!(with_utf8_charset("text/plain; charset=iso-8859-1"), None);
+        assert_eq!(with_utf8_charset("application/octet-stream"), None);
+    }
+
+    #[test]
+    fn encodes_body() {
+        assert_eq!(encode_body("café", "utf-8").unwrap(), "café".as_bytes());
+        assert_eq!(encode_body("café", "ISO-8859-1").unwrap(), b"caf\xe9");
+        assert!(encode_body("日本", "ISO-8859-1").is_err());
+        assert!(encode_body("café", "utf-16le").is_err());
+        assert!(encode_body("café", "not-a-charset").is_err());
+    }
+}
@@ -0,0 +1,158 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// One row of a data file, mapping column names to values
+pub type DataRow = BTreeMap<String, String>;
+
+/// Parse the rows of a data file, as JSON if it's named `*.json` and CSV otherwise
+pub fn parse_data_file(file_name: &str, contents: &str) -> Result<Vec<DataRow>, String> {
+    match file_name.to_lowercase().ends_with(".json") {
+        true => parse_json_rows(contents),
+        false => parse_csv_rows(contents),
+    }
+}
+
+/// Parse a JSON array of objects. Strings are used as-is and anything else is stringified, so
+/// `{"id": 1}` gives the same row as the CSV `id\n1`.
+pub fn parse_json_rows(contents: &str) -> Result<Vec<DataRow>, String> {
+    let rows: Vec<serde_json::Map<String, Value>> = serde_json::from_str(contents)
+        .map_err(|e| format!("Data file must be an array of objects: {e}"))?;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|(k, v)| match v {
+                    Value::String(s) => (k, s),
+                    Value::Null => (k, "".to_string()),
+                    v => (k, v.to_string()),
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Parse CSV with a header row of column names. Fields may be quoted, with `""` for a literal
+/// quote, which allows commas and newlines inside them. Blank lines are skipped.
+pub fn parse_csv_rows(contents: &str) -> Result<Vec<DataRow>, String> {
+    let mut records = parse_csv(contents.strip_prefix('\u{feff}').unwrap_or(contents))?.into_iter();
+    let header = match records.next() {
+        Some(h) => h,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut rows = Vec::new();
+    for (i, record) in records.enumerate() {
+        if record.len() != header.len() {
+            return Err(format!(
+                "Row {} has {} columns but the header has {}",
+                i + 1,
+                record.len(),
+                header.len()
+            ));
+        }
+        rows.push(header.iter().cloned().zip(record).collect());
+    }
+    Ok(rows)
+}
+
+fn parse_csv(contents: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = contents.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => record.push(std::mem::take(&mut field)),
+            ('\r', false) if chars.peek() == Some(&'\n') => {}
+            ('\n', false) => {
+                record.push(std::mem::take(&mut field));
+                if record != [""] {
+                    records.push(std::mem::take(&mut record));
+                }
+                record.clear();
+            }
+            (c, _) => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err("Data file has an unclosed quote".to_string());
+    }
+
+    record.push(field);
+    if record != [""] {
+        records.push(record);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_file::{DataRow, parse_csv_rows, parse_data_file, parse_json_rows};
+
+    fn row(pairs: &[(&str, &str)]) -> DataRow {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn csv_rows() {
+        assert_eq!(
+            parse_csv_rows("id,name\r\n1,Alice\r\n\r\n2,Bob\n").unwrap(),
+            vec![
+                row(&[("id", "1"), ("name", "Alice")]),
+                row(&[("id", "2"), ("name", "Bob")])
+            ]
+        );
+        assert_eq!(parse_csv_rows("").unwrap(), vec![]);
+        assert_eq!(parse_csv_rows("id,name").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn csv_quoting() {
+        assert_eq!(
+            parse_csv_rows("\u{feff}q,n\n\"a, \"\"b\"\"\nc\",\n").unwrap(),
+            vec![row(&[("q", "a, \"b\"\nc"), ("n", "")])]
+        );
+        assert!(parse_csv_rows("q\n\"open").is_err());
+    }
+
+    #[test]
+    fn csv_column_mismatch() {
+        assert_eq!(
+            parse_csv_rows("a,b\n1,2\n3").unwrap_err(),
+            "Row 2 has 1 columns but the header has 2"
+        );
+    }
+
+    #[test]
+    fn json_rows() {
+        assert_eq!(
+            parse_json_rows(r#"[{"id": 1, "name": "Alice", "admin": true, "team": null}]"#)
+                .unwrap(),
+            vec![row(&[
+                ("id", "1"),
+                ("name", "Alice"),
+                ("admin", "true"),
+                ("team", "")
+            ])]
+        );
+        assert!(parse_json_rows(r#"{"id": 1}"#).is_err());
+    }
+
+    #[test]
+    fn by_file_name() {
+        assert_eq!(
+            parse_data_file("rows.JSON", r#"[{"a": "1"}]"#).unwrap(),
+            vec![row(&[("a", "1")])]
+        );
+        assert_eq!(parse_data_file("rows.csv", "a\n1").unwrap(), vec![row(&[("a", "1")])]);
+    }
+}
@@ -0,0 +1,175 @@
+use flate2::write::{GzDecoder, ZlibDecoder};
+use std::io::{self, Write};
+
+/// Streaming decoder for a response body's `Content-Encoding`. Bodies are decoded as they're
+/// read, rather than by the client, so the number of bytes that came over the wire is known.
+pub enum BodyDecoder {
+    Gzip(GzDecoder<LimitedBuf>),
+    Deflate(ZlibDecoder<LimitedBuf>),
+    Brotli(Box<brotli::DecompressorWriter<LimitedBuf>>),
+}
+
+impl BodyDecoder {
+    /// Decoder for a `Content-Encoding`, or None for `identity` and encodings that aren't
+    /// supported, which are left as-is. Decoding fails once the body decodes to more than
+    /// `max_size` bytes, unless it's 0.
+    pub fn new(content_encoding: &str, max_size: usize) -> Option<Self> {
+        let buf = LimitedBuf::new(max_size);
+        match content_encoding.trim().to_lowercase().as_str() {
+            "gzip" | "x-gzip" => Some(BodyDecoder::Gzip(GzDecoder::new(buf))),
+            // HTTP's "deflate" is zlib-wrapped, despite the name
+            "deflate" => Some(BodyDecoder::Deflate(ZlibDecoder::new(buf))),
+            "br" => Some(BodyDecoder::Brotli(Box::new(brotli::DecompressorWriter::new(buf, 4096)))),
+            _ => None,
+        }
+    }
+
+    /// Decode the next chunk of the body, returning what it decoded to so far
+    pub fn decode(&mut self, chunk: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            BodyDecoder::Gzip(d) => {
+                d.write_all(chunk)?;
+                d.flush()?;
+                Ok(d.get_mut().take())
+            }
+            BodyDecoder::Deflate(d) => {
+                d.write_all(chunk)?;
+                d.flush()?;
+                Ok(d.get_mut().take())
+            }
+            BodyDecoder::Brotli(d) => {
+                d.write_all(chunk)?;
+                d.flush()?;
+                Ok(d.get_mut().take())
+            }
+        }
+    }
+
+    /// Finish decoding once the whole body was read, returning anything left over. Fails if the
+    /// body was cut off partway through.
+    pub fn finish(self) -> io::Result<Vec<u8>> {
+        match self {
+            BodyDecoder::Gzip(d) => Ok(d.finish()?.take()),
+            BodyDecoder::Deflate(d) => Ok(d.finish()?.take()),
+            BodyDecoder::Brotli(mut d) => {
+                d.close()?;
+                Ok(d.get_mut().take())
+            }
+        }
+    }
+}
+
+/// Buffer for decoded bytes that fails once more than `max_size` bytes were written to it in
+/// total, so a small body can't decode to gigabytes in memory or on disk
+pub struct LimitedBuf {
+    buf: Vec<u8>,
+    written: usize,
+    max_size: usize,
+}
+
+impl LimitedBuf {
+    fn new(max_size: usize) -> Self {
+        LimitedBuf {
+            buf: Vec::new(),
+            written: 0,
+            max_size,
+        }
+    }
+
+    /// Bytes written since the last call
+    fn take(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.buf)
+    }
+}
+
+impl Write for LimitedBuf {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.max_size > 0 && self.written + buf.len() > self.max_size {
+            return Err(io::Error::other(format!(
+                "body decodes to more than the {} byte limit",
+                self.max_size
+            )));
+        }
+        self.written += buf.len();
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decode::BodyDecoder;
+    use flate2::Compression;
+    use flate2::write::{GzEncoder, ZlibEncoder};
+    use std::io::Write;
+
+    fn decode_in_chunks(mut decoder: BodyDecoder, encoded: &[u8]) -> Vec<u8> {
+        let mut decoded = Vec::new();
+        for chunk in encoded.chunks(7) {
+            decoded.extend(decoder.decode(chunk).unwrap());
+        }
+        decoded.extend(decoder.finish().unwrap());
+        decoded
+    }
+
+    #[test]
+    fn decodes_gzip() {
+        let body = "hello world ".repeat(100);
+        let mut e = GzEncoder::new(Vec::new(), Compression::default());
+        e.write_all(body.as_bytes()).unwrap();
+        let encoded = e.finish().unwrap();
+
+        assert!(encoded.len() < body.len());
+        let decoder = BodyDecoder::new("GZIP", 0).unwrap();
+        assert_eq!(decode_in_chunks(decoder, &encoded), body.as_bytes());
+    }
+
+    #[test]
+    fn decodes_deflate() {
+        let body = "abc".repeat(50);
+        let mut e = ZlibEncoder::new(Vec::new(), Compression::default());
+        e.write_all(body.as_bytes()).unwrap();
+        let encoded = e.finish().unwrap();
+
+        let decoder = BodyDecoder::new("deflate", 0).unwrap();
+        assert_eq!(decode_in_chunks(decoder, &encoded), body.as_bytes());
+    }
+
+    #[test]
+    fn truncated_body_fails() {
+        let mut e = GzEncoder::new(Vec::new(), Compression::default());
+        e.write_all(b"some body that gets cut off").unwrap();
+        let encoded = e.finish().unwrap();
+
+        let mut decoder = BodyDecoder::new("gzip", 0).unwrap();
+        decoder.decode(&encoded[..encoded.len() / 2]).unwrap();
+        assert!(decoder.finish().is_err());
+    }
+
+    #[test]
+    fn limits_decoded_size() {
+        let body = vec![0u8; 1024 * 1024];
+        let mut e = GzEncoder::new(Vec::new(), Compression::best());
+        e.write_all(&body).unwrap();
+        let encoded = e.finish().unwrap();
+
+        let mut decoder = BodyDecoder::new("gzip", 64 * 1024).unwrap();
+        let result = decoder.decode(&encoded).and_then(|_| decoder.finish());
+        assert!(result.unwrap_err().to_string().contains("65536 byte limit"));
+
+        // The limit is for the whole body, not each chunk
+        let decoder = BodyDecoder::new("gzip", body.len()).unwrap();
+        assert_eq!(decode_in_chunks(decoder, &encoded), body);
+    }
+
+    #[test]
+    fn unsupported_encodings() {
+        assert!(BodyDecoder::new("identity", 0).is_none());
+        assert!(BodyDecoder::new("zstd", 0).is_none());
+        assert!(BodyDecoder::new("gzip, br", 0).is_none());
+    }
+}
@@ -0,0 +1,78 @@
+use std::error::Error;
+
+/// Message for an error followed by its sources, down to the root cause, like
+/// `error sending request: client error (Connect): Connection refused (os error 111)`. Wrapping
+/// errors often say only that something failed, while the source says why. Sources whose message
+/// is already part of the one before them are skipped.
+pub fn error_chain(e: &dyn Error) -> String {
+    let mut message = e.to_string();
+    let mut source = e.source();
+    while let Some(s) = source {
+        let s_message = s.to_string();
+        if !s_message.is_empty() && !message.contains(&s_message) {
+            message = format!("{message}: {s_message}");
+        }
+        source = s.source();
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::error_chain;
+    use std::error::Error;
+    use std::fmt;
+
+    #[derive(Debug)]
+    struct TestError {
+        message: &'static str,
+        source: Option<Box<TestError>>,
+    }
+
+    impl fmt::Display for TestError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.message)
+        }
+    }
+
+    impl Error for TestError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            self.source.as_ref().map(|s| s.as_ref() as &(dyn Error + 'static))
+        }
+    }
+
+    fn chain(messages: &[&'static str]) -> TestError {
+        let mut error = None;
+        for message in messages.iter().rev() {
+            error = Some(TestError {
+                message,
+                source: error.map(Box::new),
+            });
+        }
+        error.unwrap()
+    }
+
+    #[test]
+    fn no_source() {
+        assert_eq!(error_chain(&chain(&["request failed"])), "request failed");
+    }
+
+    #[test]
+    fn includes_root_cause() {
+        let e = chain(&[
+            "error sending request",
+            "client error (Connect)",
+            "Connection refused",
+        ]);
+        assert_eq!(
+            error_chain(&e),
+            "error sending request: client error (Connect): Connection refused"
+        );
+    }
+
+    #[test]
+    fn skips_repeated_sources() {
+        let e = chain(&["dns error: no such host", "no such host", ""]);
+        assert_eq!(error_chain(&e), "dns error: no such host");
+    }
+}
@@ -0,0 +1,296 @@
+use serde_json::Value;
+
+/// A variable declared by a GraphQL operation, like `$id: ID!`
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableDefinition {
+    pub name: String,
+    /// Non-null and without a default, so it has to be provided
+    pub required: bool,
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Name(String),
+    Variable(String),
+    Punct(char),
+}
+
+/// Warnings for variables that don't match what the query's operation declares. Only documents
+/// with a single operation are checked, since there's no way to tell which of several is sent.
+pub fn variable_warnings(query: &str, variables: &str) -> Vec<String> {
+    let definitions = match operation_variables(query) {
+        Some(d) => d,
+        None => return Vec::new(),
+    };
+
+    let provided = match variables.trim() {
+        "" => serde_json::Map::new(),
+        v => match serde_json::from_str::<Value>(v) {
+            Ok(Value::Object(o)) => o,
+            Ok(_) => return vec!["GraphQL variables should be a JSON object".to_string()],
+            Err(e) => return vec![format!("GraphQL variables are not valid JSON: {e}")],
+        },
+    };
+
+    let mut warnings = Vec::new();
+    for d in &definitions {
+        let value = provided.get(&d.name).unwrap_or(&Value::Null);
+        if d.required && value.is_null() {
+            warnings.push(format!("Variable ${} is required but was not provided", d.name));
+        }
+    }
+    for name in provided.keys() {
+        if !definitions.iter().any(|d| &d.name == name) {
+            warnings.push(format!("Variable ${name} is not declared by the operation"));
+        }
+    }
+    warnings
+}
+
+/// Variables declared by the operation in a GraphQL document, or None when the document doesn't
+/// contain exactly one operation
+pub fn operation_variables(query: &str) -> Option<Vec<VariableDefinition>> {
+    let tokens = tokenize(query);
+    let mut operations: Vec<Vec<VariableDefinition>> = Vec::new();
+    let mut depth = 0;
+    let mut in_definition = false;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        match &tokens[i] {
+            Token::Punct('{') => {
+                // A selection set on its own is the shorthand for an anonymous query
+                if depth == 0 && !in_definition {
+                    operations.push(Vec::new());
+                }
+                depth += 1;
+            }
+            Token::Punct('}') => {
+                depth -= 1;
+                if depth == 0 {
+                    in_definition = false;
+                }
+            }
+            Token::Name(n) if depth == 0 && n == "fragment" => in_definition = true,
+            Token::Name(n)
+                if depth == 0 && matches!(n.as_str(), "query" | "mutation" | "subscription") =>
+            {
+                in_definition = true;
+                if let Some(Token::Name(_)) = tokens.get(i + 1) {
+                    i += 1;
+                }
+                let mut definitions = Vec::new();
+                if tokens.get(i + 1) == Some(&Token::Punct('(')) {
+                    let (d, end) = variable_definitions(&tokens, i + 1);
+                    definitions = d;
+                    i = end;
+                }
+                operations.push(definitions);
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    match operations.len() {
+        1 => operations.pop(),
+        _ => None,
+    }
+}
+
+/// Parse the variable definitions in the parentheses starting at `start`, returning them along
+/// with the index of the closing parenthesis
+fn variable_definitions(tokens: &[Token], start: usize) -> (Vec<VariableDefinition>, usize) {
+    let mut definitions: Vec<VariableDefinition> = Vec::new();
+    let mut nesting = 0;
+    let mut in_type = false;
+    let mut last_type_token = None;
+    let mut i = start;
+
+    let finish_type = |definitions: &mut Vec<VariableDefinition>, last: Option<&Token>| {
+        if let Some(d) = definitions.last_mut() {
+            d.required = last == Some(&Token::Punct('!'));
+        }
+    };
+
+    while i < tokens.len() {
+        let token = &tokens[i];
+        match token {
+            Token::Punct('(') | Token::Punct('[') | Token::Punct('{') => nesting += 1,
+            Token::Punct(')') | Token::Punct(']') | Token::Punct('}') => nesting -= 1,
+            _ => {}
+        }
+        if nesting == 0 {
+            break;
+        }
+
+        // Only the top level holds definitions. Deeper levels are list types, default values,
+        // and directive arguments.
+        let top_level = nesting == 1 && !matches!(token, Token::Punct('('));
+        match token {
+            Token::Variable(name) if top_level && tokens.get(i + 1) == Some(&Token::Punct(':')) => {
+                if in_type {
+                    finish_type(&mut definitions, last_type_token);
+                }
+                definitions.push(VariableDefinition {
+                    name: name.to_owned(),
+                    required: false,
+                });
+                in_type = true;
+                last_type_token = None;
+                i += 1;
+            }
+            Token::Punct('=') | Token::Punct('@') if top_level && in_type => {
+                // A default makes even a non-null variable optional
+                if matches!(token, Token::Punct('@')) {
+                    finish_type(&mut definitions, last_type_token);
+                }
+                in_type = false;
+            }
+            // Only the outermost part of a type says if it's non-null, like the last `!` in
+            // `[String!]!`
+            _ if in_type && nesting == 1 => last_type_token = Some(token),
+            _ => {}
+        }
+        i += 1;
+    }
+
+    if in_type {
+        finish_type(&mut definitions, last_type_token);
+    }
+    (definitions, i)
+}
+
+/// Split a GraphQL document into the tokens needed to find variable definitions. Comments and
+/// strings are dropped, since they can contain anything, and commas are insignificant.
+fn tokenize(query: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = query;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(r) = rest.strip_prefix("\"\"\"") {
+            let mut end = None;
+            let mut from = 0;
+            while let Some(pos) = r[from..].find("\"\"\"") {
+                if !r[..from + pos].ends_with('\\') {
+                    end = Some(from + pos);
+                    break;
+                }
+                from += pos + 3;
+            }
+            rest = end.map(|e| &r[e + 3..]).unwrap_or("");
+        } else if c == '"' {
+            let mut escaped = false;
+            let end = rest[1..].find(|c| {
+                let done = !escaped && (c == '"' || c == '\n');
+                escaped = !escaped && c == '\\';
+                done
+            });
+            rest = end.map(|e| &rest[e + 2..]).unwrap_or("");
+        } else if c == '#' {
+            rest = rest.find('\n').map(|e| &rest[e..]).unwrap_or("");
+        } else if c == '$' || c == '_' || c.is_ascii_alphanumeric() {
+            let name_start = if c == '$' { 1 } else { 0 };
+            let len = rest[name_start..]
+                .find(|c: char| c != '_' && !c.is_ascii_alphanumeric())
+                .unwrap_or(rest.len() - name_start);
+            let name = rest[name_start..name_start + len].to_string();
+            tokens.push(match c {
+                '$' => Token::Variable(name),
+                _ => Token::Name(name),
+            });
+            rest = &rest[name_start + len..];
+        } else {
+            if !c.is_whitespace() && c != ',' {
+                tokens.push(Token::Punct(c));
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::{VariableDefinition, operation_variables, variable_warnings};
+
+    fn var(name: &str, required: bool) -> VariableDefinition {
+        VariableDefinition {
+            name: name.to_string(),
+            required,
+        }
+    }
+
+    #[test]
+    fn declared_variables() {
+        assert_eq!(
+            operation_variables(
+                "query Get($id: ID!, $first: Int = 10, $tags: [String!]!, $q: String) { a }"
+            ),
+            Some(vec![
+                var("id", true),
+                var("first", false),
+                var("tags", true),
+                var("q", false)
+            ])
+        );
+        assert_eq!(
+            operation_variables("mutation ($in: [Int!], $ok: Boolean! = true) { a }"),
+            Some(vec![var("in", false), var("ok", false)])
+        );
+        assert_eq!(operation_variables("{ user(id: 1) { name } }"), Some(vec![]));
+    }
+
+    #[test]
+    fn ignores_strings_comments_and_fragments() {
+        let query = r#"
+            # query Fake($x: Int!) { a }
+            query Real($id: ID! @deprecated(reason: "not (really) $used")) {
+                user(id: $id, note: """ { query Block($y: Int!) } """) { ...F }
+            }
+            fragment F on User { name(arg: "{") }
+        "#;
+        assert_eq!(operation_variables(query), Some(vec![var("id", true)]));
+    }
+
+    #[test]
+    fn multiple_operations_are_skipped() {
+        assert_eq!(operation_variables("query A { a } query B($id: ID!) { b }"), None);
+        assert_eq!(operation_variables(""), None);
+        assert_eq!(
+            variable_warnings("query A { a } query B($id: ID!) { b }", ""),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn warns_on_mismatch() {
+        let query = "query ($id: ID!, $limit: Int) { a }";
+        assert_eq!(variable_warnings(query, r#"{"id": 1, "limit": 2}"#), Vec::<String>::new());
+        assert_eq!(
+            variable_warnings(query, r#"{"limit": 2, "extra": true}"#),
+            vec![
+                "Variable $id is required but was not provided".to_string(),
+                "Variable $extra is not declared by the operation".to_string(),
+            ]
+        );
+        assert_eq!(
+            variable_warnings(query, r#"{"id": null}"#),
+            vec!["Variable $id is required but was not provided".to_string()]
+        );
+        assert_eq!(
+            variable_warnings(query, "  "),
+            vec!["Variable $id is required but was not provided".to_string()]
+        );
+    }
+
+    #[test]
+    fn warns_on_invalid_variables() {
+        assert_eq!(
+            variable_warnings("query ($id: ID) { a }", "[1]"),
+            vec!["GraphQL variables should be a JSON object".to_string()]
+        );
+        assert_eq!(variable_warnings("query ($id: ID) { a }", "{").len(), 1);
+    }
+}
@@ -0,0 +1,91 @@
+use http::HeaderMap;
+
+/// Error the HTTP client gives for an HTTP/1.1 response whose headers are over its own limits,
+/// which are about 400 KiB and 100 headers
+const HTTP1_TOO_LARGE: &str = "message head is too large";
+
+/// Total size of a response's headers, counting the bytes of each name and value
+pub fn headers_size(headers: &HeaderMap) -> usize {
+    headers.iter().map(|(name, value)| name.as_str().len() + value.len()).sum()
+}
+
+/// Why a response's headers are over the workspace's limits, if they are. A limit of 0 isn't
+/// enforced.
+pub fn header_limit_error(
+    headers: &HeaderMap,
+    max_size: usize,
+    max_count: usize,
+) -> Option<String> {
+    let count = headers.len();
+    if max_count > 0 && count > max_count {
+        return Some(format!(
+            "Response has {count} headers, which is over the limit of {max_count}"
+        ));
+    }
+
+    let size = headers_size(headers);
+    if max_size > 0 && size > max_size {
+        return Some(format!(
+            "Response headers are {size} bytes, which is over the limit of {max_size} bytes"
+        ));
+    }
+
+    None
+}
+
+/// Explain a request error caused by response headers that were over the HTTP client's own
+/// limits, since the client only says the message head is too large. Other errors are returned
+/// as-is.
+pub fn with_header_limit_hint(error: String) -> String {
+    match error.contains(HTTP1_TOO_LARGE) {
+        true => format!(
+            "{error}. The response headers are over the HTTP/1.1 limit of about 400 KiB or 100 \
+             headers, which can't be raised"
+        ),
+        false => error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::header_limits::{header_limit_error, headers_size, with_header_limit_hint};
+    use http::{HeaderMap, HeaderValue};
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn measures_names_and_values() {
+        let h = headers(&[("set-cookie", "a=1"), ("set-cookie", "b=2"), ("etag", "x")]);
+        assert_eq!(headers_size(&h), 10 + 3 + 10 + 3 + 4 + 1);
+    }
+
+    #[test]
+    fn enforces_limits() {
+        let h = headers(&[("set-cookie", "a=1"), ("set-cookie", "b=2")]);
+        assert_eq!(header_limit_error(&h, 0, 0), None);
+        assert_eq!(header_limit_error(&h, 26, 2), None);
+        assert_eq!(
+            header_limit_error(&h, 0, 1),
+            Some("Response has 2 headers, which is over the limit of 1".to_string())
+        );
+        assert_eq!(
+            header_limit_error(&h, 25, 0),
+            Some("Response headers are 26 bytes, which is over the limit of 25 bytes".to_string())
+        );
+    }
+
+    #[test]
+    fn explains_client_limits() {
+        let e = "error sending request: message head is too large".to_string();
+        assert!(with_header_limit_hint(e).ends_with("which can't be raised"));
+
+        let e = "error sending request: connection refused".to_string();
+        assert_eq!(with_header_limit_hint(e.clone()), e);
+    }
+}
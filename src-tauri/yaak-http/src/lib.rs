@@ -1,5 +1,32 @@
 use yaak_models::models::HttpUrlParameter;
 
+pub mod auth_challenge;
+pub mod cache;
+pub mod chain;
+pub mod charset;
+pub mod data_file;
+pub mod decode;
+pub mod error;
+pub mod graphql;
+pub mod header_limits;
+pub mod multipart;
+pub mod ndjson;
+pub mod oauth2;
+pub mod openapi;
+pub mod query;
+pub mod redirect;
+pub mod replay;
+pub mod retry;
+pub mod secrets;
+pub mod status;
+pub mod timeout;
+pub mod tls;
+pub mod trace;
+pub mod trailers;
+pub mod tunnel;
+pub mod unresolved;
+pub mod url;
+
 pub fn apply_path_placeholders(
     url: &str,
     parameters: Vec<HttpUrlParameter>,
@@ -60,6 +87,7 @@ mod placeholder_tests {
             value: "xxx".into(),
             enabled: true,
             id: None,
+            notation: None,
         };
         assert_eq!(
             replace_path_placeholder(&p, "https://example.com/:foo/bar"),
@@ -74,6 +102,7 @@ mod placeholder_tests {
             value: "xxx".into(),
             enabled: true,
             id: None,
+            notation: None,
         };
         assert_eq!(
             replace_path_placeholder(&p, "https://example.com/:foo"),
@@ -88,6 +117,7 @@ mod placeholder_tests {
             value: "xxx".into(),
             enabled: true,
             id: None,
+            notation: None,
         };
         assert_eq!(
             replace_path_placeholder(&p, "https://example.com/:foo?:foo"),
@@ -102,6 +132,7 @@ mod placeholder_tests {
             name: "".to_string(),
             value: "".to_string(),
             id: None,
+            notation: None,
         };
         assert_eq!(
             replace_path_placeholder(&p, "https://example.com/:missing"),
@@ -116,6 +147,7 @@ mod placeholder_tests {
             name: ":foo".to_string(),
             value: "xxx".to_string(),
             id: None,
+            notation: None,
         };
         assert_eq!(
             replace_path_placeholder(&p, "https://example.com/:foo"),
@@ -130,6 +162,7 @@ mod placeholder_tests {
             value: "xxx".into(),
             enabled: true,
             id: None,
+            notation: None,
         };
         assert_eq!(
             replace_path_placeholder(&p, "https://example.com/:foooo"),
@@ -144,6 +177,7 @@ mod placeholder_tests {
             value: "Hello World".into(),
             enabled: true,
             id: None,
+            notation: None,
         };
         assert_eq!(
             replace_path_placeholder(&p, "https://example.com/:foo"),
@@ -161,12 +195,14 @@ mod placeholder_tests {
                     value: "bbb".to_string(),
                     enabled: true,
                     id: None,
+                    notation: None,
                 },
                 HttpUrlParameter {
                     name: ":a".to_string(),
                     value: "aaa".to_string(),
                     enabled: true,
                     id: None,
+                    notation: None,
                 },
             ],
             ..Default::default()
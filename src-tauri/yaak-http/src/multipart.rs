@@ -0,0 +1,163 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Content type to send for a multipart part. An explicit type always wins, including on text
+/// parts, like `application/json` for a part holding JSON. Otherwise, files get one guessed from
+/// their extension and text parts get none, which means `text/plain`.
+pub fn part_content_type(content_type: &str, file_path: &str) -> Option<String> {
+    let content_type = content_type.trim();
+    if !content_type.is_empty() {
+        return Some(content_type.to_string());
+    }
+
+    match file_path.is_empty() {
+        true => None,
+        false => Some(
+            mime_guess::from_path(file_path)
+                .first_raw()
+                .unwrap_or("application/octet-stream")
+                .to_string(),
+        ),
+    }
+}
+
+/// Files to send for a multipart entry that points at a directory, sorted by name. Only files
+/// directly inside it are included, skipping hidden ones and any that don't match the filter.
+pub async fn directory_files(dir: &Path, filter: &str) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') || !entry.file_type().await?.is_file() {
+            continue;
+        }
+        if matches_filter(filter, &name) {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Field name for a file sent from a directory, which is its name without the extension
+pub fn field_name(path: &Path) -> String {
+    path.file_stem().unwrap_or_default().to_string_lossy().to_string()
+}
+
+/// Whether a file name matches a filter of comma-separated patterns, like `*.png, *.jpg`, where
+/// `*` matches anything and `?` matches one character. An empty filter matches every file.
+pub fn matches_filter(filter: &str, file_name: &str) -> bool {
+    let mut patterns = filter.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()).peekable();
+    if patterns.peek().is_none() {
+        return true;
+    }
+
+    let name: Vec<char> = file_name.to_lowercase().chars().collect();
+    patterns.any(|p| wildcard_match(&p.to_lowercase().chars().collect::<Vec<_>>(), &name))
+}
+
+fn wildcard_match(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|i| wildcard_match(rest, &name[i..])),
+        Some((p, rest)) => match name.split_first() {
+            Some((n, name_rest)) => (*p == '?' || p == n) && wildcard_match(rest, name_rest),
+            None => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::multipart::{directory_files, field_name, matches_filter, part_content_type};
+    use reqwest::multipart::{Form, Part};
+    use std::path::Path;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn explicit_content_type() {
+        assert_eq!(part_content_type("application/json", ""), Some("application/json".into()));
+        assert_eq!(part_content_type(" text/csv ", "a.json"), Some("text/csv".into()));
+    }
+
+    #[test]
+    fn guessed_content_type() {
+        assert_eq!(part_content_type("", ""), None);
+        assert_eq!(part_content_type("", "/tmp/photo.png"), Some("image/png".into()));
+        assert_eq!(part_content_type("", "/tmp/blob"), Some("application/octet-stream".into()));
+    }
+
+    #[test]
+    fn filters_file_names() {
+        assert!(matches_filter("", "cat.png"));
+        assert!(matches_filter(" , ", "cat.png"));
+        assert!(matches_filter("*.png, *.jpg", "cat.JPG"));
+        assert!(matches_filter("img_??.*", "img_01.gif"));
+        assert!(!matches_filter("img_??.*", "img_1.gif"));
+        assert!(!matches_filter("*.png", "cat.png.txt"));
+        assert!(matches_filter("cat.png", "cat.png"));
+    }
+
+    #[test]
+    fn field_names_from_files() {
+        assert_eq!(field_name(Path::new("/tmp/images/cat.png")), "cat");
+        assert_eq!(field_name(Path::new("/tmp/images/archive.tar.gz")), "archive.tar");
+        assert_eq!(field_name(Path::new("README")), "README");
+    }
+
+    #[tokio::test]
+    async fn lists_directory_files() {
+        let dir = std::env::temp_dir().join(format!("yaak-multipart-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        for name in ["b.png", "a.png", "notes.txt", ".hidden.png", "nested/c.png"] {
+            std::fs::write(dir.join(name), name).unwrap();
+        }
+
+        let names = |files: Vec<std::path::PathBuf>| {
+            files
+                .iter()
+                .map(|f| f.file_name().unwrap().to_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(directory_files(&dir, "").await.unwrap()),
+            ["a.png", "b.png", "notes.txt"]
+        );
+        assert_eq!(names(directory_files(&dir, "*.png").await.unwrap()), ["a.png", "b.png"]);
+        assert!(directory_files(&dir.join("missing"), "").await.is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn json_text_part_keeps_content_type() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/upload", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut received = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !String::from_utf8_lossy(&received).trim_end().ends_with("--") {
+                let n = stream.read(&mut buf).await.unwrap();
+                received.extend_from_slice(&buf[..n]);
+            }
+            stream.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n").await.unwrap();
+            String::from_utf8(received).unwrap()
+        });
+
+        let mime = part_content_type("application/json", "").unwrap();
+        let part = Part::text(r#"{"a":1}"#).mime_str(&mime).unwrap();
+        let form = Form::new().part("data", part);
+        reqwest::Client::new().post(url).multipart(form).send().await.unwrap();
+
+        let received = server.await.unwrap();
+        assert!(received.contains(concat!(
+            "Content-Disposition: form-data; name=\"data\"\r\n",
+            "Content-Type: application/json\r\n",
+            "\r\n",
+            "{\"a\":1}\r\n",
+        )));
+    }
+}
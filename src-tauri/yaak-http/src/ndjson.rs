@@ -0,0 +1,112 @@
+/// Whether a content type is newline-delimited JSON, like `application/x-ndjson` or
+/// `application/jsonl`
+pub fn is_ndjson(content_type: &str) -> bool {
+    let essence = content_type.split(';').next().unwrap_or_default().trim().to_lowercase();
+    matches!(
+        essence.as_str(),
+        "application/x-ndjson"
+            | "application/ndjson"
+            | "application/jsonl"
+            | "application/x-jsonl"
+            | "application/jsonlines"
+            | "application/x-jsonlines"
+    )
+}
+
+/// One line of a newline-delimited JSON body
+#[derive(Debug, Clone, PartialEq)]
+pub struct NdjsonLine {
+    /// 1-based line number within the body
+    pub line: usize,
+    pub data: String,
+    /// Why the line isn't valid JSON, if it isn't
+    pub error: Option<String>,
+}
+
+/// Streaming parser for a newline-delimited JSON body. Chunks can split lines anywhere, so
+/// anything after the last newline is held back until the rest of the line arrives.
+#[derive(Default)]
+pub struct NdjsonParser {
+    buf: Vec<u8>,
+    line: usize,
+}
+
+impl NdjsonParser {
+    /// Parse the next chunk of the body, returning the lines it completed. Blank lines are
+    /// skipped.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<NdjsonLine> {
+        self.buf.extend_from_slice(chunk);
+        let end = match self.buf.iter().rposition(|b| *b == b'\n') {
+            Some(i) => i + 1,
+            None => return Vec::new(),
+        };
+        let complete = self.buf.drain(..end).collect::<Vec<_>>();
+        let complete = &complete[..complete.len() - 1];
+        complete.split(|b| *b == b'\n').filter_map(|l| self.parse_line(l)).collect()
+    }
+
+    /// Finish once the whole body was read, returning the last line if it didn't end in a
+    /// newline
+    pub fn finish(mut self) -> Option<NdjsonLine> {
+        let rest = std::mem::take(&mut self.buf);
+        match rest.is_empty() {
+            true => None,
+            false => self.parse_line(&rest),
+        }
+    }
+
+    fn parse_line(&mut self, bytes: &[u8]) -> Option<NdjsonLine> {
+        self.line += 1;
+        let data = String::from_utf8_lossy(bytes).trim_end_matches('\r').to_string();
+        if data.trim().is_empty() {
+            return None;
+        }
+        let error = serde_json::from_str::<serde_json::Value>(&data).err().map(|e| e.to_string());
+        Some(NdjsonLine {
+            line: self.line,
+            data,
+            error,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ndjson::{NdjsonParser, is_ndjson};
+
+    #[test]
+    fn detects_content_types() {
+        assert!(is_ndjson("application/x-ndjson"));
+        assert!(is_ndjson("Application/JSONL; charset=utf-8"));
+        assert!(!is_ndjson("application/json"));
+        assert!(!is_ndjson("text/event-stream"));
+    }
+
+    #[test]
+    fn parses_lines_split_across_chunks() {
+        let mut parser = NdjsonParser::default();
+        assert_eq!(parser.push(b"{\"a\":").len(), 0);
+
+        let lines = parser.push(b"1}\r\n\n{\"b\"");
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].line, 1);
+        assert_eq!(lines[0].data, "{\"a\":1}");
+        assert_eq!(lines[0].error, None);
+
+        let lines = parser.push(b":2}\nnope\n[3]");
+        assert_eq!(lines.iter().map(|l| l.line).collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(lines[0].error, None);
+        assert!(lines[1].error.is_some());
+
+        let last = parser.finish().unwrap();
+        assert_eq!(last.line, 5);
+        assert_eq!(last.data, "[3]");
+    }
+
+    #[test]
+    fn finishes_without_trailing_line() {
+        let mut parser = NdjsonParser::default();
+        assert_eq!(parser.push(b"{}\n").len(), 1);
+        assert_eq!(parser.finish(), None);
+    }
+}
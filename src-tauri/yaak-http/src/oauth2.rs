@@ -0,0 +1,188 @@
+use base64::Engine;
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use sha2::{Digest, Sha256};
+
+pub const PKCE_S256: &str = "S256";
+pub const PKCE_PLAIN: &str = "plain";
+
+/// Random PKCE code verifier. 32 bytes encodes to 43 characters, the minimum allowed by RFC 7636.
+pub fn generate_code_verifier() -> String {
+    BASE64_URL_SAFE_NO_PAD.encode(rand::random::<[u8; 32]>())
+}
+
+/// Code challenge for a verifier. Anything other than "plain" is treated as S256.
+pub fn code_challenge(verifier: &str, method: &str) -> String {
+    match method {
+        PKCE_PLAIN => verifier.to_string(),
+        _ => BASE64_URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes())),
+    }
+}
+
+/// Append query parameters to a URL, which may already have some
+pub fn append_query(url: &str, params: &[(&str, &str)]) -> String {
+    let (url, fragment) = match url.split_once('#') {
+        Some((u, f)) => (u, Some(f)),
+        None => (url, None),
+    };
+
+    let mut url = url.to_string();
+    for (name, value) in params {
+        url.push(if url.contains('?') { '&' } else { '?' });
+        url.push_str(&urlencoding::encode(name));
+        url.push('=');
+        url.push_str(&urlencoding::encode(value));
+    }
+
+    if let Some(f) = fragment {
+        url.push('#');
+        url.push_str(f);
+    }
+    url
+}
+
+/// Check whether a URL the authorization window navigated to is the end of the flow. Returns
+/// `None` to keep waiting, or the authorization code (or error) once the provider redirects back.
+pub fn authorization_code_from_redirect(
+    url: &str,
+    redirect_uri: Option<&str>,
+    state: Option<&str>,
+) -> Option<Result<String, String>> {
+    if let Some(redirect_uri) = redirect_uri {
+        let base = redirect_uri.split(['?', '#']).next().unwrap_or_default();
+        if !url.starts_with(base) {
+            return None;
+        }
+    }
+
+    if let Some(error) = query_param(url, "error") {
+        return Some(Err(match query_param(url, "error_description") {
+            Some(description) => format!("Failed to authorize: {error} -> {description}"),
+            None => format!("Failed to authorize: {error}"),
+        }));
+    }
+
+    let code = query_param(url, "code")?;
+    if let Some(state) = state {
+        if query_param(url, "state").as_deref() != Some(state) {
+            return Some(Err("Authorization state did not match".to_string()));
+        }
+    }
+
+    Some(Ok(code))
+}
+
+/// Decode an `application/x-www-form-urlencoded` string, like a query or some token responses
+pub fn parse_form_urlencoded(form: &str) -> Vec<(String, String)> {
+    form.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (n, v) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(n), decode(v))
+        })
+        .collect()
+}
+
+fn query_param(url: &str, name: &str) -> Option<String> {
+    let query = url.split('#').next()?.split_once('?')?.1;
+    parse_form_urlencoded(query).into_iter().find(|(n, _)| n == name).map(|(_, v)| v)
+}
+
+fn decode(v: &str) -> String {
+    let v = v.replace('+', " ");
+    urlencoding::decode(&v).map(|v| v.into_owned()).unwrap_or(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::oauth2::{
+        PKCE_PLAIN, PKCE_S256, append_query, authorization_code_from_redirect, code_challenge,
+        generate_code_verifier, parse_form_urlencoded,
+    };
+
+    #[test]
+    fn rfc_7636_challenge() {
+        assert_eq!(
+            code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk", PKCE_S256),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+        assert_eq!(code_challenge("abc", PKCE_PLAIN), "abc");
+    }
+
+    #[test]
+    fn verifier_is_url_safe() {
+        let verifier = generate_code_verifier();
+        assert_eq!(verifier.len(), 43);
+        assert!(verifier.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_ne!(verifier, generate_code_verifier());
+    }
+
+    #[test]
+    fn appends_query() {
+        assert_eq!(
+            append_query("https://auth.example.com/authorize", &[("a", "1"), ("scope", "x y")]),
+            "https://auth.example.com/authorize?a=1&scope=x%20y"
+        );
+        assert_eq!(
+            append_query("https://auth.example.com/authorize?tenant=t#frag", &[("a", "1")]),
+            "https://auth.example.com/authorize?tenant=t&a=1#frag"
+        );
+    }
+
+    #[test]
+    fn waits_for_redirect() {
+        let redirect_uri = Some("https://app.example.com/callback");
+        let login_url = "https://auth.example.com/login?code=1";
+        assert_eq!(authorization_code_from_redirect(login_url, redirect_uri, None), None);
+        let callback_url = "https://app.example.com/callback";
+        assert_eq!(authorization_code_from_redirect(callback_url, redirect_uri, None), None);
+    }
+
+    #[test]
+    fn extracts_code() {
+        assert_eq!(
+            authorization_code_from_redirect(
+                "https://app.example.com/callback?code=a%2Fb&state=s1",
+                Some("https://app.example.com/callback"),
+                Some("s1"),
+            ),
+            Some(Ok("a/b".to_string()))
+        );
+        assert_eq!(
+            authorization_code_from_redirect("http://localhost/?code=xyz", None, None),
+            Some(Ok("xyz".to_string()))
+        );
+    }
+
+    #[test]
+    fn redirect_errors() {
+        assert_eq!(
+            authorization_code_from_redirect(
+                "https://app.example.com/callback?error=access_denied&error_description=User+said+no",
+                None,
+                None,
+            ),
+            Some(Err("Failed to authorize: access_denied -> User said no".to_string()))
+        );
+        assert_eq!(
+            authorization_code_from_redirect(
+                "https://app.example.com/callback?code=xyz&state=other",
+                None,
+                Some("s1"),
+            ),
+            Some(Err("Authorization state did not match".to_string()))
+        );
+    }
+
+    #[test]
+    fn form_urlencoded() {
+        assert_eq!(
+            parse_form_urlencoded("access_token=a%2Bb&scope=repo+user&empty="),
+            vec![
+                ("access_token".to_string(), "a+b".to_string()),
+                ("scope".to_string(), "repo user".to_string()),
+                ("empty".to_string(), "".to_string()),
+            ]
+        );
+        assert_eq!(parse_form_urlencoded(""), vec![]);
+    }
+}
@@ -0,0 +1,285 @@
+use serde_json::{Map, Value, json};
+use yaak_models::bodies::{MultipartBody, TextBody};
+use yaak_models::models::HttpRequest;
+
+/// Headers that OpenAPI describes elsewhere, so they can't be header parameters
+const RESERVED_HEADERS: [&str; 3] = ["accept", "authorization", "content-type"];
+
+/// OpenAPI path item with a single operation for a request, like
+/// `{"/users/{id}": {"get": {...}}}`, for pasting into a larger spec. Path placeholders (`:id`),
+/// URL parameters and headers become parameters, and the body's schema is guessed from its
+/// contents. Values aren't included, since they may be secrets.
+pub fn openapi_operation(request: &HttpRequest) -> Value {
+    let path = operation_path(&request.url);
+
+    let mut parameters = Vec::new();
+    for name in path.split('/').filter_map(|s| s.strip_prefix('{')?.strip_suffix('}')) {
+        parameters.push(parameter(name, "path", true));
+    }
+    let mut query_names = Vec::new();
+    let url_query = request.url.split_once('?').map(|(_, q)| q).unwrap_or_default();
+    for name in url_query.split('&').filter_map(|p| p.split('=').next()) {
+        query_names.push(name.to_string());
+    }
+    for p in request.url_parameters.iter().filter(|p| p.enabled && !p.name.starts_with(':')) {
+        query_names.push(p.name.clone());
+    }
+    for name in query_names {
+        if !name.is_empty() && !parameters.iter().any(|p| p["in"] == "query" && p["name"] == name) {
+            parameters.push(parameter(&name, "query", false));
+        }
+    }
+    for h in request.headers.iter().filter(|h| h.enabled && !h.name.is_empty()) {
+        if !RESERVED_HEADERS.contains(&h.name.to_lowercase().as_str()) {
+            parameters.push(parameter(&h.name, "header", false));
+        }
+    }
+
+    let mut operation = Map::new();
+    if !request.name.is_empty() {
+        operation.insert("summary".into(), request.name.clone().into());
+    }
+    if !request.description.is_empty() {
+        operation.insert("description".into(), request.description.clone().into());
+    }
+    if !parameters.is_empty() {
+        operation.insert("parameters".into(), parameters.into());
+    }
+    if let Some((content_type, schema)) = request_body(request) {
+        operation.insert(
+            "requestBody".into(),
+            json!({ "content": { content_type: { "schema": schema } } }),
+        );
+    }
+    operation.insert("responses".into(), json!({ "default": { "description": "Response" } }));
+
+    json!({ path: { request.method.to_lowercase(): operation } })
+}
+
+/// Path of a URL, without the host or query, and with `:name` placeholders written as `{name}`
+fn operation_path(url: &str) -> String {
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    let rest = url.split_once("://").map(|(_, r)| r).unwrap_or(url);
+    let path = match rest.starts_with('/') {
+        true => rest,
+        false => rest.find('/').map(|i| &rest[i..]).unwrap_or_default(),
+    };
+
+    let segments = path
+        .split('/')
+        .skip(1)
+        .map(|s| match s.strip_prefix(':') {
+            Some(name) if !name.is_empty() => format!("{{{name}}}"),
+            _ => s.to_string(),
+        })
+        .collect::<Vec<_>>();
+    format!("/{}", segments.join("/"))
+}
+
+fn parameter(name: &str, location: &str, required: bool) -> Value {
+    json!({ "name": name, "in": location, "required": required, "schema": { "type": "string" } })
+}
+
+/// Content type and schema of a request's body, if it has one
+fn request_body(request: &HttpRequest) -> Option<(String, Value)> {
+    let body_type = request.body_type.as_deref()?;
+    let header = request
+        .headers
+        .iter()
+        .find(|h| h.enabled && h.name.eq_ignore_ascii_case("content-type"))
+        .map(|h| h.value.clone());
+    let text = TextBody::try_from(&request.body).map(|b| b.text).unwrap_or_default();
+
+    let (content_type, schema) = match body_type {
+        "graphql" => (
+            "application/json".to_string(),
+            json!({
+                "type": "object",
+                "properties": { "query": { "type": "string" }, "variables": { "type": "object" } }
+            }),
+        ),
+        "application/json" => {
+            let schema = match serde_json::from_str::<Value>(&text) {
+                Ok(v) => json_schema(&v),
+                // Templates can make a body that's only JSON once rendered
+                Err(_) => json!({}),
+            };
+            (header.unwrap_or_else(|| body_type.to_string()), schema)
+        }
+        "application/x-www-form-urlencoded" | "multipart/form-data" => {
+            let mut properties = Map::new();
+            // URL-encoded forms have the same fields, just without files
+            let form = MultipartBody::try_from(&request.body).map(|b| b.form).unwrap_or_default();
+            for p in form.into_iter().filter(|p| p.enabled && !p.name.is_empty()) {
+                let schema = match p.file.is_empty() {
+                    false => json!({ "type": "string", "format": "binary" }),
+                    true => json!({ "type": "string" }),
+                };
+                properties.insert(p.name, schema);
+            }
+            (body_type.to_string(), json!({ "type": "object", "properties": properties }))
+        }
+        "binary" => (
+            header.unwrap_or_else(|| "application/octet-stream".to_string()),
+            json!({ "type": "string", "format": "binary" }),
+        ),
+        _ if request.body.contains_key("text") => {
+            (header.unwrap_or_else(|| "text/plain".to_string()), json!({ "type": "string" }))
+        }
+        _ => return None,
+    };
+
+    // Parameters like `; charset=utf-8` aren't part of a media type key
+    let content_type = content_type.split(';').next().unwrap_or_default().trim().to_string();
+    Some((content_type, schema))
+}
+
+/// Schema that a JSON value matches, with arrays described by their first item
+fn json_schema(value: &Value) -> Value {
+    match value {
+        Value::Null => json!({ "nullable": true }),
+        Value::Bool(_) => json!({ "type": "boolean" }),
+        Value::Number(n) if n.is_f64() => json!({ "type": "number" }),
+        Value::Number(_) => json!({ "type": "integer" }),
+        Value::String(_) => json!({ "type": "string" }),
+        Value::Array(a) => json!({
+            "type": "array",
+            "items": a.first().map(json_schema).unwrap_or_else(|| json!({})),
+        }),
+        Value::Object(o) => {
+            let properties =
+                o.iter().map(|(k, v)| (k.clone(), json_schema(v))).collect::<Map<_, _>>();
+            json!({ "type": "object", "properties": properties })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::openapi::{openapi_operation, operation_path};
+    use serde_json::{Value, json};
+    use std::collections::BTreeMap;
+    use yaak_models::models::{HttpRequest, HttpRequestHeader, HttpUrlParameter};
+
+    #[test]
+    fn paths() {
+        assert_eq!(operation_path("https://example.com/users/:id?x=1"), "/users/{id}");
+        assert_eq!(operation_path("${[ host ]}/users"), "/users");
+        assert_eq!(operation_path("/users/"), "/users/");
+        assert_eq!(operation_path("https://example.com"), "/");
+    }
+
+    #[test]
+    fn operation_for_json_request() {
+        let request = HttpRequest {
+            name: "Update user".into(),
+            method: "PUT".into(),
+            url: "https://example.com/users/:id?verbose=true".into(),
+            url_parameters: vec![
+                HttpUrlParameter {
+                    enabled: true,
+                    name: ":id".into(),
+                    value: "1".into(),
+                    ..Default::default()
+                },
+                HttpUrlParameter {
+                    enabled: true,
+                    name: "fields".into(),
+                    value: "name".into(),
+                    ..Default::default()
+                },
+                HttpUrlParameter {
+                    enabled: false,
+                    name: "debug".into(),
+                    value: "1".into(),
+                    ..Default::default()
+                },
+            ],
+            headers: vec![
+                HttpRequestHeader {
+                    enabled: true,
+                    name: "Content-Type".into(),
+                    value: "application/json; charset=utf-8".into(),
+                    ..Default::default()
+                },
+                HttpRequestHeader {
+                    enabled: true,
+                    name: "X-Api-Key".into(),
+                    value: "secret".into(),
+                    ..Default::default()
+                },
+            ],
+            body_type: Some("application/json".into()),
+            body: BTreeMap::from([(
+                "text".into(),
+                Value::from(r#"{"name":"Ada","age":36,"tags":["a"],"score":1.5}"#),
+            )]),
+            ..Default::default()
+        };
+
+        let string = json!({ "type": "string" });
+        assert_eq!(
+            openapi_operation(&request),
+            json!({
+                "/users/{id}": {
+                    "put": {
+                        "summary": "Update user",
+                        "parameters": [
+                            { "name": "id", "in": "path", "required": true, "schema": string },
+                            { "name": "verbose", "in": "query", "required": false, "schema": string },
+                            { "name": "fields", "in": "query", "required": false, "schema": string },
+                            { "name": "X-Api-Key", "in": "header", "required": false, "schema": string },
+                        ],
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": {
+                                        "type": "object",
+                                        "properties": {
+                                            "name": string,
+                                            "age": { "type": "integer" },
+                                            "tags": { "type": "array", "items": string },
+                                            "score": { "type": "number" },
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                        "responses": { "default": { "description": "Response" } },
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn operation_for_form_request() {
+        let request = HttpRequest {
+            method: "POST".into(),
+            url: "/upload".into(),
+            body_type: Some("multipart/form-data".into()),
+            body: BTreeMap::from([(
+                "form".into(),
+                json!([
+                    { "enabled": true, "name": "title", "value": "Hi" },
+                    { "enabled": true, "name": "file", "file": "/tmp/a.png" },
+                    { "enabled": false, "name": "skipped", "value": "" },
+                ]),
+            )]),
+            ..Default::default()
+        };
+
+        let operation = &openapi_operation(&request)["/upload"]["post"];
+        assert_eq!(
+            operation["requestBody"]["content"]["multipart/form-data"]["schema"],
+            json!({
+                "type": "object",
+                "properties": {
+                    "title": { "type": "string" },
+                    "file": { "type": "string", "format": "binary" },
+                }
+            })
+        );
+        assert!(operation.get("parameters").is_none());
+    }
+}
@@ -0,0 +1,61 @@
+use yaak_models::models::UrlParameterNotation;
+
+/// Name a URL parameter is sent with, after adding the brackets of its notation, like `items[]`
+/// or `filter[name]`. Fails for names that can't be written in the notation.
+pub fn query_param_name(
+    name: &str,
+    notation: Option<UrlParameterNotation>,
+) -> Result<String, String> {
+    let notation = match notation {
+        Some(n) => n,
+        None => return Ok(name.to_string()),
+    };
+    if name.contains(['[', ']']) {
+        return Err(format!(
+            "Parameter {name:?} already has brackets, so it can't also use bracket notation"
+        ));
+    }
+
+    match notation {
+        UrlParameterNotation::Array => Ok(format!("{name}[]")),
+        UrlParameterNotation::Object => {
+            let mut parts = name.split('.');
+            let base = parts.next().unwrap_or_default();
+            let keys = parts.collect::<Vec<_>>();
+            if base.is_empty() || keys.is_empty() || keys.iter().any(|k| k.is_empty()) {
+                return Err(format!(
+                    "Parameter {name:?} uses object notation, so it needs keys separated by \
+                     dots, like filter.name"
+                ));
+            }
+            Ok(keys.iter().fold(base.to_string(), |n, k| format!("{n}[{k}]")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::query::query_param_name;
+    use yaak_models::models::UrlParameterNotation::{Array, Object};
+
+    #[test]
+    fn plain_names_are_unchanged() {
+        assert_eq!(query_param_name("a.b[c]", None).unwrap(), "a.b[c]");
+    }
+
+    #[test]
+    fn array_notation() {
+        assert_eq!(query_param_name("items", Some(Array)).unwrap(), "items[]");
+        assert!(query_param_name("items[]", Some(Array)).is_err());
+    }
+
+    #[test]
+    fn object_notation() {
+        assert_eq!(query_param_name("filter.name", Some(Object)).unwrap(), "filter[name]");
+        assert_eq!(query_param_name("a.b.c", Some(Object)).unwrap(), "a[b][c]");
+        assert!(query_param_name("filter", Some(Object)).is_err());
+        assert!(query_param_name("filter.", Some(Object)).is_err());
+        assert!(query_param_name(".name", Some(Object)).is_err());
+        assert!(query_param_name("filter[name]", Some(Object)).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+/// Maximum number of redirects to follow for a single send
+pub const MAX_REDIRECTS: usize = 10;
+
+pub fn is_redirect_status(status: u16) -> bool {
+    matches!(status, 301 | 302 | 303 | 307 | 308)
+}
+
+/// Method to use when following a redirect. 307 and 308 always keep the method (and body). On 301
+/// and 302, browsers change POST to GET, but `preserve_method` keeps it for APIs that misuse 302.
+/// A 303 always means GET, other than for HEAD.
+pub fn redirect_method(status: u16, method: &str, preserve_method: bool) -> String {
+    let method = method.to_uppercase();
+    match status {
+        301 | 302 if !preserve_method && method == "POST" => "GET".to_string(),
+        303 if method != "HEAD" => "GET".to_string(),
+        _ => method,
+    }
+}
+
+/// Whether a redirect goes from HTTPS to plain HTTP, which would send everything after it
+/// (cookies, the body on 307/308) unencrypted
+pub fn is_downgrade(from_scheme: &str, to_scheme: &str) -> bool {
+    from_scheme.eq_ignore_ascii_case("https") && to_scheme.eq_ignore_ascii_case("http")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::redirect::{is_downgrade, is_redirect_status, redirect_method};
+
+    #[test]
+    fn redirect_statuses() {
+        for status in [301, 302, 303, 307, 308] {
+            assert!(is_redirect_status(status));
+        }
+        assert!(!is_redirect_status(200));
+        assert!(!is_redirect_status(304));
+    }
+
+    #[test]
+    fn browser_behavior() {
+        assert_eq!(redirect_method(301, "POST", false), "GET");
+        assert_eq!(redirect_method(302, "post", false), "GET");
+        assert_eq!(redirect_method(302, "PUT", false), "PUT");
+        assert_eq!(redirect_method(307, "POST", false), "POST");
+        assert_eq!(redirect_method(308, "PATCH", false), "PATCH");
+    }
+
+    #[test]
+    fn preserve_method() {
+        assert_eq!(redirect_method(301, "POST", true), "POST");
+        assert_eq!(redirect_method(302, "POST", true), "POST");
+        assert_eq!(redirect_method(307, "DELETE", true), "DELETE");
+    }
+
+    #[test]
+    fn see_other() {
+        assert_eq!(redirect_method(303, "POST", true), "GET");
+        assert_eq!(redirect_method(303, "PUT", false), "GET");
+        assert_eq!(redirect_method(303, "HEAD", false), "HEAD");
+    }
+
+    #[test]
+    fn downgrades() {
+        assert!(is_downgrade("https", "http"));
+        assert!(is_downgrade("HTTPS", "http"));
+        assert!(!is_downgrade("http", "https"));
+        assert!(!is_downgrade("https", "https"));
+        assert!(!is_downgrade("http", "http"));
+    }
+}
@@ -0,0 +1,92 @@
+use yaak_models::models::{HttpRequest, HttpRequestHeader, HttpResponse};
+
+/// Headers that are worked out again for the body being sent, so stored values could be wrong
+const RECOMPUTED_HEADERS: [&str; 2] = ["content-length", "host"];
+
+/// Request that sends again what produced a response, however the request was edited since. The
+/// URL and headers are the ones that were sent, with templates rendered and auth and cookies
+/// applied, so authentication is turned off. Responses don't store the method or body, so those
+/// come from `request`, and the URL is the one that answered, after any redirects.
+pub fn replay_request(request: &HttpRequest, response: &HttpResponse) -> HttpRequest {
+    let headers = response
+        .request_headers
+        .iter()
+        .filter(|h| !RECOMPUTED_HEADERS.contains(&h.name.to_lowercase().as_str()))
+        .map(|h| HttpRequestHeader {
+            enabled: true,
+            name: h.name.clone(),
+            value: h.value.clone(),
+            ..Default::default()
+        })
+        .collect();
+
+    HttpRequest {
+        url: response.url.clone(),
+        url_parameters: Vec::new(),
+        headers,
+        authentication_type: None,
+        auth_profile_id: None,
+        // Conditional headers that were sent are already among the headers
+        cache_responses: false,
+        ..request.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::replay::replay_request;
+    use yaak_models::models::{
+        HttpRequest, HttpRequestHeader, HttpResponse, HttpResponseHeader, HttpUrlParameter,
+    };
+
+    #[test]
+    fn uses_what_was_sent() {
+        let request = HttpRequest {
+            method: "POST".into(),
+            url: "${[ host ]}/users/:id".into(),
+            url_parameters: vec![HttpUrlParameter {
+                enabled: true,
+                name: ":id".into(),
+                value: "2".into(),
+                ..Default::default()
+            }],
+            headers: vec![HttpRequestHeader {
+                enabled: true,
+                name: "X-Edited".into(),
+                value: "later".into(),
+                ..Default::default()
+            }],
+            authentication_type: Some("bearer".into()),
+            ..Default::default()
+        };
+        let response = HttpResponse {
+            url: "https://example.com/users/1?verbose=true".into(),
+            request_headers: vec![
+                HttpResponseHeader {
+                    name: "authorization".into(),
+                    value: "Bearer abc".into(),
+                },
+                HttpResponseHeader {
+                    name: "content-length".into(),
+                    value: "12".into(),
+                },
+            ],
+            ..Default::default()
+        };
+
+        let replay = replay_request(&request, &response);
+        assert_eq!(replay.method, "POST");
+        assert_eq!(replay.url, "https://example.com/users/1?verbose=true");
+        assert!(replay.url_parameters.is_empty());
+        assert_eq!(replay.authentication_type, None);
+        assert_eq!(
+            replay.headers,
+            vec![HttpRequestHeader {
+                enabled: true,
+                name: "authorization".into(),
+                value: "Bearer abc".into(),
+                ..Default::default()
+            }]
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Whether a response with this status should be retried. Only statuses where the server is
+/// explicitly asking the client to come back later are retried.
+pub fn is_retryable_status(status: u16) -> bool {
+    status == 429 || status == 503
+}
+
+/// Parse a `Retry-After` header value, which is either a number of seconds or an HTTP date.
+/// Dates in the past resolve to a zero wait.
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = date.with_timezone(&Utc) - now;
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// How long to wait before the next attempt. Uses `Retry-After` when the server sent a valid one
+/// and `backoff` otherwise, never waiting longer than `max_wait`.
+pub fn retry_wait(retry_after: Option<&str>, backoff: Duration, max_wait: Duration) -> Duration {
+    let wait = retry_after.and_then(|v| parse_retry_after(v, Utc::now())).unwrap_or(backoff);
+    wait.min(max_wait)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::retry::{is_retryable_status, parse_retry_after, retry_wait};
+    use chrono::{DateTime, Utc};
+    use std::time::Duration;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn retryable_statuses() {
+        assert!(is_retryable_status(429));
+        assert!(is_retryable_status(503));
+        assert!(!is_retryable_status(500));
+        assert!(!is_retryable_status(200));
+    }
+
+    #[test]
+    fn retry_after_seconds() {
+        assert_eq!(parse_retry_after("120", now()), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after(" 5 ", now()), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn retry_after_http_date() {
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now()),
+            Some(Duration::from_secs(30))
+        );
+    }
+
+    #[test]
+    fn retry_after_date_in_past() {
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now()), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn retry_after_invalid() {
+        assert_eq!(parse_retry_after("soon", now()), None);
+        assert_eq!(parse_retry_after("-1", now()), None);
+    }
+
+    #[test]
+    fn retry_wait_capped() {
+        let backoff = Duration::from_secs(1);
+        let max = Duration::from_secs(10);
+        assert_eq!(retry_wait(Some("3"), backoff, max), Duration::from_secs(3));
+        assert_eq!(retry_wait(Some("86400"), backoff, max), max);
+        assert_eq!(retry_wait(Some("garbage"), backoff, max), backoff);
+        assert_eq!(retry_wait(None, backoff, max), backoff);
+    }
+}
@@ -0,0 +1,47 @@
+use std::cmp::Reverse;
+
+/// What secret values are replaced with when masked
+pub const SECRET_MASK: &str = "********";
+
+/// Replace every occurrence of the secrets in some text with a mask, so it's safe to log. Secrets
+/// are matched both as-is and URL-encoded, since they often end up in a query string.
+pub fn mask_secrets(text: &str, secrets: &[String]) -> String {
+    let mut needles = secrets
+        .iter()
+        .filter(|s| !s.is_empty())
+        .flat_map(|s| [s.to_string(), urlencoding::encode(s).into_owned()])
+        .collect::<Vec<_>>();
+
+    // Longest first, so a secret that contains another is masked whole
+    needles.sort_by_key(|n| Reverse(n.len()));
+
+    needles.iter().fold(text.to_string(), |text, needle| text.replace(needle, SECRET_MASK))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::secrets::mask_secrets;
+
+    #[test]
+    fn masks_secrets() {
+        let secrets = vec!["abc123".to_string()];
+        assert_eq!(
+            mask_secrets("https://api.example.com?key=abc123&q=abc", &secrets),
+            "https://api.example.com?key=********&q=abc"
+        );
+        assert_eq!(mask_secrets("nothing here", &secrets), "nothing here");
+        assert_eq!(mask_secrets("text", &["".to_string()]), "text");
+    }
+
+    #[test]
+    fn masks_encoded_secrets() {
+        let secrets = vec!["p@ss word".to_string()];
+        assert_eq!(mask_secrets("?password=p%40ss%20word", &secrets), "?password=********");
+    }
+
+    #[test]
+    fn masks_longest_first() {
+        let secrets = vec!["tok".to_string(), "token-xyz".to_string()];
+        assert_eq!(mask_secrets("a=token-xyz b=tok", &secrets), "a=******** b=********");
+    }
+}
@@ -0,0 +1,69 @@
+use std::ops::RangeInclusive;
+
+/// Parse a comma-separated list of status codes and ranges, like `404, 500-599` or `5xx`
+pub fn parse_status_ranges(spec: &str) -> Result<Vec<RangeInclusive<u16>>, String> {
+    spec.split(',')
+        .map(|part| part.trim())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let invalid = || format!("Invalid status code range {part:?}");
+            let range = match (part.split_once('-'), part.to_lowercase().strip_suffix("xx")) {
+                (Some((start, end)), _) => {
+                    let start = start.trim().parse::<u16>().map_err(|_| invalid())?;
+                    let end = end.trim().parse::<u16>().map_err(|_| invalid())?;
+                    start..=end
+                }
+                (None, Some(class)) => {
+                    let class = class.parse::<u16>().ok().filter(|c| (1..=9).contains(c));
+                    let class = class.ok_or_else(invalid)?;
+                    class * 100..=class * 100 + 99
+                }
+                (None, None) => {
+                    let code = part.parse::<u16>().map_err(|_| invalid())?;
+                    code..=code
+                }
+            };
+            match range.is_empty() || !(100..=999).contains(range.start()) || *range.end() > 999 {
+                true => Err(invalid()),
+                false => Ok(range),
+            }
+        })
+        .collect()
+}
+
+/// Whether a status falls in any of the ranges
+pub fn status_in_ranges(ranges: &[RangeInclusive<u16>], status: u16) -> bool {
+    ranges.iter().any(|r| r.contains(&status))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::status::{parse_status_ranges, status_in_ranges};
+
+    #[test]
+    fn parses_ranges() {
+        assert_eq!(parse_status_ranges("404, 500-599").unwrap(), vec![404..=404, 500..=599]);
+        assert_eq!(parse_status_ranges("4xx,5XX").unwrap(), vec![400..=499, 500..=599]);
+        assert_eq!(parse_status_ranges(" 429 - 430 ,").unwrap(), vec![429..=430]);
+        assert_eq!(parse_status_ranges("").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn rejects_invalid() {
+        assert!(parse_status_ranges("abc").is_err());
+        assert!(parse_status_ranges("599-500").is_err());
+        assert!(parse_status_ranges("5000").is_err());
+        assert!(parse_status_ranges("0xx").is_err());
+        assert!(parse_status_ranges("700xx").is_err());
+        assert_eq!(parse_status_ranges("500-").unwrap_err(), "Invalid status code range \"500-\"");
+    }
+
+    #[test]
+    fn matches_status() {
+        let ranges = parse_status_ranges("404,5xx").unwrap();
+        assert!(status_in_ranges(&ranges, 404));
+        assert!(status_in_ranges(&ranges, 503));
+        assert!(!status_in_ranges(&ranges, 200));
+        assert!(!status_in_ranges(&[], 500));
+    }
+}
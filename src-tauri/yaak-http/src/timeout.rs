@@ -0,0 +1,37 @@
+use std::time::Duration;
+
+/// Timeout for a request from the workspace setting, in milliseconds. 0 means no timeout, and a
+/// negative value is an error rather than being treated as either.
+pub fn request_timeout(millis: i32) -> Result<Option<Duration>, String> {
+    match millis {
+        0 => Ok(None),
+        m if m < 0 => Err(format!(
+            "Request timeout must be 0 (no timeout) or a positive number of milliseconds, not {m}"
+        )),
+        m => Ok(Some(Duration::from_millis(m as u64))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::timeout::request_timeout;
+    use std::time::Duration;
+
+    #[test]
+    fn zero_is_no_timeout() {
+        assert_eq!(request_timeout(0), Ok(None));
+    }
+
+    #[test]
+    fn positive_is_milliseconds() {
+        assert_eq!(request_timeout(1), Ok(Some(Duration::from_millis(1))));
+        assert_eq!(request_timeout(250), Ok(Some(Duration::from_millis(250))));
+        assert_eq!(request_timeout(i32::MAX), Ok(Some(Duration::from_millis(i32::MAX as u64))));
+    }
+
+    #[test]
+    fn negative_is_invalid() {
+        assert!(request_timeout(-1).unwrap_err().ends_with("milliseconds, not -1"));
+        assert!(request_timeout(i32::MIN).is_err());
+    }
+}
@@ -0,0 +1,56 @@
+use std::net::IpAddr;
+
+/// Hostname sent with SNI when connecting to a host. SNI is only for DNS names, so nothing is
+/// sent for IP addresses.
+pub fn sni_hostname(host: &str) -> Option<String> {
+    let unbracketed = host.trim_start_matches('[').trim_end_matches(']');
+    if host.is_empty() || unbracketed.parse::<IpAddr>().is_ok() {
+        return None;
+    }
+    Some(host.trim_end_matches('.').to_lowercase())
+}
+
+/// Hosts listed in the insecure hosts setting, separated by commas or whitespace. They're
+/// normalized to match how hosts are compared when connecting, like `::1` for `[::1]`.
+pub fn insecure_hosts(setting: &str) -> Vec<String> {
+    setting
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|h| h.trim_start_matches('[').trim_end_matches(']').trim_end_matches('.'))
+        .filter(|h| !h.is_empty())
+        .map(|h| h.to_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tls::{insecure_hosts, sni_hostname};
+
+    #[test]
+    fn sni_for_dns_names() {
+        assert_eq!(sni_hostname("API.Example.com"), Some("api.example.com".to_string()));
+        assert_eq!(sni_hostname("example.com."), Some("example.com".to_string()));
+        assert_eq!(sni_hostname("localhost"), Some("localhost".to_string()));
+    }
+
+    #[test]
+    fn no_sni_for_ips() {
+        assert_eq!(sni_hostname("127.0.0.1"), None);
+        assert_eq!(sni_hostname("[::1]"), None);
+        assert_eq!(sni_hostname(""), None);
+    }
+
+    #[test]
+    fn parses_insecure_hosts() {
+        assert_eq!(
+            insecure_hosts("localhost, *.Internal.example.com,,dev.local.\n[::1] 10.0.0.5"),
+            vec![
+                "localhost",
+                "*.internal.example.com",
+                "dev.local",
+                "::1",
+                "10.0.0.5"
+            ]
+        );
+        assert!(insecure_hosts(" , ").is_empty());
+    }
+}
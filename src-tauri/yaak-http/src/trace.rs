@@ -0,0 +1,191 @@
+use std::fs::File;
+use std::io::Read;
+use yaak_models::bodies::TextBody;
+use yaak_models::models::{HttpRequest, HttpResponse};
+
+/// Max number of bytes of each body to include in a trace
+const BODY_SNIPPET_LEN: usize = 2048;
+
+/// Build a human-readable, curl-style trace of a request and its response, suitable for pasting
+/// into a ticket. The response body is read from its `body_path`, if there is one.
+pub fn format_http_trace(request: &HttpRequest, response: &HttpResponse) -> String {
+    let response_body = response.body_path.as_ref().and_then(|p| read_snippet(p));
+    build_trace(request, response, response_body)
+}
+
+fn build_trace(
+    request: &HttpRequest,
+    response: &HttpResponse,
+    response_body: Option<String>,
+) -> String {
+    let mut lines = Vec::new();
+
+    // Prefer the URL and headers that were actually sent, which have templates rendered and
+    // auth applied, falling back to the request as it was defined
+    let url = if response.url.is_empty() { &request.url } else { &response.url };
+    lines.push(format!("> {} {}", request.method.to_uppercase(), url));
+    if response.request_headers.is_empty() {
+        for h in request.headers.iter().filter(|h| h.enabled && !h.name.is_empty()) {
+            lines.push(format!("> {}: {}", h.name, h.value));
+        }
+    } else {
+        for h in &response.request_headers {
+            lines.push(format!("> {}: {}", h.name, h.value));
+        }
+    }
+    if let Ok(TextBody { text }) = TextBody::try_from(&request.body) {
+        if !text.is_empty() {
+            lines.push(">".to_string());
+            push_body(&mut lines, ">", &truncate(&text));
+        }
+    }
+
+    lines.push(String::new());
+
+    if let Some(error) = &response.error {
+        lines.push(format!("* Error: {error}"));
+    } else {
+        let version = response.version.as_deref().unwrap_or("HTTP/1.1");
+        let reason = response.status_reason.as_deref().unwrap_or_default();
+        lines.push(format!("< {version} {} {reason}", response.status).trim_end().to_string());
+        for h in &response.headers {
+            lines.push(format!("< {}: {}", h.name, h.value));
+        }
+        if let Some(body) = response_body.filter(|b| !b.is_empty()) {
+            lines.push("<".to_string());
+            push_body(&mut lines, "<", &body);
+        }
+    }
+
+    lines.push(String::new());
+
+    if let Some(remote_addr) = &response.remote_addr {
+        lines.push(format!("* Remote address: {remote_addr}"));
+    }
+    lines.push(format!("* Time to headers: {}ms", response.elapsed_headers));
+    lines.push(format!("* Total time: {}ms", response.elapsed));
+    if let Some(content_length) = response.content_length {
+        lines.push(format!("* Size: {content_length} bytes"));
+    }
+    lines.push(format!("* Sent at: {}", response.created_at));
+
+    lines.join("\n") + "\n"
+}
+
+fn push_body(lines: &mut Vec<String>, prefix: &str, body: &str) {
+    for l in body.lines() {
+        lines.push(format!("{prefix} {l}"));
+    }
+}
+
+fn truncate(text: &str) -> String {
+    if text.len() <= BODY_SNIPPET_LEN {
+        return text.to_string();
+    }
+    let mut end = BODY_SNIPPET_LEN;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}\n[truncated]", &text[..end])
+}
+
+fn read_snippet(path: &str) -> Option<String> {
+    let mut buf = Vec::new();
+    let f = File::open(path).ok()?;
+    f.take(BODY_SNIPPET_LEN as u64 + 1).read_to_end(&mut buf).ok()?;
+    let truncated = buf.len() > BODY_SNIPPET_LEN;
+    buf.truncate(BODY_SNIPPET_LEN);
+    let text = String::from_utf8_lossy(&buf).to_string();
+    match truncated {
+        true => Some(format!("{text}\n[truncated]")),
+        false => Some(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trace::build_trace;
+    use serde_json::Value;
+    use std::collections::BTreeMap;
+    use yaak_models::models::{HttpRequest, HttpRequestHeader, HttpResponse, HttpResponseHeader};
+
+    #[test]
+    fn trace_request_and_response() {
+        let req = HttpRequest {
+            method: "post".into(),
+            url: "${[ host ]}/users".into(),
+            body: BTreeMap::from([("text".into(), Value::from("{\"name\":\"Ada\"}"))]),
+            ..Default::default()
+        };
+        let resp = HttpResponse {
+            url: "https://example.com/users".into(),
+            request_headers: vec![HttpResponseHeader {
+                name: "content-type".into(),
+                value: "application/json".into(),
+            }],
+            status: 201,
+            status_reason: Some("Created".into()),
+            version: Some("HTTP/2".into()),
+            headers: vec![HttpResponseHeader {
+                name: "x-id".into(),
+                value: "1".into(),
+            }],
+            elapsed_headers: 10,
+            elapsed: 12,
+            ..Default::default()
+        };
+
+        let trace = build_trace(&req, &resp, Some("{\"id\":1}".into()));
+        assert!(trace.starts_with(
+            "> POST https://example.com/users\n\
+             > content-type: application/json\n\
+             >\n\
+             > {\"name\":\"Ada\"}\n\
+             \n\
+             < HTTP/2 201 Created\n\
+             < x-id: 1\n\
+             <\n\
+             < {\"id\":1}\n\
+             \n\
+             * Time to headers: 10ms\n\
+             * Total time: 12ms\n"
+        ));
+    }
+
+    #[test]
+    fn trace_error_falls_back_to_request_headers() {
+        let req = HttpRequest {
+            method: "GET".into(),
+            url: "https://example.com".into(),
+            headers: vec![
+                HttpRequestHeader {
+                    enabled: true,
+                    name: "Accept".into(),
+                    value: "*/*".into(),
+                    id: None,
+                    environments: Vec::new(),
+                },
+                HttpRequestHeader {
+                    enabled: false,
+                    name: "X-Disabled".into(),
+                    value: "1".into(),
+                    id: None,
+                    environments: Vec::new(),
+                },
+            ],
+            ..Default::default()
+        };
+        let resp = HttpResponse {
+            error: Some("Connection refused".into()),
+            ..Default::default()
+        };
+
+        let trace = build_trace(&req, &resp, None);
+        assert!(trace.starts_with(
+            "> GET https://example.com\n\
+             > Accept: */*\n\
+             \n\
+             * Error: Connection refused\n"
+        ));
+    }
+}
@@ -0,0 +1,69 @@
+use http::HeaderMap;
+use http_body::{Body, Frame, SizeHint};
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+/// Request body that sends trailers once the body it wraps is done. Its length is never known up
+/// front, so HTTP/1.1 sends it chunked, which is the only way trailers can go over HTTP/1.1 at
+/// all, and then only the ones named in the request's `Trailer` header.
+pub struct WithTrailers<B> {
+    inner: B,
+    inner_done: bool,
+    trailers: Option<HeaderMap>,
+}
+
+impl<B> WithTrailers<B> {
+    pub fn new(inner: B, trailers: HeaderMap) -> Self {
+        WithTrailers {
+            inner,
+            inner_done: false,
+            trailers: Some(trailers),
+        }
+    }
+}
+
+impl<B: Body + Unpin> Body for WithTrailers<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        if !self.inner_done {
+            match ready!(Pin::new(&mut self.inner).poll_frame(cx)) {
+                Some(frame) => return Poll::Ready(Some(frame)),
+                None => self.inner_done = true,
+            }
+        }
+        Poll::Ready(self.trailers.take().map(|t| Ok(Frame::trailers(t))))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner_done && self.trailers.is_none()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trailers::WithTrailers;
+    use http::{HeaderMap, HeaderValue};
+    use http_body::Body;
+    use http_body_util::{BodyExt, Full};
+
+    #[tokio::test]
+    async fn sends_trailers_after_body() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("grpc-status", HeaderValue::from_static("0"));
+        let body = WithTrailers::new(Full::new(&b"hello"[..]), trailers.clone());
+        assert_eq!(body.size_hint().exact(), None);
+
+        let collected = body.collect().await.unwrap();
+        assert_eq!(collected.trailers(), Some(&trailers));
+        assert_eq!(collected.to_bytes().as_ref(), b"hello");
+    }
+}
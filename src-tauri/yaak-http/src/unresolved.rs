@@ -0,0 +1,38 @@
+/// Opening and closing delimiters of templates that can be left over after rendering. `${[ ]}` is
+/// this app's own syntax and `{{ }}` is the one most other clients use, which shows up in
+/// imported requests.
+const TEMPLATE_DELIMITERS: [(&str, &str); 2] = [("${[", "]}"), ("{{", "}}")];
+
+/// First template marker left in rendered text, like `{{ token }}`, which would otherwise be sent
+/// as literal text
+pub fn unresolved_template(text: &str) -> Option<&str> {
+    TEMPLATE_DELIMITERS
+        .iter()
+        .filter_map(|(open, close)| {
+            let start = text.find(open)?;
+            let end = text[start + open.len()..].find(close)? + start + open.len() + close.len();
+            Some((start, end))
+        })
+        .min()
+        .map(|(start, end)| &text[start..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::unresolved::unresolved_template;
+
+    #[test]
+    fn finds_markers() {
+        assert_eq!(unresolved_template("Bearer {{ token }}"), Some("{{ token }}"));
+        assert_eq!(unresolved_template("https://${[ host ]}/{{path}}"), Some("${[ host ]}"));
+        assert_eq!(unresolved_template("{{a}} ${[ b ]}"), Some("{{a}}"));
+    }
+
+    #[test]
+    fn ignores_plain_text() {
+        assert_eq!(unresolved_template("Bearer abc123"), None);
+        assert_eq!(unresolved_template("{\"a\":{\"b\":1}}"), None);
+        assert_eq!(unresolved_template("only {{ opened"), None);
+        assert_eq!(unresolved_template("${ not a template }"), None);
+    }
+}
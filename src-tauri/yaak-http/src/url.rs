@@ -0,0 +1,214 @@
+use std::fmt;
+use std::net::Ipv6Addr;
+
+/// Part of a URL, for pointing at the one that's invalid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UrlPart {
+    Scheme,
+    Host,
+    Port,
+    Path,
+    Query,
+    Fragment,
+}
+
+impl fmt::Display for UrlPart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            UrlPart::Scheme => "scheme",
+            UrlPart::Host => "host",
+            UrlPart::Port => "port",
+            UrlPart::Path => "path",
+            UrlPart::Query => "query",
+            UrlPart::Fragment => "fragment",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UrlError {
+    pub part: UrlPart,
+    pub message: String,
+}
+
+impl fmt::Display for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Find what makes a URL invalid, to explain a parse failure better than the parsers do. Returns
+/// None when no part of it stands out, in which case the parser's own error is all there is.
+pub fn find_url_error(url: &str) -> Option<UrlError> {
+    let err = |part, message: String| Some(UrlError { part, message });
+
+    let (scheme, rest) = match url.split_once("://") {
+        Some(s) => s,
+        None => return err(UrlPart::Scheme, "Missing scheme, like http://".to_string()),
+    };
+    let valid_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    if !valid_scheme {
+        return err(UrlPart::Scheme, format!("Invalid scheme {scheme:?}"));
+    }
+    if !matches!(scheme.to_lowercase().as_str(), "http" | "https") {
+        return err(
+            UrlPart::Scheme,
+            format!("Unsupported scheme {scheme:?}, expected http or https"),
+        );
+    }
+
+    let authority_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (authority, rest) = rest.split_at(authority_end);
+    let host_port = authority.rsplit_once('@').map(|(_, hp)| hp).unwrap_or(authority);
+
+    let (host, port) = match host_port.strip_prefix('[') {
+        Some(bracketed) => {
+            let (ip, after) = match bracketed.split_once(']') {
+                Some(s) => s,
+                None => return err(UrlPart::Host, "Missing \"]\" after IPv6 host".to_string()),
+            };
+            if ip.parse::<Ipv6Addr>().is_err() {
+                return err(UrlPart::Host, format!("Invalid IPv6 address {ip:?}"));
+            }
+            match after {
+                "" => (ip, None),
+                a => match a.strip_prefix(':') {
+                    Some(p) => (ip, Some(p)),
+                    None => return err(UrlPart::Host, format!("Unexpected {a:?} after IPv6 host")),
+                },
+            }
+        }
+        None => match host_port.rsplit_once(':') {
+            Some((h, p)) => (h, Some(p)),
+            None => (host_port, None),
+        },
+    };
+
+    if host.is_empty() {
+        return err(UrlPart::Host, "Missing host".to_string());
+    }
+    let invalid_host_char = |c: char| {
+        c.is_whitespace()
+            || c.is_control()
+            || matches!(
+                c,
+                '#' | '%' | '/' | ':' | '<' | '>' | '?' | '@' | '[' | '\\' | ']' | '^' | '|'
+            )
+    };
+    // IPv6 addresses were already checked, colons and all
+    let is_ipv6 = host_port.starts_with('[');
+    if let Some(c) = host.chars().find(|c| !is_ipv6 && invalid_host_char(*c)) {
+        return err(UrlPart::Host, format!("Invalid character {c:?} in host {host:?}"));
+    }
+
+    // An empty port (like `http://host:/`) just means the default
+    if let Some(port) = port.filter(|p| !p.is_empty()) {
+        if !port.chars().all(|c| c.is_ascii_digit()) {
+            return err(UrlPart::Port, format!("Invalid port {port:?}, expected a number"));
+        }
+        if port.parse::<u16>().is_err() {
+            return err(UrlPart::Port, format!("Port {port} is out of range (0-65535)"));
+        }
+    }
+
+    let (rest, fragment) = rest.split_once('#').unwrap_or((rest, ""));
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+    for (part, value) in [
+        (UrlPart::Path, path),
+        (UrlPart::Query, query),
+        (UrlPart::Fragment, fragment),
+    ] {
+        if let Some(c) = value.chars().find(|c| invalid_char(*c)) {
+            return err(part, format!("Invalid character {c:?} in {part}"));
+        }
+    }
+
+    None
+}
+
+/// URL for a request URL that's a path, like `/users`, relative to an environment's base URL.
+/// Anything else, including protocol-relative `//host/path` URLs, is returned as-is.
+pub fn resolve_base_url(base_url: &str, url: &str) -> String {
+    let base_url = base_url.trim();
+    if base_url.is_empty() || !url.starts_with('/') || url.starts_with("//") {
+        return url.to_string();
+    }
+    format!("{}{url}", base_url.trim_end_matches('/'))
+}
+
+/// Characters that have to be percent-encoded to appear in a path, query, or fragment
+fn invalid_char(c: char) -> bool {
+    c.is_whitespace()
+        || c.is_control()
+        || !c.is_ascii()
+        || matches!(c, '"' | '<' | '>' | '\\' | '^' | '`' | '{' | '|' | '}')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::url::{UrlPart, find_url_error, resolve_base_url};
+
+    fn part(url: &str) -> Option<UrlPart> {
+        find_url_error(url).map(|e| e.part)
+    }
+
+    #[test]
+    fn valid_urls() {
+        assert_eq!(find_url_error("https://example.com"), None);
+        assert_eq!(find_url_error("http://user:pw@localhost:8080/a/b?c=d#e"), None);
+        assert_eq!(find_url_error("http://[::1]:3000/"), None);
+        assert_eq!(find_url_error("http://host:/path"), None);
+    }
+
+    #[test]
+    fn invalid_port() {
+        let e = find_url_error("http://localhost:abc/path").unwrap();
+        assert_eq!(e.part, UrlPart::Port);
+        assert_eq!(e.message, "Invalid port \"abc\", expected a number");
+        assert_eq!(
+            find_url_error("http://localhost:70000").unwrap().message,
+            "Port 70000 is out of range (0-65535)"
+        );
+        assert_eq!(part("http://[::1]:x"), Some(UrlPart::Port));
+    }
+
+    #[test]
+    fn invalid_host() {
+        assert_eq!(find_url_error("http:///path").unwrap().message, "Missing host");
+        assert_eq!(
+            find_url_error("http://exa mple.com/").unwrap().message,
+            "Invalid character ' ' in host \"exa mple.com\""
+        );
+        assert_eq!(part("http://[::zz]/"), Some(UrlPart::Host));
+        assert_eq!(part("http://[::1/"), Some(UrlPart::Host));
+    }
+
+    #[test]
+    fn invalid_scheme_path_and_query() {
+        assert_eq!(part("localhost"), Some(UrlPart::Scheme));
+        assert_eq!(part("ht tp://localhost"), Some(UrlPart::Scheme));
+        assert_eq!(part("ftp://localhost"), Some(UrlPart::Scheme));
+        assert_eq!(
+            find_url_error("http://localhost/a b").unwrap().message,
+            "Invalid character ' ' in path"
+        );
+        assert_eq!(part("http://localhost/a?q={x}"), Some(UrlPart::Query));
+        assert_eq!(part("http://localhost/a#x|y"), Some(UrlPart::Fragment));
+    }
+
+    #[test]
+    fn paths_are_relative_to_base_url() {
+        let base = "https://api.example.com/v1";
+        assert_eq!(resolve_base_url(base, "/users"), "https://api.example.com/v1/users");
+        assert_eq!(
+            resolve_base_url("https://api.example.com/", "/users?a=b"),
+            "https://api.example.com/users?a=b"
+        );
+        assert_eq!(resolve_base_url(base, "https://other.com/users"), "https://other.com/users");
+        assert_eq!(resolve_base_url(base, "localhost:8080/users"), "localhost:8080/users");
+        assert_eq!(resolve_base_url(base, "//cdn.example.com/a"), "//cdn.example.com/a");
+        assert_eq!(resolve_base_url(" ", "/users"), "/users");
+    }
+}
@@ -1,10 +1,14 @@
 const COMMANDS: &[&str] = &[
     "delete",
+    "deleted_models",
     "duplicate",
     "get_settings",
     "grpc_events",
+    "restore",
+    "search",
     "upsert",
     "websocket_events",
+    "workspace_integrity",
     "workspace_models",
 ];
 
@@ -7,6 +7,7 @@ import { newStoreData } from './util';
 
 export const modelStoreDataAtom = atom(newStoreData());
 
+export const authProfilesAtom = createOrderedModelAtom('auth_profile', 'name', 'asc');
 export const cookieJarsAtom = createOrderedModelAtom('cookie_jar', 'name', 'asc');
 export const environmentsAtom = createOrderedModelAtom('environment', 'name', 'asc');
 export const foldersAtom = createModelAtom('folder');
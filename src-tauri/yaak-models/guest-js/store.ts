@@ -1,6 +1,6 @@
 import { invoke } from '@tauri-apps/api/core';
 import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
-import { AnyModel, ModelPayload } from '../bindings/gen_models';
+import { AnyModel, IntegrityReport, ModelPayload, SearchResult } from '../bindings/gen_models';
 import { modelStoreDataAtom } from './atoms';
 import { ExtractModel, JotaiStore, ModelStoreData } from './types';
 import { newStoreData } from './util';
@@ -197,3 +197,19 @@ function shouldIgnoreModel({ model, updateSource }: ModelPayload) {
 
   return false;
 }
+
+export function checkWorkspaceIntegrity(workspaceId: string) {
+  return invoke<IntegrityReport>('plugin:yaak-models|workspace_integrity', { workspaceId });
+}
+
+export function listDeletedModels(workspaceId: string) {
+  return invoke<AnyModel[]>('plugin:yaak-models|deleted_models', { workspaceId });
+}
+
+export function restoreModel(modelId: string) {
+  return invoke<string>('plugin:yaak-models|restore', { modelId });
+}
+
+export function searchWorkspace(workspaceId: string, query: string, limit?: number) {
+  return invoke<SearchResult[]>('plugin:yaak-models|search', { workspaceId, query, limit });
+}
@@ -2,6 +2,7 @@ import { ModelStoreData } from './types';
 
 export function newStoreData(): ModelStoreData {
   return {
+    auth_profile: {},
     cookie_jar: {},
     environment: {},
     folder: {},
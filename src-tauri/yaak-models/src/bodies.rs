@@ -0,0 +1,190 @@
+use crate::error::Error;
+use crate::error::Result;
+use crate::models::HttpRequest;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Body of a `graphql` request
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GraphqlBody {
+    pub query: String,
+    /// JSON object of variables, which may be empty
+    pub variables: String,
+}
+
+/// Body of an `application/x-www-form-urlencoded` request
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct UrlEncodedFormBody {
+    pub form: Vec<UrlEncodedFormParam>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct UrlEncodedFormParam {
+    pub enabled: bool,
+    pub name: String,
+    pub value: String,
+}
+
+impl Default for UrlEncodedFormParam {
+    fn default() -> Self {
+        UrlEncodedFormParam {
+            enabled: true,
+            name: String::new(),
+            value: String::new(),
+        }
+    }
+}
+
+/// Body of a `multipart/form-data` request
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MultipartBody {
+    pub form: Vec<MultipartParam>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MultipartParam {
+    pub enabled: bool,
+    pub name: String,
+    /// Text of the part, used when no file is set
+    pub value: String,
+    /// File or directory to send instead of the value. A directory sends each of its files as
+    /// its own part.
+    pub file: String,
+    /// Glob that files of a directory must match to be sent
+    pub filter: String,
+    /// Content type of the part, which is guessed from the file if empty
+    pub content_type: String,
+}
+
+impl Default for MultipartParam {
+    fn default() -> Self {
+        MultipartParam {
+            enabled: true,
+            name: String::new(),
+            value: String::new(),
+            file: String::new(),
+            filter: String::new(),
+            content_type: String::new(),
+        }
+    }
+}
+
+/// Body of a `binary` request, which sends a file as-is
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BinaryBody {
+    pub file_path: String,
+}
+
+/// Body of a JSON, XML or other text request
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TextBody {
+    pub text: String,
+}
+
+impl TryFrom<&BTreeMap<String, Value>> for GraphqlBody {
+    type Error = Error;
+
+    fn try_from(body: &BTreeMap<String, Value>) -> Result<Self> {
+        from_body(body)
+    }
+}
+
+impl TryFrom<&BTreeMap<String, Value>> for UrlEncodedFormBody {
+    type Error = Error;
+
+    fn try_from(body: &BTreeMap<String, Value>) -> Result<Self> {
+        from_body(body)
+    }
+}
+
+impl TryFrom<&BTreeMap<String, Value>> for MultipartBody {
+    type Error = Error;
+
+    fn try_from(body: &BTreeMap<String, Value>) -> Result<Self> {
+        from_body(body)
+    }
+}
+
+impl TryFrom<&BTreeMap<String, Value>> for BinaryBody {
+    type Error = Error;
+
+    fn try_from(body: &BTreeMap<String, Value>) -> Result<Self> {
+        from_body(body)
+    }
+}
+
+impl TryFrom<&BTreeMap<String, Value>> for TextBody {
+    type Error = Error;
+
+    fn try_from(body: &BTreeMap<String, Value>) -> Result<Self> {
+        from_body(body)
+    }
+}
+
+/// Body of a request, parsed according to its `body_type`
+#[derive(Debug, Clone, PartialEq)]
+pub enum HttpRequestBody {
+    Graphql(GraphqlBody),
+    UrlEncodedForm(UrlEncodedFormBody),
+    Multipart(MultipartBody),
+    Binary(BinaryBody),
+    Json(TextBody),
+    Text(TextBody),
+}
+
+impl HttpRequestBody {
+    /// Parse the body of a request, or None if it has no body type, or its body is missing the
+    /// fields that type needs. Bodies keep the fields of other types they were switched from,
+    /// so a form body with only `text` is sent as text.
+    pub fn from_request(request: &HttpRequest) -> Result<Option<HttpRequestBody>> {
+        let body = &request.body;
+        let body_type = match request.body_type.as_deref() {
+            Some(t) => t,
+            None => return Ok(None),
+        };
+
+        let body = match body_type {
+            "graphql" => HttpRequestBody::Graphql(body.try_into()?),
+            "application/x-www-form-urlencoded" if body.contains_key("form") => {
+                HttpRequestBody::UrlEncodedForm(body.try_into()?)
+            }
+            "multipart/form-data" if body.contains_key("form") => {
+                HttpRequestBody::Multipart(body.try_into()?)
+            }
+            "binary" if body.contains_key("filePath") => HttpRequestBody::Binary(body.try_into()?),
+            "application/json" if body.contains_key("text") => {
+                HttpRequestBody::Json(body.try_into()?)
+            }
+            _ if body.contains_key("text") => HttpRequestBody::Text(body.try_into()?),
+            _ => return Ok(None),
+        };
+        Ok(Some(body))
+    }
+}
+
+/// Parse a stored body leniently, so missing and `null` fields read as empty like they always
+/// have, and any fields the type doesn't use are ignored
+fn from_body<T: DeserializeOwned>(body: &BTreeMap<String, Value>) -> Result<T> {
+    let body = Value::Object(body.clone().into_iter().collect());
+    Ok(serde_json::from_value(without_nulls(&body))?)
+}
+
+fn without_nulls(value: &Value) -> Value {
+    match value {
+        Value::Object(o) => {
+            let o = o.iter().filter(|(_, v)| !v.is_null());
+            Value::Object(o.map(|(k, v)| (k.clone(), without_nulls(v))).collect())
+        }
+        Value::Array(a) => Value::Array(a.iter().map(without_nulls).collect()),
+        v => v.clone(),
+    }
+}
@@ -1,6 +1,7 @@
 use crate::error::Error::GenericError;
 use crate::error::Result;
 use crate::models::{AnyModel, GrpcEvent, Settings, WebsocketEvent};
+use crate::queries::{IntegrityReport, SearchResult};
 use crate::query_manager::QueryManagerExt;
 use crate::util::UpdateSource;
 use tauri::{AppHandle, Runtime, WebviewWindow};
@@ -10,6 +11,7 @@ pub(crate) fn upsert<R: Runtime>(window: WebviewWindow<R>, model: AnyModel) -> R
     let db = window.db();
     let source = &UpdateSource::from_window(&window);
     let id = match model {
+        AnyModel::AuthProfile(m) => db.upsert_auth_profile(&m, source)?.id,
         AnyModel::CookieJar(m) => db.upsert_cookie_jar(&m, source)?.id,
         AnyModel::Environment(m) => db.upsert_environment(&m, source)?.id,
         AnyModel::Folder(m) => db.upsert_folder(&m, source)?.id,
@@ -34,6 +36,7 @@ pub(crate) fn delete<R: Runtime>(window: WebviewWindow<R>, model: AnyModel) -> R
     window.with_tx(|tx| {
         let source = &UpdateSource::from_window(&window);
         let id = match model {
+            AnyModel::AuthProfile(m) => tx.delete_auth_profile(&m, source)?.id,
             AnyModel::CookieJar(m) => tx.delete_cookie_jar(&m, source)?.id,
             AnyModel::Environment(m) => tx.delete_environment(&m, source)?.id,
             AnyModel::Folder(m) => tx.delete_folder(&m, source)?.id,
@@ -69,6 +72,30 @@ pub(crate) fn duplicate<R: Runtime>(window: WebviewWindow<R>, model: AnyModel) -
     })
 }
 
+/// Restore a deleted request, folder or environment
+#[tauri::command]
+pub(crate) fn restore<R: Runtime>(window: WebviewWindow<R>, model_id: &str) -> Result<String> {
+    // Use transaction for restores because it might recurse
+    window.with_tx(|tx| {
+        let source = &UpdateSource::from_window(&window);
+        let id = match tx.restore_model(model_id, source)? {
+            AnyModel::Environment(m) => m.id,
+            AnyModel::Folder(m) => m.id,
+            AnyModel::HttpRequest(m) => m.id,
+            a => return Err(GenericError(format!("Cannot restore AnyModel {a:?})"))),
+        };
+        Ok(id)
+    })
+}
+
+#[tauri::command]
+pub(crate) fn deleted_models<R: Runtime>(
+    window: WebviewWindow<R>,
+    workspace_id: &str,
+) -> Result<Vec<AnyModel>> {
+    Ok(window.db().list_deleted_models(workspace_id)?)
+}
+
 #[tauri::command]
 pub(crate) fn websocket_events<R: Runtime>(
     app_handle: AppHandle<R>,
@@ -90,6 +117,24 @@ pub(crate) fn get_settings<R: Runtime>(app_handle: AppHandle<R>) -> Result<Setti
     Ok(app_handle.db().get_settings())
 }
 
+#[tauri::command]
+pub(crate) fn workspace_integrity<R: Runtime>(
+    window: WebviewWindow<R>,
+    workspace_id: &str,
+) -> Result<IntegrityReport> {
+    Ok(window.db().check_workspace_integrity(workspace_id)?)
+}
+
+#[tauri::command]
+pub(crate) fn search<R: Runtime>(
+    window: WebviewWindow<R>,
+    workspace_id: &str,
+    query: &str,
+    limit: Option<usize>,
+) -> Result<Vec<SearchResult>> {
+    Ok(window.db().search_workspace(workspace_id, query, limit)?)
+}
+
 #[tauri::command]
 pub(crate) fn workspace_models<R: Runtime>(
     window: WebviewWindow<R>,
@@ -108,6 +153,7 @@ pub(crate) fn workspace_models<R: Runtime>(
 
     // Add the workspace children
     if let Some(wid) = workspace_id {
+        l.append(&mut db.list_auth_profiles(wid)?.into_iter().map(Into::into).collect());
         l.append(&mut db.list_cookie_jars(wid)?.into_iter().map(Into::into).collect());
         l.append(&mut db.list_environments(wid)?.into_iter().map(Into::into).collect());
         l.append(&mut db.list_folders(wid)?.into_iter().map(Into::into).collect());
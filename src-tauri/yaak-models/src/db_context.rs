@@ -2,10 +2,11 @@ use crate::connection_or_tx::ConnectionOrTx;
 use crate::error::Error::RowNotFound;
 use crate::models::{AnyModel, UpsertModelInfo};
 use crate::util::{ModelChangeEvent, ModelPayload, UpdateSource};
+use chrono::Utc;
 use rusqlite::OptionalExtension;
 use sea_query::{
-    Asterisk, Expr, IntoColumnRef, IntoIden, IntoTableRef, OnConflict, Query, SimpleExpr,
-    SqliteQueryBuilder,
+    Alias, Asterisk, Expr, IntoColumnRef, IntoIden, IntoTableRef, OnConflict, Query,
+    SelectStatement, SimpleExpr, SqliteQueryBuilder,
 };
 use sea_query_rusqlite::RusqliteBinder;
 use tokio::sync::mpsc;
@@ -38,10 +39,8 @@ impl<'a> DbContext<'a> {
     where
         M: Into<AnyModel> + Clone + UpsertModelInfo,
     {
-        let (sql, params) = Query::select()
-            .from(M::table_name())
-            .column(Asterisk)
-            .cond_where(Expr::col(col).eq(value))
+        let (sql, params) = select_existing::<M>()
+            .and_where(Expr::col(col).eq(value))
             .build_rusqlite(SqliteQueryBuilder);
         let mut stmt = self.conn.prepare(sql.as_str()).expect("Failed to prepare query");
         stmt.query_row(&*params.as_params(), M::from_row)
@@ -53,10 +52,7 @@ impl<'a> DbContext<'a> {
     where
         M: Into<AnyModel> + Clone + UpsertModelInfo,
     {
-        let (sql, params) = Query::select()
-            .from(M::table_name())
-            .column(Asterisk)
-            .build_rusqlite(SqliteQueryBuilder);
+        let (sql, params) = select_existing::<M>().build_rusqlite(SqliteQueryBuilder);
         let mut stmt = self.conn.resolve().prepare(sql.as_str())?;
         let items = stmt.query_map(&*params.as_params(), M::from_row)?;
         Ok(items.map(|v| v.unwrap()).collect())
@@ -71,27 +67,34 @@ impl<'a> DbContext<'a> {
     where
         M: Into<AnyModel> + Clone + UpsertModelInfo,
     {
-        // TODO: Figure out how to do this conditional builder better
-        let (sql, params) = if let Some(limit) = limit {
-            Query::select()
-                .from(M::table_name())
-                .column(Asterisk)
-                .cond_where(Expr::col(col).eq(value))
-                .limit(limit)
-                .build_rusqlite(SqliteQueryBuilder)
-        } else {
-            Query::select()
-                .from(M::table_name())
-                .column(Asterisk)
-                .cond_where(Expr::col(col).eq(value))
-                .build_rusqlite(SqliteQueryBuilder)
-        };
+        let mut query = select_existing::<M>();
+        query.and_where(Expr::col(col).eq(value));
+        if let Some(limit) = limit {
+            query.limit(limit);
+        }
+        let (sql, params) = query.build_rusqlite(SqliteQueryBuilder);
 
         let mut stmt = self.conn.resolve().prepare(sql.as_str())?;
         let items = stmt.query_map(&*params.as_params(), M::from_row)?;
         Ok(items.map(|v| v.unwrap()).collect())
     }
 
+    /// Soft-deleted rows (see [`UpsertModelInfo::soft_deletes`]) that match a condition
+    pub(crate) fn find_deleted<M>(&self, cond: SimpleExpr) -> crate::error::Result<Vec<M>>
+    where
+        M: Into<AnyModel> + Clone + UpsertModelInfo,
+    {
+        let (sql, params) = Query::select()
+            .from(M::table_name())
+            .column(Asterisk)
+            .and_where(cond)
+            .and_where(Expr::col(Alias::new("deleted_at")).is_not_null())
+            .build_rusqlite(SqliteQueryBuilder);
+        let mut stmt = self.conn.resolve().prepare(sql.as_str())?;
+        let items = stmt.query_map(&*params.as_params(), M::from_row)?;
+        Ok(items.map(|v| v.unwrap()).collect())
+    }
+
     pub fn upsert<M>(&self, model: &M, source: &UpdateSource) -> crate::error::Result<M>
     where
         M: Into<AnyModel> + From<AnyModel> + UpsertModelInfo + Clone,
@@ -150,6 +153,8 @@ impl<'a> DbContext<'a> {
         Ok(m)
     }
 
+    /// Delete a model, which only marks it as deleted for models that soft-delete. Either way,
+    /// it's gone as far as listeners are concerned.
     pub(crate) fn delete<'s, M>(
         &self,
         m: &M,
@@ -158,10 +163,18 @@ impl<'a> DbContext<'a> {
     where
         M: Into<AnyModel> + Clone + UpsertModelInfo,
     {
-        let (sql, params) = Query::delete()
-            .from_table(M::table_name())
-            .cond_where(Expr::col(M::id_column().into_iden()).eq(m.get_id()))
-            .build_rusqlite(SqliteQueryBuilder);
+        let id_matches = Expr::col(M::id_column().into_iden()).eq(m.get_id());
+        let (sql, params) = match M::soft_deletes() {
+            true => Query::update()
+                .table(M::table_name())
+                .value(Alias::new("deleted_at"), Utc::now().naive_utc())
+                .cond_where(id_matches)
+                .build_rusqlite(SqliteQueryBuilder),
+            false => Query::delete()
+                .from_table(M::table_name())
+                .cond_where(id_matches)
+                .build_rusqlite(SqliteQueryBuilder),
+        };
         self.conn.execute(sql.as_str(), &*params.as_params())?;
 
         let payload = ModelPayload {
@@ -173,4 +186,27 @@ impl<'a> DbContext<'a> {
         self.events_tx.try_send(payload).unwrap();
         Ok(m.clone())
     }
+
+    /// Delete a soft-deleted model's row for good. Listeners were already told it was deleted.
+    pub(crate) fn purge<M>(&self, m: &M) -> crate::error::Result<()>
+    where
+        M: UpsertModelInfo,
+    {
+        let (sql, params) = Query::delete()
+            .from_table(M::table_name())
+            .cond_where(Expr::col(M::id_column().into_iden()).eq(m.get_id()))
+            .build_rusqlite(SqliteQueryBuilder);
+        self.conn.execute(sql.as_str(), &*params.as_params())?;
+        Ok(())
+    }
+}
+
+/// Select every column of a model's table, leaving out soft-deleted rows
+fn select_existing<M: UpsertModelInfo>() -> SelectStatement {
+    let mut query = Query::select();
+    query.from(M::table_name()).column(Asterisk);
+    if M::soft_deletes() {
+        query.and_where(Expr::col(Alias::new("deleted_at")).is_null());
+    }
+    query
 }
@@ -21,6 +21,13 @@ pub enum Error {
     #[error("Model error: {0}")]
     GenericError(String),
     
+    #[error("Invalid {field} proxy URL \"{url}\": {reason}")]
+    InvalidProxyUrl {
+        field: String,
+        url: String,
+        reason: String,
+    },
+
     #[error("Row not found")]
     RowNotFound,
 
@@ -17,6 +17,7 @@ use tauri::plugin::TauriPlugin;
 use tauri::{generate_handler, AppHandle, Emitter, Manager, Runtime};
 use tokio::sync::mpsc;
 
+pub mod bodies;
 mod commands;
 
 mod connection_or_tx;
@@ -42,9 +43,13 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             upsert,
             delete,
             duplicate,
+            restore,
+            deleted_models,
             workspace_models,
             grpc_events,
             websocket_events,
+            workspace_integrity,
+            search,
             get_settings,
         ])
         .setup(|app_handle, _api| {
@@ -1,10 +1,13 @@
+use crate::error::Error::InvalidProxyUrl;
 use crate::error::Result;
 use crate::models::HttpRequestIden::{
-    Authentication, AuthenticationType, Body, BodyType, CreatedAt, Description, FolderId, Headers,
-    Method, Name, SortPriority, UpdatedAt, Url, UrlParameters, WorkspaceId,
+    AuthProfileId, Authentication, AuthenticationType, Body, BodyType, CaCertPem, CacheResponses,
+    CreatedAt, DeletedAt, DependsOn, Description, FolderId, Headers, LastResponseId, Method, Name,
+    Notes, SortPriority, TimeoutMs, TlsServerName, Trailers, UpdatedAt, Url, UrlParameters,
+    WorkspaceId,
 };
 use crate::util::{generate_prefixed_id, UpdateSource};
-use chrono::{NaiveDateTime, Utc};
+use chrono::{NaiveDateTime, NaiveTime, Utc};
 use rusqlite::Row;
 use sea_query::{enum_def, IntoIden, IntoTableRef, SimpleExpr};
 use serde::{Deserialize, Deserializer, Serialize};
@@ -33,10 +36,58 @@ pub enum ProxySetting {
         http: String,
         https: String,
         auth: Option<ProxySettingAuth>,
+        /// Tunnel plain-HTTP requests with CONNECT too, instead of forwarding them to the proxy
+        #[serde(default, rename = "alwaysConnect")]
+        #[ts(optional, as = "Option<bool>")]
+        always_connect: bool,
     },
     Disabled,
 }
 
+impl ProxySetting {
+    /// Check that the proxy URLs will be accepted when sending a request. Empty URLs are valid
+    /// and mean no proxy is used for that scheme.
+    pub fn validate(&self) -> Result<()> {
+        let (http, https) = match self {
+            ProxySetting::Enabled { http, https, .. } => (http, https),
+            ProxySetting::Disabled => return Ok(()),
+        };
+        validate_proxy_url("http", http)?;
+        validate_proxy_url("https", https)?;
+        Ok(())
+    }
+}
+
+fn validate_proxy_url(field: &str, url: &str) -> Result<()> {
+    let url = url.trim();
+    // Templates are only known once rendered, which happens when a request is sent
+    if url.is_empty() || url.contains("${[") {
+        return Ok(());
+    }
+
+    let err = |reason: &str| InvalidProxyUrl {
+        field: field.to_string(),
+        url: url.to_string(),
+        reason: reason.to_string(),
+    };
+
+    // Like reqwest, treat a URL without a scheme (eg. localhost:8080) as http://
+    let full_url = match url.contains("://") {
+        true => url.to_string(),
+        false => format!("http://{url}"),
+    };
+    let parsed = url::Url::parse(&full_url).map_err(|e| err(&e.to_string()))?;
+    match parsed.scheme() {
+        "http" | "https" => {}
+        s => return Err(err(&format!("unsupported scheme {s}, expected http or https"))),
+    }
+    if parsed.host_str().unwrap_or_default().is_empty() {
+        return Err(err("missing host"));
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -99,6 +150,10 @@ pub struct Settings {
     pub updated_at: NaiveDateTime,
 
     pub appearance: String,
+    /// Local time ("HH:MM") to switch to the dark appearance, if scheduled
+    pub dark_appearance_start: Option<String>,
+    /// Local time ("HH:MM") to switch back to the light appearance, if scheduled
+    pub dark_appearance_end: Option<String>,
     pub editor_font_size: i32,
     pub editor_soft_wrap: bool,
     pub interface_font_size: i32,
@@ -141,6 +196,8 @@ impl UpsertModelInfo for Settings {
             (CreatedAt, upsert_date(source, self.created_at)),
             (UpdatedAt, upsert_date(source, self.updated_at)),
             (Appearance, self.appearance.as_str().into()),
+            (DarkAppearanceStart, self.dark_appearance_start.into()),
+            (DarkAppearanceEnd, self.dark_appearance_end.into()),
             (EditorFontSize, self.editor_font_size.into()),
             (EditorKeymap, self.editor_keymap.to_string().into()),
             (EditorSoftWrap, self.editor_soft_wrap.into()),
@@ -158,6 +215,8 @@ impl UpsertModelInfo for Settings {
         vec![
             SettingsIden::UpdatedAt,
             SettingsIden::Appearance,
+            SettingsIden::DarkAppearanceStart,
+            SettingsIden::DarkAppearanceEnd,
             SettingsIden::EditorFontSize,
             SettingsIden::EditorKeymap,
             SettingsIden::EditorSoftWrap,
@@ -183,6 +242,8 @@ impl UpsertModelInfo for Settings {
             created_at: row.get("created_at")?,
             updated_at: row.get("updated_at")?,
             appearance: row.get("appearance")?,
+            dark_appearance_start: row.get("dark_appearance_start")?,
+            dark_appearance_end: row.get("dark_appearance_end")?,
             editor_font_size: row.get("editor_font_size")?,
             editor_keymap: EditorKeymap::from_str(editor_keymap.as_str()).unwrap(),
             editor_soft_wrap: row.get("editor_soft_wrap")?,
@@ -197,6 +258,32 @@ impl UpsertModelInfo for Settings {
     }
 }
 
+impl Settings {
+    /// Resolve the appearance ("light", "dark", or "system") to use at the given local time. When
+    /// both ends of the dark schedule are set, the schedule takes precedence over `appearance`.
+    /// A schedule whose start is after its end wraps past midnight (eg. 19:00 to 07:00).
+    pub fn resolve_appearance(&self, now: NaiveTime) -> String {
+        let parse = |t: &Option<String>| {
+            t.as_deref().and_then(|t| NaiveTime::parse_from_str(t.trim(), "%H:%M").ok())
+        };
+        let start = parse(&self.dark_appearance_start);
+        let end = parse(&self.dark_appearance_end);
+        let (start, end) = match (start, end) {
+            (Some(start), Some(end)) if start != end => (start, end),
+            _ => return self.appearance.clone(),
+        };
+
+        let dark = match start < end {
+            true => now >= start && now < end,
+            false => now >= start || now < end,
+        };
+        match dark {
+            true => "dark".to_string(),
+            false => "light".to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -213,9 +300,71 @@ pub struct Workspace {
     // Settings
     #[serde(default = "default_true")]
     pub setting_validate_certificates: bool,
+    pub setting_accept_encoding: Option<String>,
+    /// Add `; charset=utf-8` to the `Content-Type` of text and JSON bodies that don't name a
+    /// charset. Bodies whose `Content-Type` names another charset are always sent in it.
+    pub setting_add_charset: bool,
+    pub setting_ca_bundle_path: Option<String>,
+    pub setting_client_cert_path: Option<String>,
+    pub setting_client_key_path: Option<String>,
+    #[serde(default = "default_true")]
+    pub setting_decompress: bool,
+    /// Attempt HTTP/3 (QUIC) for HTTPS requests, falling back to HTTP/1.1 or HTTP/2 over TCP when
+    /// the server doesn't answer it
+    pub setting_enable_http3: bool,
+    /// Status codes and ranges, like `404, 500-599` or `5xx`, that mark a response as an error.
+    /// The body is still stored.
+    pub setting_error_status_codes: Option<String>,
     #[serde(default = "default_true")]
     pub setting_follow_redirects: bool,
+    /// Hostnames, separated by commas, whose certificates are accepted even when invalid, while
+    /// every other host is still validated. `*.example.com` matches any subdomain.
+    pub setting_insecure_hosts: Option<String>,
+    /// Largest rendered body, in bytes, that will be sent. 0 means no limit.
+    pub setting_max_body_size: i32,
+    pub setting_max_concurrency: i32,
+    /// Largest size, in bytes, that a compressed response body may decode to before it's cut
+    /// off, to guard against decompression bombs. 0 means no limit.
+    #[serde(default = "default_max_decoded_size")]
+    pub setting_max_decoded_size: i32,
+    /// Most headers a response may have. 0 means no limit beyond the HTTP client's own, which is
+    /// 100 headers over HTTP/1.1.
+    pub setting_max_header_count: i32,
+    /// Largest total size, in bytes, of a response's header names and values. 0 means no limit
+    /// beyond the HTTP client's own, which is about 400 KiB over HTTP/1.1 and 16 KiB over HTTP/2.
+    /// Larger values raise the HTTP/2 limit, but the HTTP/1.1 one can't be raised.
+    pub setting_max_header_size: i32,
+    pub setting_max_retries: i32,
+    pub setting_max_retry_wait: i32,
+    /// How long, in milliseconds, an idle connection is kept for reuse. 0 closes connections as
+    /// soon as they're idle.
+    #[serde(default = "default_pool_idle_timeout")]
+    pub setting_pool_idle_timeout: i32,
+    /// Follow redirects from HTTPS to plain HTTP, which are otherwise blocked
+    pub setting_redirect_allow_downgrade: bool,
+    /// Keep the method and body when following 301 and 302 redirects, instead of switching POST
+    /// to GET like browsers do
+    pub setting_redirect_preserve_method: bool,
+    /// Refuse to send a request whose rendered URL, query parameters or headers still contain
+    /// template markers, like `{{ token }}` or `${[ token ]}`, rather than sending them as-is
+    pub setting_reject_unresolved_templates: bool,
+    pub setting_request_id_header: Option<String>,
+    /// Milliseconds to wait for a request to finish. 0 means no timeout, and negative values are
+    /// invalid, so requests fail before being sent.
     pub setting_request_timeout: i32,
+    pub setting_response_dir: Option<String>,
+    /// Send an `Accept-Encoding` header, with the workspace's encodings or the ones that can be
+    /// decoded. Off sends none unless the request sets its own, so servers aren't asked for
+    /// compression at all.
+    #[serde(default = "default_true")]
+    pub setting_send_accept_encoding: bool,
+    /// Send a `yaak` User-Agent and an `Accept` for any type unless a request sets its own. Off
+    /// leaves them out, though the HTTP client always adds the same `Accept` if there's none.
+    #[serde(default = "default_true")]
+    pub setting_send_default_headers: bool,
+    /// Send HTTP/1 header names in Title-Case (eg. `X-Api-Key`) instead of lowercase. The exact
+    /// casing typed can't be kept, and HTTP/2 always uses lowercase.
+    pub setting_title_case_headers: bool,
 }
 
 impl UpsertModelInfo for Workspace {
@@ -245,8 +394,33 @@ impl UpsertModelInfo for Workspace {
             (UpdatedAt, upsert_date(source, self.updated_at)),
             (Name, self.name.trim().into()),
             (Description, self.description.into()),
+            (SettingAcceptEncoding, self.setting_accept_encoding.into()),
+            (SettingAddCharset, self.setting_add_charset.into()),
+            (SettingCaBundlePath, self.setting_ca_bundle_path.into()),
+            (SettingClientCertPath, self.setting_client_cert_path.into()),
+            (SettingClientKeyPath, self.setting_client_key_path.into()),
+            (SettingDecompress, self.setting_decompress.into()),
+            (SettingEnableHttp3, self.setting_enable_http3.into()),
+            (SettingErrorStatusCodes, self.setting_error_status_codes.into()),
             (SettingFollowRedirects, self.setting_follow_redirects.into()),
+            (SettingInsecureHosts, self.setting_insecure_hosts.into()),
+            (SettingMaxBodySize, self.setting_max_body_size.into()),
+            (SettingMaxConcurrency, self.setting_max_concurrency.into()),
+            (SettingMaxDecodedSize, self.setting_max_decoded_size.into()),
+            (SettingMaxHeaderCount, self.setting_max_header_count.into()),
+            (SettingMaxHeaderSize, self.setting_max_header_size.into()),
+            (SettingMaxRetries, self.setting_max_retries.into()),
+            (SettingMaxRetryWait, self.setting_max_retry_wait.into()),
+            (SettingPoolIdleTimeout, self.setting_pool_idle_timeout.into()),
+            (SettingRedirectAllowDowngrade, self.setting_redirect_allow_downgrade.into()),
+            (SettingRedirectPreserveMethod, self.setting_redirect_preserve_method.into()),
+            (SettingRejectUnresolvedTemplates, self.setting_reject_unresolved_templates.into()),
+            (SettingRequestIdHeader, self.setting_request_id_header.into()),
             (SettingRequestTimeout, self.setting_request_timeout.into()),
+            (SettingResponseDir, self.setting_response_dir.into()),
+            (SettingSendAcceptEncoding, self.setting_send_accept_encoding.into()),
+            (SettingSendDefaultHeaders, self.setting_send_default_headers.into()),
+            (SettingTitleCaseHeaders, self.setting_title_case_headers.into()),
             (SettingValidateCertificates, self.setting_validate_certificates.into()),
         ])
     }
@@ -257,8 +431,33 @@ impl UpsertModelInfo for Workspace {
             WorkspaceIden::Name,
             WorkspaceIden::Description,
             WorkspaceIden::SettingRequestTimeout,
+            WorkspaceIden::SettingAcceptEncoding,
+            WorkspaceIden::SettingAddCharset,
+            WorkspaceIden::SettingCaBundlePath,
+            WorkspaceIden::SettingClientCertPath,
+            WorkspaceIden::SettingClientKeyPath,
+            WorkspaceIden::SettingDecompress,
+            WorkspaceIden::SettingEnableHttp3,
+            WorkspaceIden::SettingErrorStatusCodes,
             WorkspaceIden::SettingFollowRedirects,
+            WorkspaceIden::SettingInsecureHosts,
+            WorkspaceIden::SettingMaxBodySize,
+            WorkspaceIden::SettingMaxConcurrency,
+            WorkspaceIden::SettingMaxDecodedSize,
+            WorkspaceIden::SettingMaxHeaderCount,
+            WorkspaceIden::SettingMaxHeaderSize,
+            WorkspaceIden::SettingMaxRetries,
+            WorkspaceIden::SettingMaxRetryWait,
+            WorkspaceIden::SettingPoolIdleTimeout,
+            WorkspaceIden::SettingRedirectAllowDowngrade,
+            WorkspaceIden::SettingRedirectPreserveMethod,
+            WorkspaceIden::SettingRejectUnresolvedTemplates,
+            WorkspaceIden::SettingRequestIdHeader,
             WorkspaceIden::SettingRequestTimeout,
+            WorkspaceIden::SettingResponseDir,
+            WorkspaceIden::SettingSendAcceptEncoding,
+            WorkspaceIden::SettingSendDefaultHeaders,
+            WorkspaceIden::SettingTitleCaseHeaders,
             WorkspaceIden::SettingValidateCertificates,
         ]
     }
@@ -274,8 +473,33 @@ impl UpsertModelInfo for Workspace {
             updated_at: row.get("updated_at")?,
             name: row.get("name")?,
             description: row.get("description")?,
+            setting_accept_encoding: row.get("setting_accept_encoding")?,
+            setting_add_charset: row.get("setting_add_charset")?,
+            setting_ca_bundle_path: row.get("setting_ca_bundle_path")?,
+            setting_client_cert_path: row.get("setting_client_cert_path")?,
+            setting_client_key_path: row.get("setting_client_key_path")?,
+            setting_decompress: row.get("setting_decompress")?,
+            setting_enable_http3: row.get("setting_enable_http3")?,
+            setting_error_status_codes: row.get("setting_error_status_codes")?,
             setting_follow_redirects: row.get("setting_follow_redirects")?,
+            setting_insecure_hosts: row.get("setting_insecure_hosts")?,
+            setting_max_body_size: row.get("setting_max_body_size")?,
+            setting_max_concurrency: row.get("setting_max_concurrency")?,
+            setting_max_decoded_size: row.get("setting_max_decoded_size")?,
+            setting_max_header_count: row.get("setting_max_header_count")?,
+            setting_max_header_size: row.get("setting_max_header_size")?,
+            setting_max_retries: row.get("setting_max_retries")?,
+            setting_max_retry_wait: row.get("setting_max_retry_wait")?,
+            setting_pool_idle_timeout: row.get("setting_pool_idle_timeout")?,
+            setting_redirect_allow_downgrade: row.get("setting_redirect_allow_downgrade")?,
+            setting_redirect_preserve_method: row.get("setting_redirect_preserve_method")?,
+            setting_reject_unresolved_templates: row.get("setting_reject_unresolved_templates")?,
+            setting_request_id_header: row.get("setting_request_id_header")?,
             setting_request_timeout: row.get("setting_request_timeout")?,
+            setting_response_dir: row.get("setting_response_dir")?,
+            setting_send_accept_encoding: row.get("setting_send_accept_encoding")?,
+            setting_send_default_headers: row.get("setting_send_default_headers")?,
+            setting_title_case_headers: row.get("setting_title_case_headers")?,
             setting_validate_certificates: row.get("setting_validate_certificates")?,
         })
     }
@@ -287,7 +511,15 @@ impl Workspace {
             name,
             model: "workspace".to_string(),
             setting_validate_certificates: true,
+            setting_decompress: true,
             setting_follow_redirects: true,
+            setting_max_body_size: 100 * 1024 * 1024,
+            setting_max_concurrency: 1,
+            setting_max_decoded_size: default_max_decoded_size(),
+            setting_max_retry_wait: 30_000,
+            setting_pool_idle_timeout: default_pool_idle_timeout(),
+            setting_send_accept_encoding: true,
+            setting_send_default_headers: true,
             ..Default::default()
         }
     }
@@ -304,6 +536,8 @@ pub struct WorkspaceMeta {
     pub workspace_id: String,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    /// Environment the most recent request in this workspace was sent with
+    pub last_environment_id: Option<String>,
     pub setting_sync_dir: Option<String>,
 }
 
@@ -333,6 +567,7 @@ impl UpsertModelInfo for WorkspaceMeta {
             (CreatedAt, upsert_date(source, self.created_at)),
             (UpdatedAt, upsert_date(source, self.updated_at)),
             (WorkspaceId, self.workspace_id.into()),
+            (LastEnvironmentId, self.last_environment_id.into()),
             (SettingSyncDir, self.setting_sync_dir.into()),
         ])
     }
@@ -340,6 +575,7 @@ impl UpsertModelInfo for WorkspaceMeta {
     fn update_columns() -> Vec<impl IntoIden> {
         vec![
             WorkspaceMetaIden::UpdatedAt,
+            WorkspaceMetaIden::LastEnvironmentId,
             WorkspaceMetaIden::SettingSyncDir,
         ]
     }
@@ -354,12 +590,13 @@ impl UpsertModelInfo for WorkspaceMeta {
             model: row.get("model")?,
             created_at: row.get("created_at")?,
             updated_at: row.get("updated_at")?,
+            last_environment_id: row.get("last_environment_id")?,
             setting_sync_dir: row.get("setting_sync_dir")?,
         })
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "gen_models.ts")]
 enum CookieDomain {
     HostOnly(String),
@@ -384,6 +621,116 @@ pub struct Cookie {
     path: (String, bool),
 }
 
+impl Cookie {
+    pub fn name(&self) -> &str {
+        self.name_value().0
+    }
+
+    pub fn value(&self) -> &str {
+        self.name_value().1
+    }
+
+    /// Copy of the cookie with another value, keeping its attributes
+    pub fn with_value(&self, value: &str) -> Cookie {
+        let raw_cookie = match self.raw_cookie.split_once(';') {
+            Some((_, attributes)) => format!("{}={value};{attributes}", self.name()),
+            None => format!("{}={value}", self.name()),
+        };
+        Cookie {
+            raw_cookie,
+            ..self.clone()
+        }
+    }
+
+    /// Whether both are the same cookie, going by name, domain and path, whatever their values
+    pub fn is_same_cookie(&self, other: &Cookie) -> bool {
+        self.name() == other.name() && self.domain == other.domain && self.path == other.path
+    }
+
+    fn name_value(&self) -> (&str, &str) {
+        let pair = self.raw_cookie.split(';').next().unwrap_or_default();
+        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+        (name.trim(), value.trim())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+#[enum_def(table_name = "auth_profiles")]
+pub struct AuthProfile {
+    #[ts(type = "\"auth_profile\"")]
+    pub model: String,
+    pub id: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub workspace_id: String,
+
+    #[ts(type = "Record<string, any>")]
+    pub authentication: BTreeMap<String, Value>,
+    pub authentication_type: Option<String>,
+    pub name: String,
+}
+
+impl UpsertModelInfo for AuthProfile {
+    fn table_name() -> impl IntoTableRef {
+        AuthProfileIden::Table
+    }
+
+    fn id_column() -> impl IntoIden + Eq + Clone {
+        AuthProfileIden::Id
+    }
+
+    fn generate_id() -> String {
+        generate_prefixed_id("ap")
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn insert_values(
+        self,
+        source: &UpdateSource,
+    ) -> Result<Vec<(impl IntoIden + Eq, impl Into<SimpleExpr>)>> {
+        use AuthProfileIden::*;
+        Ok(vec![
+            (CreatedAt, upsert_date(source, self.created_at)),
+            (UpdatedAt, upsert_date(source, self.updated_at)),
+            (WorkspaceId, self.workspace_id.into()),
+            (Name, self.name.trim().into()),
+            (Authentication, serde_json::to_string(&self.authentication)?.into()),
+            (AuthenticationType, self.authentication_type.into()),
+        ])
+    }
+
+    fn update_columns() -> Vec<impl IntoIden> {
+        vec![
+            AuthProfileIden::UpdatedAt,
+            AuthProfileIden::Name,
+            AuthProfileIden::Authentication,
+            AuthProfileIden::AuthenticationType,
+        ]
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self>
+    where
+        Self: Sized,
+    {
+        let authentication: String = row.get("authentication")?;
+        Ok(Self {
+            id: row.get("id")?,
+            model: row.get("model")?,
+            workspace_id: row.get("workspace_id")?,
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+            name: row.get("name")?,
+            authentication: serde_json::from_str(authentication.as_str()).unwrap_or_default(),
+            authentication_type: row.get("authentication_type")?,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -468,8 +815,14 @@ pub struct Environment {
     pub environment_id: Option<String>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    /// When the environment was deleted. Deleted environments are hidden, and can be restored until
+    /// they're purged.
+    pub deleted_at: Option<NaiveDateTime>,
 
     pub name: String,
+    /// URL that request URLs starting with `/` are relative to, like `https://api.example.com`.
+    /// Environments without one use the base environment's.
+    pub base_url: Option<String>,
     pub variables: Vec<EnvironmentVariable>,
 }
 
@@ -490,6 +843,10 @@ impl UpsertModelInfo for Environment {
         self.id.clone()
     }
 
+    fn soft_deletes() -> bool {
+        true
+    }
+
     fn insert_values(
         self,
         source: &UpdateSource,
@@ -498,9 +855,11 @@ impl UpsertModelInfo for Environment {
         Ok(vec![
             (CreatedAt, upsert_date(source, self.created_at)),
             (UpdatedAt, upsert_date(source, self.updated_at)),
+            (DeletedAt, self.deleted_at.into()),
             (EnvironmentId, self.environment_id.into()),
             (WorkspaceId, self.workspace_id.into()),
             (Name, self.name.trim().into()),
+            (BaseUrl, self.base_url.into()),
             (Variables, serde_json::to_string(&self.variables)?.into()),
         ])
     }
@@ -508,7 +867,9 @@ impl UpsertModelInfo for Environment {
     fn update_columns() -> Vec<impl IntoIden> {
         vec![
             EnvironmentIden::UpdatedAt,
+            EnvironmentIden::DeletedAt,
             EnvironmentIden::Name,
+            EnvironmentIden::BaseUrl,
             EnvironmentIden::Variables,
         ]
     }
@@ -525,12 +886,58 @@ impl UpsertModelInfo for Environment {
             environment_id: row.get("environment_id")?,
             created_at: row.get("created_at")?,
             updated_at: row.get("updated_at")?,
+            deleted_at: row.get("deleted_at")?,
             name: row.get("name")?,
+            base_url: row.get("base_url")?,
             variables: serde_json::from_str(variables.as_str()).unwrap_or_default(),
         })
     }
 }
 
+impl Environment {
+    /// Copy of this environment with the variables of another layered over it, like a personal
+    /// environment over a shared one. See [`Environment::merged_variables`] for how they combine.
+    /// The other environment's base URL is used if it has one.
+    pub fn merge(&self, other: &Environment) -> Environment {
+        let base_url = match other.base_url.as_deref().map(|u| u.trim()) {
+            Some(u) if !u.is_empty() => other.base_url.clone(),
+            _ => self.base_url.clone(),
+        };
+        Environment {
+            base_url,
+            variables: self.merged_variables(other).into_iter().map(|v| v.variable).collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Variables of this environment with those of another layered over it, along with where
+    /// each came from. The other's variables win, except disabled and empty ones, which never
+    /// override anything when rendering either. So rendering with the merged variables gives the
+    /// same values as rendering with both environments.
+    pub fn merged_variables(&self, other: &Environment) -> Vec<MergedEnvironmentVariable> {
+        let overrides = |v: &EnvironmentVariable| v.enabled && !v.value.is_empty();
+        let in_self = |name: &str| self.variables.iter().any(|v| v.name == name);
+        let in_other = |name: &str| other.variables.iter().any(|v| v.name == name && overrides(v));
+
+        let mut merged = Vec::new();
+        for v in self.variables.iter().filter(|v| !in_other(&v.name)) {
+            merged.push(MergedEnvironmentVariable {
+                variable: v.clone(),
+                environment_id: self.id.clone(),
+                overridden: false,
+            });
+        }
+        for v in other.variables.iter().filter(|v| overrides(v) || !in_self(&v.name)) {
+            merged.push(MergedEnvironmentVariable {
+                variable: v.clone(),
+                environment_id: other.id.clone(),
+                overridden: in_self(&v.name),
+            });
+        }
+        merged
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -542,6 +949,19 @@ pub struct EnvironmentVariable {
     pub value: String,
     #[ts(optional, as = "Option<String>")]
     pub id: Option<String>,
+    /// Secret values are left as placeholders when rendering for export
+    #[ts(optional, as = "Option<bool>")]
+    pub secret: bool,
+}
+
+/// Variable of an environment merged with another, and where it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergedEnvironmentVariable {
+    pub variable: EnvironmentVariable,
+    /// ID of the environment the variable is from
+    pub environment_id: String,
+    /// Whether it replaced a variable of the same name from the environment it was merged over
+    pub overridden: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
@@ -554,12 +974,18 @@ pub struct Folder {
     pub id: String,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    /// When the folder was deleted. Deleted folders are hidden, and can be restored until they're
+    /// purged.
+    pub deleted_at: Option<NaiveDateTime>,
     pub workspace_id: String,
     pub folder_id: Option<String>,
 
     pub name: String,
     pub description: String,
     pub sort_priority: f32,
+    /// Headers sent with every request in the folder and its sub-folders, unless a sub-folder or
+    /// the request sets the same header
+    pub default_headers: Vec<HttpRequestHeader>,
 }
 
 impl UpsertModelInfo for Folder {
@@ -579,6 +1005,10 @@ impl UpsertModelInfo for Folder {
         self.id.clone()
     }
 
+    fn soft_deletes() -> bool {
+        true
+    }
+
     fn insert_values(
         self,
         source: &UpdateSource,
@@ -587,21 +1017,25 @@ impl UpsertModelInfo for Folder {
         Ok(vec![
             (CreatedAt, upsert_date(source, self.created_at)),
             (UpdatedAt, upsert_date(source, self.updated_at)),
+            (DeletedAt, self.deleted_at.into()),
             (WorkspaceId, self.workspace_id.into()),
             (FolderId, self.folder_id.into()),
             (Name, self.name.trim().into()),
             (Description, self.description.into()),
             (SortPriority, self.sort_priority.into()),
+            (DefaultHeaders, serde_json::to_string(&self.default_headers)?.into()),
         ])
     }
 
     fn update_columns() -> Vec<impl IntoIden> {
         vec![
             FolderIden::UpdatedAt,
+            FolderIden::DeletedAt,
             FolderIden::Name,
             FolderIden::Description,
             FolderIden::FolderId,
             FolderIden::SortPriority,
+            FolderIden::DefaultHeaders,
         ]
     }
 
@@ -609,6 +1043,7 @@ impl UpsertModelInfo for Folder {
     where
         Self: Sized,
     {
+        let default_headers: String = row.get("default_headers")?;
         Ok(Self {
             id: row.get("id")?,
             model: row.get("model")?,
@@ -616,9 +1051,11 @@ impl UpsertModelInfo for Folder {
             workspace_id: row.get("workspace_id")?,
             created_at: row.get("created_at")?,
             updated_at: row.get("updated_at")?,
+            deleted_at: row.get("deleted_at")?,
             folder_id: row.get("folder_id")?,
             name: row.get("name")?,
             description: row.get("description")?,
+            default_headers: serde_json::from_str(default_headers.as_str()).unwrap_or_default(),
         })
     }
 }
@@ -634,6 +1071,17 @@ pub struct HttpRequestHeader {
     pub value: String,
     #[ts(optional, as = "Option<String>")]
     pub id: Option<String>,
+    /// IDs of the only environments the header is sent in. Empty sends it in all of them.
+    #[ts(optional, as = "Option<Vec<String>>")]
+    pub environments: Vec<String>,
+}
+
+impl HttpRequestHeader {
+    /// Whether the header is sent while the environment with this ID is active
+    pub fn in_environment(&self, environment_id: Option<&str>) -> bool {
+        self.environments.is_empty()
+            || environment_id.is_some_and(|id| self.environments.iter().any(|e| e == id))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
@@ -647,6 +1095,21 @@ pub struct HttpUrlParameter {
     pub value: String,
     #[ts(optional, as = "Option<String>")]
     pub id: Option<String>,
+    /// Brackets to add to the name when it's sent. None sends the name as it's typed.
+    #[ts(optional, as = "Option<UrlParameterNotation>")]
+    pub notation: Option<UrlParameterNotation>,
+}
+
+/// Bracket notation for grouping URL parameters into arrays and objects, which many frameworks
+/// parse query strings with
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum UrlParameterNotation {
+    /// `items` is sent as `items[]`, and repeated for each value
+    Array,
+    /// `filter.name` is sent as `filter[name]`, and `a.b.c` as `a[b][c]`
+    Object,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
@@ -659,21 +1122,54 @@ pub struct HttpRequest {
     pub id: String,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    /// When the request was deleted. Deleted requests are hidden, and can be restored until they're
+    /// purged.
+    pub deleted_at: Option<NaiveDateTime>,
     pub workspace_id: String,
     pub folder_id: Option<String>,
 
+    /// Shared auth profile to authenticate with, which takes the place of the request's own
+    /// authentication
+    pub auth_profile_id: Option<String>,
     #[ts(type = "Record<string, any>")]
     pub authentication: BTreeMap<String, Value>,
     pub authentication_type: Option<String>,
     #[ts(type = "Record<string, any>")]
     pub body: BTreeMap<String, Value>,
     pub body_type: Option<String>,
+    /// PEM text of CA certificates to trust for this request, in addition to the system roots
+    pub ca_cert_pem: Option<String>,
+    /// Revalidate the last response with `If-None-Match`/`If-Modified-Since`, and reuse its body
+    /// when the server answers `304 Not Modified`
+    #[serde(default)]
+    pub cache_responses: bool,
+    /// IDs of requests that must be sent, and succeed, before this one when it's run as a chain.
+    /// Later requests can use the responses of earlier ones through response template functions.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
     pub description: String,
     pub headers: Vec<HttpRequestHeader>,
+    /// Most recent response sent for this request, so it can be shown without searching the
+    /// responses. Only moved by `set_last_response_id`, and cleared when that response is
+    /// deleted. It only means something in this database, so upserts, sync and export skip it.
+    pub last_response_id: Option<String>,
     #[serde(default = "default_http_method")]
     pub method: String,
     pub name: String,
+    /// Scratch notes for debugging, separate from the description, which is documentation
+    pub notes: String,
     pub sort_priority: f32,
+    /// Milliseconds to wait for this request to finish, instead of the workspace's timeout. 0
+    /// means no timeout, and negative values are invalid.
+    pub timeout_ms: Option<i32>,
+    /// Hostname to send with SNI instead of the URL's host, for connecting to an IP address while
+    /// presenting the name the server's certificate is for. The Host header is left alone.
+    pub tls_server_name: Option<String>,
+    /// Trailers sent after the body, like the ones gRPC uses. They're meant for HTTP/2. Over
+    /// HTTP/1.1 they go in a chunked body, which many servers ignore, and GET and HEAD requests
+    /// drop them along with the body.
+    #[serde(default)]
+    pub trailers: Vec<HttpRequestHeader>,
     pub url: String,
     pub url_parameters: Vec<HttpUrlParameter>,
 }
@@ -695,6 +1191,10 @@ impl UpsertModelInfo for HttpRequest {
         self.id.to_string()
     }
 
+    fn soft_deletes() -> bool {
+        true
+    }
+
     fn insert_values(
         self,
         source: &UpdateSource,
@@ -702,38 +1202,57 @@ impl UpsertModelInfo for HttpRequest {
         Ok(vec![
             (CreatedAt, upsert_date(source, self.created_at)),
             (UpdatedAt, upsert_date(source, self.updated_at)),
+            (DeletedAt, self.deleted_at.into()),
             (WorkspaceId, self.workspace_id.into()),
             (FolderId, self.folder_id.into()),
             (Name, self.name.trim().into()),
             (Description, self.description.into()),
+            (Notes, self.notes.into()),
             (Url, self.url.into()),
             (UrlParameters, serde_json::to_string(&self.url_parameters)?.into()),
             (Method, self.method.into()),
             (Body, serde_json::to_string(&self.body)?.into()),
             (BodyType, self.body_type.into()),
+            (CaCertPem, self.ca_cert_pem.into()),
+            (CacheResponses, self.cache_responses.into()),
+            (DependsOn, serde_json::to_string(&self.depends_on)?.into()),
             (Authentication, serde_json::to_string(&self.authentication)?.into()),
             (AuthenticationType, self.authentication_type.into()),
+            (AuthProfileId, self.auth_profile_id.into()),
             (Headers, serde_json::to_string(&self.headers)?.into()),
+            (LastResponseId, self.last_response_id.into()),
             (SortPriority, self.sort_priority.into()),
+            (TimeoutMs, self.timeout_ms.into()),
+            (TlsServerName, self.tls_server_name.into()),
+            (Trailers, serde_json::to_string(&self.trailers)?.into()),
         ])
     }
 
     fn update_columns() -> Vec<impl IntoIden> {
         vec![
             UpdatedAt,
+            DeletedAt,
             WorkspaceId,
             Name,
             Description,
+            Notes,
             FolderId,
             Method,
             Headers,
             Body,
             BodyType,
+            CaCertPem,
+            CacheResponses,
+            DependsOn,
             Authentication,
             AuthenticationType,
+            AuthProfileId,
             Url,
             UrlParameters,
             SortPriority,
+            TimeoutMs,
+            TlsServerName,
+            Trailers,
         ]
     }
 
@@ -742,6 +1261,8 @@ impl UpsertModelInfo for HttpRequest {
         let body: String = r.get("body")?;
         let authentication: String = r.get("authentication")?;
         let headers: String = r.get("headers")?;
+        let trailers: String = r.get("trailers")?;
+        let depends_on: String = r.get("depends_on")?;
         Ok(Self {
             id: r.get("id")?,
             model: r.get("model")?,
@@ -749,17 +1270,27 @@ impl UpsertModelInfo for HttpRequest {
             workspace_id: r.get("workspace_id")?,
             created_at: r.get("created_at")?,
             updated_at: r.get("updated_at")?,
+            deleted_at: r.get("deleted_at")?,
             url: r.get("url")?,
             url_parameters: serde_json::from_str(url_parameters.as_str()).unwrap_or_default(),
             method: r.get("method")?,
             body: serde_json::from_str(body.as_str()).unwrap_or_default(),
             body_type: r.get("body_type")?,
+            ca_cert_pem: r.get("ca_cert_pem")?,
+            cache_responses: r.get("cache_responses")?,
+            depends_on: serde_json::from_str(depends_on.as_str()).unwrap_or_default(),
             description: r.get("description")?,
+            notes: r.get("notes")?,
             authentication: serde_json::from_str(authentication.as_str()).unwrap_or_default(),
             authentication_type: r.get("authentication_type")?,
+            auth_profile_id: r.get("auth_profile_id")?,
             headers: serde_json::from_str(headers.as_str()).unwrap_or_default(),
+            last_response_id: r.get("last_response_id")?,
             folder_id: r.get("folder_id")?,
             name: r.get("name")?,
+            timeout_ms: r.get("timeout_ms")?,
+            tls_server_name: r.get("tls_server_name")?,
+            trailers: serde_json::from_str(trailers.as_str()).unwrap_or_default(),
         })
     }
 }
@@ -904,6 +1435,9 @@ pub struct WebsocketRequest {
     pub authentication_type: Option<String>,
     pub description: String,
     pub headers: Vec<HttpRequestHeader>,
+    /// Outbound messages allowed per second, queueing any beyond that. 0 means unlimited.
+    #[serde(default)]
+    pub max_messages_per_second: i32,
     pub message: String,
     pub name: String,
     pub sort_priority: f32,
@@ -942,6 +1476,7 @@ impl UpsertModelInfo for WebsocketRequest {
             (AuthenticationType, self.authentication_type.as_ref().map(|s| s.as_str()).into()),
             (Description, self.description.into()),
             (Headers, serde_json::to_string(&self.headers)?.into()),
+            (MaxMessagesPerSecond, self.max_messages_per_second.into()),
             (Message, self.message.into()),
             (Name, self.name.trim().into()),
             (SortPriority, self.sort_priority.into()),
@@ -959,6 +1494,7 @@ impl UpsertModelInfo for WebsocketRequest {
             WebsocketRequestIden::AuthenticationType,
             WebsocketRequestIden::Description,
             WebsocketRequestIden::Headers,
+            WebsocketRequestIden::MaxMessagesPerSecond,
             WebsocketRequestIden::Message,
             WebsocketRequestIden::Name,
             WebsocketRequestIden::SortPriority,
@@ -984,6 +1520,7 @@ impl UpsertModelInfo for WebsocketRequest {
             url: row.get("url")?,
             url_parameters: serde_json::from_str(url_parameters.as_str()).unwrap_or_default(),
             message: row.get("message")?,
+            max_messages_per_second: row.get("max_messages_per_second")?,
             description: row.get("description")?,
             authentication: serde_json::from_str(authentication.as_str()).unwrap_or_default(),
             authentication_type: row.get("authentication_type")?,
@@ -1005,6 +1542,7 @@ pub enum WebsocketEventType {
     Ping,
     Pong,
     Text,
+    Throttle,
 }
 
 impl Default for WebsocketEventType {
@@ -1103,6 +1641,14 @@ pub struct HttpResponseHeader {
     pub value: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseUrlParameter {
+    pub name: String,
+    pub value: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "snake_case")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -1118,6 +1664,21 @@ impl Default for HttpResponseState {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum HttpResponseErrorKind {
+    /// Failed before anything was sent, like a URL that didn't parse or a template that didn't
+    /// render
+    NotSent,
+    /// Failed while connecting or waiting for the response, like a refused connection
+    Connection,
+    /// Failed after the response started, while reading the body
+    Body,
+    /// Completed, but with a status the workspace treats as an error
+    Status,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -1131,19 +1692,53 @@ pub struct HttpResponse {
     pub workspace_id: String,
     pub request_id: String,
 
+    /// Known-good response that future responses are compared against. It is never pruned from
+    /// history, and there is at most one per request.
+    pub baseline: bool,
     pub body_path: Option<String>,
+    /// Start of the body (UTF-8 lossy), so small responses can be shown without reading the file
+    pub body_preview: Option<String>,
+    /// Seconds the response spent in a shared cache, like a CDN, from its `Age` header
+    pub cache_age: Option<i32>,
     pub content_length: Option<i32>,
+    /// Bytes received over the wire, before decoding
+    pub content_length_raw: Option<i32>,
+    /// Cookie jar the request was sent with, if any
+    pub cookie_jar_id: Option<String>,
+    pub correlation_id: Option<String>,
     pub elapsed: i32,
     pub elapsed_headers: i32,
     pub error: Option<String>,
+    pub error_kind: Option<HttpResponseErrorKind>,
+    /// Whether a shared cache, like a CDN or proxy, answered rather than the origin server, going
+    /// by headers like `Age`, `X-Cache` and `Warning`
+    pub from_cache: bool,
     pub headers: Vec<HttpResponseHeader>,
+    /// Names of the cookies sent from the cookie jar. Their values are in the `Cookie` request
+    /// header.
+    pub request_cookies: Vec<String>,
     pub request_headers: Vec<HttpResponseHeader>,
+    /// Rendered query parameters that were sent, after disabled and unnamed ones were dropped
+    pub request_url_parameters: Vec<HttpResponseUrlParameter>,
+    pub local_addr: Option<String>,
     pub remote_addr: Option<String>,
+    /// Every IP address the URL's host resolved to, of which `remote_addr` is the one connected
+    /// to. Empty when the host wasn't looked up, like for an IP address or through a proxy.
+    pub resolved_addrs: Vec<String>,
+    pub reused_connection: bool,
+    pub set_cookies: Vec<Cookie>,
+    /// Hostname sent with SNI on a TLS connection. None for plain HTTP and IP addresses.
+    pub sni_hostname: Option<String>,
     pub status: i32,
     pub status_reason: Option<String>,
     pub state: HttpResponseState,
+    /// Why the server's certificate failed validation, like it being expired or for another
+    /// host. Only set when certificates aren't validated, since the request fails otherwise.
+    pub tls_error: Option<String>,
     pub url: String,
     pub version: Option<String>,
+    /// Problems noticed with the request that didn't stop it from being sent
+    pub warnings: Vec<String>,
 }
 
 impl UpsertModelInfo for HttpResponse {
@@ -1173,38 +1768,72 @@ impl UpsertModelInfo for HttpResponse {
             (UpdatedAt, upsert_date(source, self.updated_at)),
             (RequestId, self.request_id.into()),
             (WorkspaceId, self.workspace_id.into()),
+            (Baseline, self.baseline.into()),
             (BodyPath, self.body_path.into()),
+            (BodyPreview, self.body_preview.into()),
+            (CacheAge, self.cache_age.into()),
             (ContentLength, self.content_length.into()),
+            (ContentLengthRaw, self.content_length_raw.into()),
+            (CookieJarId, self.cookie_jar_id.into()),
+            (CorrelationId, self.correlation_id.into()),
             (Elapsed, self.elapsed.into()),
             (ElapsedHeaders, self.elapsed_headers.into()),
             (Error, self.error.into()),
+            (ErrorKind, serde_json::to_value(&self.error_kind)?.as_str().into()),
+            (FromCache, self.from_cache.into()),
             (Headers, serde_json::to_string(&self.headers)?.into()),
+            (RequestCookies, serde_json::to_string(&self.request_cookies)?.into()),
             (RequestHeaders, serde_json::to_string(&self.request_headers)?.into()),
+            (RequestUrlParameters, serde_json::to_string(&self.request_url_parameters)?.into()),
+            (LocalAddr, self.local_addr.into()),
             (RemoteAddr, self.remote_addr.into()),
+            (ResolvedAddrs, serde_json::to_string(&self.resolved_addrs)?.into()),
+            (ReusedConnection, self.reused_connection.into()),
+            (SetCookies, serde_json::to_string(&self.set_cookies)?.into()),
+            (SniHostname, self.sni_hostname.into()),
             (State, serde_json::to_value(self.state)?.as_str().into()),
             (Status, self.status.into()),
             (StatusReason, self.status_reason.into()),
+            (TlsError, self.tls_error.into()),
             (Url, self.url.into()),
             (Version, self.version.into()),
+            (Warnings, serde_json::to_string(&self.warnings)?.into()),
         ])
     }
 
     fn update_columns() -> Vec<impl IntoIden> {
         vec![
             HttpResponseIden::UpdatedAt,
+            HttpResponseIden::Baseline,
             HttpResponseIden::BodyPath,
+            HttpResponseIden::BodyPreview,
+            HttpResponseIden::CacheAge,
             HttpResponseIden::ContentLength,
+            HttpResponseIden::ContentLengthRaw,
+            HttpResponseIden::CookieJarId,
+            HttpResponseIden::CorrelationId,
             HttpResponseIden::Elapsed,
             HttpResponseIden::ElapsedHeaders,
             HttpResponseIden::Error,
+            HttpResponseIden::ErrorKind,
+            HttpResponseIden::FromCache,
             HttpResponseIden::Headers,
+            HttpResponseIden::RequestCookies,
             HttpResponseIden::RequestHeaders,
+            HttpResponseIden::RequestUrlParameters,
+            HttpResponseIden::LocalAddr,
             HttpResponseIden::RemoteAddr,
+            HttpResponseIden::ResolvedAddrs,
+            HttpResponseIden::ReusedConnection,
+            HttpResponseIden::SetCookies,
+            HttpResponseIden::SniHostname,
             HttpResponseIden::State,
             HttpResponseIden::Status,
             HttpResponseIden::StatusReason,
+            HttpResponseIden::TlsError,
             HttpResponseIden::Url,
             HttpResponseIden::Version,
+            HttpResponseIden::Warnings,
         ]
     }
 
@@ -1213,8 +1842,15 @@ impl UpsertModelInfo for HttpResponse {
         Self: Sized,
     {
         let headers: String = r.get("headers")?;
+        let request_cookies: String = r.get("request_cookies")?;
         let request_headers: String = r.get("request_headers")?;
+        let request_url_parameters: String = r.get("request_url_parameters")?;
+        let resolved_addrs: String = r.get("resolved_addrs")?;
+        let set_cookies: String = r.get("set_cookies")?;
+        let warnings: String = r.get("warnings")?;
         let state: String = r.get("state")?;
+        let error_kind: Option<String> = r.get("error_kind")?;
+        let error_kind = error_kind.and_then(|k| serde_json::from_str(&format!(r#""{k}""#)).ok());
         Ok(Self {
             id: r.get("id")?,
             model: r.get("model")?,
@@ -1222,23 +1858,53 @@ impl UpsertModelInfo for HttpResponse {
             request_id: r.get("request_id")?,
             created_at: r.get("created_at")?,
             updated_at: r.get("updated_at")?,
+            baseline: r.get("baseline")?,
             error: r.get("error")?,
+            error_kind,
+            from_cache: r.get("from_cache")?,
             url: r.get("url")?,
             content_length: r.get("content_length")?,
+            content_length_raw: r.get("content_length_raw")?,
+            cookie_jar_id: r.get("cookie_jar_id")?,
+            correlation_id: r.get("correlation_id")?,
             version: r.get("version")?,
             elapsed: r.get("elapsed")?,
             elapsed_headers: r.get("elapsed_headers")?,
+            local_addr: r.get("local_addr")?,
             remote_addr: r.get("remote_addr")?,
+            resolved_addrs: serde_json::from_str(resolved_addrs.as_str()).unwrap_or_default(),
+            reused_connection: r.get("reused_connection")?,
+            sni_hostname: r.get("sni_hostname")?,
             status: r.get("status")?,
             status_reason: r.get("status_reason")?,
             state: serde_json::from_str(format!(r#""{state}""#).as_str()).unwrap(),
+            tls_error: r.get("tls_error")?,
             body_path: r.get("body_path")?,
+            body_preview: r.get("body_preview")?,
+            cache_age: r.get("cache_age")?,
             headers: serde_json::from_str(headers.as_str()).unwrap_or_default(),
+            request_cookies: serde_json::from_str(request_cookies.as_str()).unwrap_or_default(),
             request_headers: serde_json::from_str(request_headers.as_str()).unwrap_or_default(),
+            request_url_parameters: serde_json::from_str(request_url_parameters.as_str())
+                .unwrap_or_default(),
+            set_cookies: serde_json::from_str(set_cookies.as_str()).unwrap_or_default(),
+            warnings: serde_json::from_str(warnings.as_str()).unwrap_or_default(),
         })
     }
 }
 
+impl HttpResponse {
+    /// Values of every header with this name, compared case-insensitively, in the order they were
+    /// received. Headers like `Set-Cookie` and `Link` are commonly repeated.
+    pub fn header_all(&self, name: &str) -> Vec<&str> {
+        self.headers
+            .iter()
+            .filter(|h| h.name.eq_ignore_ascii_case(name))
+            .map(|h| h.value.as_str())
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -1850,6 +2516,14 @@ fn default_http_method() -> String {
     "GET".to_string()
 }
 
+fn default_pool_idle_timeout() -> i32 {
+    90_000
+}
+
+fn default_max_decoded_size() -> i32 {
+    1024 * 1024 * 1024
+}
+
 #[macro_export]
 macro_rules! define_any_model {
     ($($type:ident),* $(,)?) => {
@@ -1885,6 +2559,7 @@ macro_rules! define_any_model {
 }
 
 define_any_model! {
+    AuthProfile,
     CookieJar,
     Environment,
     Folder,
@@ -1914,6 +2589,7 @@ impl<'de> Deserialize<'de> for AnyModel {
         use serde_json::from_value as fv;
 
         let model = match model.get("model") {
+            Some(m) if m == "auth_profile" => AnyModel::AuthProfile(fv(value).unwrap()),
             Some(m) if m == "cookie_jar" => AnyModel::CookieJar(fv(value).unwrap()),
             Some(m) if m == "environment" => AnyModel::Environment(fv(value).unwrap()),
             Some(m) if m == "folder" => AnyModel::Folder(fv(value).unwrap()),
@@ -1952,6 +2628,7 @@ impl AnyModel {
         };
 
         match self.clone() {
+            AnyModel::AuthProfile(v) => v.name,
             AnyModel::CookieJar(v) => v.name,
             AnyModel::Environment(v) => v.name,
             AnyModel::Folder(v) => v.name,
@@ -1977,6 +2654,10 @@ pub trait UpsertModelInfo {
     fn from_row(row: &Row) -> rusqlite::Result<Self>
     where
         Self: Sized;
+    /// Whether deleting only sets the row's `deleted_at`, so it can be restored later
+    fn soft_deletes() -> bool {
+        false
+    }
 }
 
 // Generate the created_at or updated_at timestamps for an upsert operation, depending on the ID
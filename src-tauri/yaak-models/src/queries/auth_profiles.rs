@@ -0,0 +1,52 @@
+use crate::db_context::DbContext;
+use crate::error::Result;
+use crate::models::{AuthProfile, AuthProfileIden, HttpRequest, HttpRequestIden};
+use crate::util::UpdateSource;
+
+impl<'a> DbContext<'a> {
+    pub fn get_auth_profile(&self, id: &str) -> Result<AuthProfile> {
+        self.find_one(AuthProfileIden::Id, id)
+    }
+
+    pub fn list_auth_profiles(&self, workspace_id: &str) -> Result<Vec<AuthProfile>> {
+        self.find_many(AuthProfileIden::WorkspaceId, workspace_id, None)
+    }
+
+    pub fn delete_auth_profile(
+        &self,
+        auth_profile: &AuthProfile,
+        source: &UpdateSource,
+    ) -> Result<AuthProfile> {
+        // Requests using the profile go back to their own authentication
+        for m in
+            self.find_many::<HttpRequest>(HttpRequestIden::AuthProfileId, &auth_profile.id, None)?
+        {
+            self.upsert_http_request(
+                &HttpRequest {
+                    auth_profile_id: None,
+                    ..m
+                },
+                source,
+            )?;
+        }
+
+        self.delete(auth_profile, source)
+    }
+
+    pub fn delete_auth_profile_by_id(
+        &self,
+        id: &str,
+        source: &UpdateSource,
+    ) -> Result<AuthProfile> {
+        let auth_profile = self.get_auth_profile(id)?;
+        self.delete_auth_profile(&auth_profile, source)
+    }
+
+    pub fn upsert_auth_profile(
+        &self,
+        auth_profile: &AuthProfile,
+        source: &UpdateSource,
+    ) -> Result<AuthProfile> {
+        self.upsert(auth_profile, source)
+    }
+}
@@ -0,0 +1,328 @@
+use crate::db_context::DbContext;
+use crate::error::Error::RowNotFound;
+use crate::error::Result;
+use crate::models::{
+    AnyModel, Environment, EnvironmentIden, Folder, FolderIden, HttpRequest, HttpRequestIden,
+};
+use crate::util::UpdateSource;
+use chrono::{Duration, NaiveDateTime, Utc};
+use sea_query::{Alias, Expr};
+
+/// Days that deleted requests, folders and environments can be restored for before they're purged
+pub const DELETED_RETENTION_DAYS: i64 = 30;
+
+impl<'a> DbContext<'a> {
+    /// Requests, folders and environments of a workspace that were deleted and can still be
+    /// restored, most recently deleted first
+    pub fn list_deleted_models(&self, workspace_id: &str) -> Result<Vec<AnyModel>> {
+        let mut models: Vec<(Option<NaiveDateTime>, AnyModel)> = Vec::new();
+        let in_workspace = Expr::col(HttpRequestIden::WorkspaceId).eq(workspace_id);
+        for m in self.find_deleted::<HttpRequest>(in_workspace)? {
+            models.push((m.deleted_at, m.into()));
+        }
+        let in_workspace = Expr::col(FolderIden::WorkspaceId).eq(workspace_id);
+        for m in self.find_deleted::<Folder>(in_workspace)? {
+            models.push((m.deleted_at, m.into()));
+        }
+        let in_workspace = Expr::col(EnvironmentIden::WorkspaceId).eq(workspace_id);
+        for m in self.find_deleted::<Environment>(in_workspace)? {
+            models.push((m.deleted_at, m.into()));
+        }
+
+        let start = retention_start();
+        models.retain(|(deleted_at, _)| deleted_at.is_some_and(|d| d >= start));
+        models.sort_by(|a, b| b.0.cmp(&a.0));
+        Ok(models.into_iter().map(|(_, m)| m).collect())
+    }
+
+    /// Restore a deleted request, folder or environment. The folders it's in are restored too, so
+    /// it shows up again, as is everything that was in a restored folder or environment.
+    pub fn restore_model(&self, model_id: &str, source: &UpdateSource) -> Result<AnyModel> {
+        let m = self.find_deleted::<HttpRequest>(Expr::col(HttpRequestIden::Id).eq(model_id))?;
+        if let Some(m) = m.into_iter().next() {
+            self.restore_parent_folders(m.folder_id.as_deref(), source)?;
+            let m = HttpRequest {
+                deleted_at: None,
+                ..m
+            };
+            return Ok(self.upsert(&m, source)?.into());
+        }
+
+        let m = self.find_deleted::<Folder>(Expr::col(FolderIden::Id).eq(model_id))?;
+        if let Some(m) = m.into_iter().next() {
+            self.restore_parent_folders(m.folder_id.as_deref(), source)?;
+            return Ok(self.restore_folder_tree(m, source)?.into());
+        }
+
+        let m = self.find_deleted::<Environment>(Expr::col(EnvironmentIden::Id).eq(model_id))?;
+        if let Some(m) = m.into_iter().next() {
+            let children = Expr::col(EnvironmentIden::EnvironmentId).eq(m.id.as_str());
+            for child in self.find_deleted::<Environment>(children)? {
+                let child = Environment {
+                    deleted_at: None,
+                    ..child
+                };
+                self.upsert(&child, source)?;
+            }
+            let m = Environment {
+                deleted_at: None,
+                ..m
+            };
+            return Ok(self.upsert(&m, source)?.into());
+        }
+
+        Err(RowNotFound)
+    }
+
+    /// Permanently delete the requests, folders and environments that were deleted more than
+    /// [`DELETED_RETENTION_DAYS`] ago, along with the responses of purged requests. Returns how
+    /// many were purged.
+    pub fn purge_deleted_models(&self, source: &UpdateSource) -> Result<usize> {
+        let expired = || Expr::col(Alias::new("deleted_at")).lt(retention_start());
+        let mut count = 0;
+        for m in self.find_deleted::<HttpRequest>(expired())? {
+            for r in self.list_http_responses_for_request(&m.id, None)? {
+                self.delete_http_response(&r, source)?;
+            }
+            self.purge(&m)?;
+            count += 1;
+        }
+        for m in self.find_deleted::<Folder>(expired())? {
+            self.purge(&m)?;
+            count += 1;
+        }
+        for m in self.find_deleted::<Environment>(expired())? {
+            self.purge(&m)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Restore the deleted folders that a model is in, stopping at the first one that isn't
+    fn restore_parent_folders(&self, folder_id: Option<&str>, source: &UpdateSource) -> Result<()> {
+        let mut next_id = folder_id.map(|id| id.to_string());
+        while let Some(id) = next_id {
+            let folder = self.find_deleted::<Folder>(Expr::col(FolderIden::Id).eq(id))?;
+            next_id = match folder.into_iter().next() {
+                Some(f) => {
+                    let parent_id = f.folder_id.clone();
+                    self.upsert(
+                        &Folder {
+                            deleted_at: None,
+                            ..f
+                        },
+                        source,
+                    )?;
+                    parent_id
+                }
+                None => None,
+            };
+        }
+        Ok(())
+    }
+
+    fn restore_folder_tree(&self, folder: Folder, source: &UpdateSource) -> Result<Folder> {
+        let folder = self.upsert(
+            &Folder {
+                deleted_at: None,
+                ..folder
+            },
+            source,
+        )?;
+
+        let fid = folder.id.as_str();
+        for m in self.find_deleted::<HttpRequest>(Expr::col(HttpRequestIden::FolderId).eq(fid))? {
+            let m = HttpRequest {
+                deleted_at: None,
+                ..m
+            };
+            self.upsert(&m, source)?;
+        }
+        for m in self.find_deleted::<Folder>(Expr::col(FolderIden::FolderId).eq(fid))? {
+            self.restore_folder_tree(m, source)?;
+        }
+
+        Ok(folder)
+    }
+}
+
+fn retention_start() -> NaiveDateTime {
+    Utc::now().naive_utc() - Duration::days(DELETED_RETENTION_DAYS)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db_context::DbContext;
+    use crate::models::{Environment, Folder, HttpRequest, HttpResponse, Workspace};
+    use crate::queries::deleted::retention_start;
+    use crate::query_manager::QueryManager;
+    use crate::util::{UpdateSource, generate_id};
+    use chrono::Duration;
+    use std::fs;
+    use std::path::PathBuf;
+
+    const SOURCE: UpdateSource = UpdateSource::Background;
+
+    fn workspace(db: &DbContext) -> Workspace {
+        let w = Workspace {
+            name: "Workspace".to_string(),
+            ..Default::default()
+        };
+        db.upsert_workspace(&w, &SOURCE).unwrap()
+    }
+
+    fn request(db: &DbContext, w: &Workspace, name: &str, folder_id: Option<&str>) -> HttpRequest {
+        let r = HttpRequest {
+            workspace_id: w.id.clone(),
+            folder_id: folder_id.map(|id| id.to_string()),
+            name: name.to_string(),
+            ..Default::default()
+        };
+        db.upsert_http_request(&r, &SOURCE).unwrap()
+    }
+
+    fn folder(db: &DbContext, w: &Workspace, name: &str, folder_id: Option<&str>) -> Folder {
+        let f = Folder {
+            workspace_id: w.id.clone(),
+            folder_id: folder_id.map(|id| id.to_string()),
+            name: name.to_string(),
+            ..Default::default()
+        };
+        db.upsert_folder(&f, &SOURCE).unwrap()
+    }
+
+    /// Response to a request with a body file on disk
+    fn response(db: &DbContext, r: &HttpRequest) -> (HttpResponse, PathBuf) {
+        let body_path = std::env::temp_dir().join(format!("yaak-body-{}", generate_id()));
+        fs::write(&body_path, "body").unwrap();
+        let response = HttpResponse {
+            workspace_id: r.workspace_id.clone(),
+            request_id: r.id.clone(),
+            body_path: Some(body_path.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        (db.upsert_http_response(&response, &SOURCE).unwrap(), body_path)
+    }
+
+    fn deleted_names(db: &DbContext, w: &Workspace) -> Vec<String> {
+        let deleted = db.list_deleted_models(&w.id).unwrap();
+        let mut names: Vec<_> = deleted.iter().map(|m| m.resolved_name()).collect();
+        names.sort();
+        names
+    }
+
+    /// Make everything that was deleted look like it was deleted before the retention period
+    fn expire_deleted(db: &DbContext) {
+        let deleted_at = retention_start() - Duration::days(1);
+        for table in ["http_requests", "folders", "environments"] {
+            let sql = format!("UPDATE {table} SET deleted_at = ? WHERE deleted_at IS NOT NULL");
+            db.conn.execute(&sql, &[&deleted_at]).unwrap();
+        }
+    }
+
+    #[test]
+    fn restores_deleted_request() {
+        let (qm, _rx) = QueryManager::new_in_memory();
+        let db = qm.connect();
+        let w = workspace(&db);
+        let r = request(&db, &w, "Request", None);
+        let (res, body_path) = response(&db, &r);
+
+        db.delete_http_request(&r, &SOURCE).unwrap();
+        assert!(db.get_http_request(&r.id).is_err());
+        assert!(db.list_http_requests(&w.id).unwrap().is_empty());
+        assert_eq!(deleted_names(&db, &w), vec!["Request"]);
+
+        // Not purged yet, since it was only just deleted
+        assert_eq!(db.purge_deleted_models(&SOURCE).unwrap(), 0);
+
+        db.restore_model(&r.id, &SOURCE).unwrap();
+        assert_eq!(db.get_http_request(&r.id).unwrap().deleted_at, None);
+        assert!(deleted_names(&db, &w).is_empty());
+        assert_eq!(db.get_http_response(&res.id).unwrap().id, res.id);
+        assert!(body_path.exists());
+        fs::remove_file(body_path).unwrap();
+    }
+
+    #[test]
+    fn purges_request_with_its_responses() {
+        let (qm, _rx) = QueryManager::new_in_memory();
+        let db = qm.connect();
+        let w = workspace(&db);
+        let r = request(&db, &w, "Request", None);
+        let (res, body_path) = response(&db, &r);
+
+        db.delete_http_request(&r, &SOURCE).unwrap();
+        expire_deleted(&db);
+        assert!(deleted_names(&db, &w).is_empty());
+
+        assert_eq!(db.purge_deleted_models(&SOURCE).unwrap(), 1);
+        assert!(db.get_http_response(&res.id).is_err());
+        assert!(!body_path.exists());
+        assert!(db.restore_model(&r.id, &SOURCE).is_err());
+    }
+
+    #[test]
+    fn restores_deleted_folders() {
+        let (qm, _rx) = QueryManager::new_in_memory();
+        let db = qm.connect();
+        let w = workspace(&db);
+        let parent = folder(&db, &w, "Parent", None);
+        let child = folder(&db, &w, "Child", Some(&parent.id));
+        let r = request(&db, &w, "Request", Some(&child.id));
+
+        db.delete_folder(&parent, &SOURCE).unwrap();
+        assert!(db.list_folders(&w.id).unwrap().is_empty());
+        assert_eq!(deleted_names(&db, &w), vec!["Child", "Parent", "Request"]);
+
+        // Restoring the request brings back the folders it's in
+        db.restore_model(&r.id, &SOURCE).unwrap();
+        assert_eq!(db.list_folders(&w.id).unwrap().len(), 2);
+        assert!(deleted_names(&db, &w).is_empty());
+
+        // Restoring a folder brings back everything in it
+        db.delete_folder(&parent, &SOURCE).unwrap();
+        db.restore_model(&parent.id, &SOURCE).unwrap();
+        assert_eq!(db.list_folders(&w.id).unwrap().len(), 2);
+        assert_eq!(db.get_http_request(&r.id).unwrap().folder_id, Some(child.id.clone()));
+
+        db.delete_folder(&parent, &SOURCE).unwrap();
+        expire_deleted(&db);
+        assert_eq!(db.purge_deleted_models(&SOURCE).unwrap(), 3);
+        assert!(db.restore_model(&parent.id, &SOURCE).is_err());
+    }
+
+    #[test]
+    fn restores_deleted_environments() {
+        let (qm, _rx) = QueryManager::new_in_memory();
+        let db = qm.connect();
+        let w = workspace(&db);
+        let base = db.get_base_environment(&w.id).unwrap();
+        let child = Environment {
+            workspace_id: w.id.clone(),
+            environment_id: Some(base.id.clone()),
+            name: "Production".to_string(),
+            ..Default::default()
+        };
+        let child = db.upsert_environment(&child, &SOURCE).unwrap();
+
+        db.delete_environment(&child, &SOURCE).unwrap();
+        assert!(db.get_environment(&child.id).is_err());
+        assert_eq!(deleted_names(&db, &w), vec!["Production"]);
+
+        db.restore_model(&child.id, &SOURCE).unwrap();
+        assert_eq!(db.get_environment(&child.id).unwrap().deleted_at, None);
+
+        // Restoring an environment brings back the ones under it
+        db.delete_environment(&base, &SOURCE).unwrap();
+        assert_eq!(deleted_names(&db, &w), vec!["Global Variables", "Production"]);
+        db.restore_model(&base.id, &SOURCE).unwrap();
+        assert!(db.get_environment(&child.id).is_ok());
+
+        db.delete_environment(&child, &SOURCE).unwrap();
+        expire_deleted(&db);
+        assert_eq!(db.purge_deleted_models(&SOURCE).unwrap(), 1);
+        assert!(db.restore_model(&child.id, &SOURCE).is_err());
+        assert!(db.get_environment(&base.id).is_ok());
+    }
+}
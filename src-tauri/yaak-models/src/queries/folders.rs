@@ -2,10 +2,11 @@ use crate::connection_or_tx::ConnectionOrTx;
 use crate::db_context::DbContext;
 use crate::error::Result;
 use crate::models::{
-    Folder, FolderIden, GrpcRequest, GrpcRequestIden, HttpRequest, HttpRequestIden,
-    WebsocketRequest, WebsocketRequestIden,
+    Folder, FolderIden, GrpcRequest, GrpcRequestIden, HttpRequest, HttpRequestHeader,
+    HttpRequestIden, WebsocketRequest, WebsocketRequestIden,
 };
-use crate::util::UpdateSource;
+use crate::queries::http_requests::copy_http_request;
+use crate::util::{UpdateSource, copy_name};
 
 impl<'a> DbContext<'a> {
     pub fn get_folder(&self, id: &str) -> Result<Folder> {
@@ -16,6 +17,26 @@ impl<'a> DbContext<'a> {
         self.find_many(FolderIden::WorkspaceId, workspace_id, None)
     }
 
+    /// Default headers of a folder and the folders above it, outermost first, so that a header
+    /// from a sub-folder comes after (and overrides) the same header from its parent
+    pub fn folder_default_headers(
+        &self,
+        folder_id: Option<&str>,
+    ) -> Result<Vec<HttpRequestHeader>> {
+        let mut folders = Vec::new();
+        let mut next_id = folder_id.map(|id| id.to_string());
+        while let Some(id) = next_id {
+            // Stop at a cycle rather than walking it forever
+            if folders.iter().any(|f: &Folder| f.id == id) {
+                break;
+            }
+            let folder = self.get_folder(&id)?;
+            next_id = folder.folder_id.clone();
+            folders.push(folder);
+        }
+        Ok(folders.into_iter().rev().flat_map(|f| f.default_headers).collect())
+    }
+
     pub fn delete_folder(&self, folder: &Folder, source: &UpdateSource) -> Result<Folder> {
         match self.conn {
             ConnectionOrTx::Connection(_) => {}
@@ -52,13 +73,23 @@ impl<'a> DbContext<'a> {
         self.upsert(folder, source)
     }
 
+    /// Duplicate a folder along with everything in it. Only the top folder is renamed, since its
+    /// contents already live in a differently named folder.
     pub fn duplicate_folder(&self, src_folder: &Folder, source: &UpdateSource) -> Result<Folder> {
+        let folder = Folder {
+            name: copy_name(&src_folder.name),
+            sort_priority: src_folder.sort_priority + 0.001,
+            ..src_folder.clone()
+        };
+        self.copy_folder_tree(&folder, source)
+    }
+
+    fn copy_folder_tree(&self, src_folder: &Folder, source: &UpdateSource) -> Result<Folder> {
         let fid = &src_folder.id;
 
         let new_folder = self.upsert_folder(
             &Folder {
                 id: "".into(),
-                sort_priority: src_folder.sort_priority + 0.001,
                 ..src_folder.clone()
             },
             source,
@@ -67,9 +98,8 @@ impl<'a> DbContext<'a> {
         for m in self.find_many::<HttpRequest>(HttpRequestIden::FolderId, fid, None)? {
             self.upsert_http_request(
                 &HttpRequest {
-                    id: "".into(),
                     folder_id: Some(new_folder.id.clone()),
-                    ..m
+                    ..copy_http_request(&m)
                 },
                 source,
             )?;
@@ -99,7 +129,7 @@ impl<'a> DbContext<'a> {
 
         for m in self.find_many::<Folder>(FolderIden::FolderId, fid, None)? {
             // Recurse down
-            self.duplicate_folder(
+            self.copy_folder_tree(
                 &Folder {
                     folder_id: Some(new_folder.id.clone()),
                     ..m
@@ -1,7 +1,9 @@
 use crate::db_context::DbContext;
 use crate::error::Result;
 use crate::models::{HttpRequest, HttpRequestIden};
-use crate::util::UpdateSource;
+use crate::util::{UpdateSource, copy_name, generate_id};
+use sea_query::{Expr, Query, SimpleExpr, SqliteQueryBuilder};
+use sea_query_rusqlite::RusqliteBinder;
 
 impl<'a> DbContext<'a> {
     pub fn get_http_request(&self, id: &str) -> Result<HttpRequest> {
@@ -17,7 +19,7 @@ impl<'a> DbContext<'a> {
         m: &HttpRequest,
         source: &UpdateSource,
     ) -> Result<HttpRequest> {
-        self.delete_all_http_responses_for_request(m.id.as_str(), source)?;
+        // Responses are kept so they come back if the request is restored, and go when it's purged
         self.delete(m, source)
     }
 
@@ -35,9 +37,11 @@ impl<'a> DbContext<'a> {
         http_request: &HttpRequest,
         source: &UpdateSource,
     ) -> Result<HttpRequest> {
-        let mut http_request = http_request.clone();
-        http_request.id = "".to_string();
-        http_request.sort_priority = http_request.sort_priority + 0.001;
+        let http_request = HttpRequest {
+            name: copy_name(&http_request.name),
+            sort_priority: http_request.sort_priority + 0.001,
+            ..copy_http_request(http_request)
+        };
         self.upsert(&http_request, source)
     }
 
@@ -48,4 +52,43 @@ impl<'a> DbContext<'a> {
     ) -> Result<HttpRequest> {
         self.upsert(http_request, source)
     }
+
+    /// Point a request at its latest response. Only that column is written, so the request isn't
+    /// marked as updated, and no change event is sent, since the pointer isn't synced or exported.
+    pub fn set_last_response_id(&self, request_id: &str, response_id: &str) -> Result<()> {
+        let cond = Expr::col(HttpRequestIden::Id).eq(request_id);
+        self.update_last_response_id(cond, Some(response_id))
+    }
+
+    /// Stop any request pointing at a response that's been deleted
+    pub(crate) fn clear_last_response_id(&self, response_id: &str) -> Result<()> {
+        let cond = Expr::col(HttpRequestIden::LastResponseId).eq(response_id);
+        self.update_last_response_id(cond, None)
+    }
+
+    fn update_last_response_id(&self, cond: SimpleExpr, response_id: Option<&str>) -> Result<()> {
+        let (sql, params) = Query::update()
+            .table(HttpRequestIden::Table)
+            .value(HttpRequestIden::LastResponseId, response_id)
+            .cond_where(cond)
+            .build_rusqlite(SqliteQueryBuilder);
+        self.conn.execute(sql.as_str(), &*params.as_params())?;
+        Ok(())
+    }
+}
+
+/// Copy of a request that's ready to be inserted as a new one. Headers and URL parameters get new
+/// IDs too, so editing them in the copy can't be confused with the original, and it has no last
+/// response yet.
+pub(crate) fn copy_http_request(http_request: &HttpRequest) -> HttpRequest {
+    let mut http_request = http_request.clone();
+    http_request.id = "".to_string();
+    http_request.last_response_id = None;
+    for h in http_request.headers.iter_mut().filter(|h| h.id.is_some()) {
+        h.id = Some(generate_id());
+    }
+    for p in http_request.url_parameters.iter_mut().filter(|p| p.id.is_some()) {
+        p.id = Some(generate_id());
+    }
+    http_request
 }
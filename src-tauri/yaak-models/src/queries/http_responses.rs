@@ -1,6 +1,7 @@
 use crate::error::Result;
 use crate::models::{HttpResponse, HttpResponseIden, HttpResponseState};
 use crate::util::UpdateSource;
+use chrono::NaiveDateTime;
 use log::{debug, error};
 use sea_query::{Expr, Query, SqliteQueryBuilder};
 use sea_query_rusqlite::RusqliteBinder;
@@ -21,6 +22,11 @@ impl<'a> DbContext<'a> {
         self.find_many(HttpResponseIden::RequestId, request_id, limit)
     }
 
+    pub fn get_baseline_http_response(&self, request_id: &str) -> Result<Option<HttpResponse>> {
+        let responses = self.list_http_responses_for_request(request_id, None)?;
+        Ok(responses.into_iter().find(|r| r.baseline))
+    }
+
     pub fn list_http_responses(
         &self,
         workspace_id: &str,
@@ -36,7 +42,7 @@ impl<'a> DbContext<'a> {
     ) -> Result<()> {
         let responses = self.list_http_responses_for_request(request_id, None)?;
         for m in responses {
-            self.delete(&m, source)?;
+            self.delete_http_response(&m, source)?;
         }
         Ok(())
     }
@@ -49,11 +55,33 @@ impl<'a> DbContext<'a> {
         let responses =
             self.find_many::<HttpResponse>(HttpResponseIden::WorkspaceId, workspace_id, None)?;
         for m in responses {
-            self.delete(&m, source)?;
+            self.delete_http_response(&m, source)?;
         }
         Ok(())
     }
 
+    /// Delete the responses in a workspace that were created before `before`, along with their
+    /// body files. Baselines are kept, since they are never pruned. Returns the number of
+    /// responses deleted and the total size of the body files removed.
+    pub fn delete_http_responses_before(
+        &self,
+        workspace_id: &str,
+        before: NaiveDateTime,
+        source: &UpdateSource,
+    ) -> Result<(usize, u64)> {
+        let responses = self.list_http_responses(workspace_id, None)?;
+        let mut count = 0;
+        let mut bytes = 0;
+        for m in responses.iter().filter(|r| !r.baseline && r.created_at < before) {
+            if let Some(p) = &m.body_path {
+                bytes += fs::metadata(p).map(|m| m.len()).unwrap_or_default();
+            }
+            self.delete_http_response(m, source)?;
+            count += 1;
+        }
+        Ok((count, bytes))
+    }
+
     pub fn delete_http_response(
         &self,
         http_response: &HttpResponse,
@@ -66,7 +94,12 @@ impl<'a> DbContext<'a> {
             };
         }
 
-        Ok(self.delete(http_response, source)?)
+        let deleted = self.delete(http_response, source)?;
+
+        // Don't leave the request pointing at a response that's gone
+        self.clear_last_response_id(&http_response.id)?;
+
+        Ok(deleted)
     }
 
     pub fn upsert_http_response(
@@ -76,11 +109,24 @@ impl<'a> DbContext<'a> {
     ) -> Result<HttpResponse> {
         let responses = self.list_http_responses_for_request(&http_response.request_id, None)?;
 
-        for m in responses.iter().skip(MAX_HISTORY_ITEMS - 1) {
+        for m in responses.iter().filter(|r| !r.baseline).skip(MAX_HISTORY_ITEMS - 1) {
             debug!("Deleting old HTTP response {}", http_response.id);
             self.delete_http_response(&m, source)?;
         }
 
+        // Only one response per request can be the baseline, so replace the previous one
+        if http_response.baseline {
+            for m in responses.iter().filter(|r| r.baseline && r.id != http_response.id) {
+                self.upsert(
+                    &HttpResponse {
+                        baseline: false,
+                        ..m.clone()
+                    },
+                    source,
+                )?;
+            }
+        }
+
         self.upsert(http_response, source)
     }
 
@@ -108,3 +154,82 @@ impl<'a> DbContext<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::db_context::DbContext;
+    use crate::models::{HttpRequest, HttpResponse, Workspace};
+    use crate::query_manager::QueryManager;
+    use crate::util::{UpdateSource, generate_id};
+    use std::fs;
+    use std::path::PathBuf;
+
+    const SOURCE: UpdateSource = UpdateSource::Background;
+
+    fn request(db: &DbContext) -> HttpRequest {
+        let w = db.upsert_workspace(&Workspace::default(), &SOURCE).unwrap();
+        let r = HttpRequest {
+            workspace_id: w.id,
+            ..Default::default()
+        };
+        db.upsert_http_request(&r, &SOURCE).unwrap()
+    }
+
+    /// Response to a request with a body file on disk, which becomes the request's latest
+    fn response(db: &DbContext, r: &HttpRequest) -> (HttpResponse, PathBuf) {
+        let body_path = std::env::temp_dir().join(format!("yaak-body-{}", generate_id()));
+        fs::write(&body_path, "body").unwrap();
+        let response = HttpResponse {
+            workspace_id: r.workspace_id.clone(),
+            request_id: r.id.clone(),
+            body_path: Some(body_path.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let response = db.upsert_http_response(&response, &SOURCE).unwrap();
+        db.set_last_response_id(&r.id, &response.id).unwrap();
+        (response, body_path)
+    }
+
+    #[test]
+    fn moves_last_response_without_updating_request() {
+        let (qm, _rx) = QueryManager::new_in_memory();
+        let db = qm.connect();
+        let r = request(&db);
+        let (res, _) = response(&db, &r);
+
+        let updated = db.get_http_request(&r.id).unwrap();
+        assert_eq!(updated.last_response_id, Some(res.id.clone()));
+        assert_eq!(updated.updated_at, r.updated_at);
+
+        // Saving an edit made with an older copy of the request keeps the pointer
+        let edited = HttpRequest {
+            name: "Edited".to_string(),
+            ..r.clone()
+        };
+        db.upsert_http_request(&edited, &SOURCE).unwrap();
+        assert_eq!(db.get_http_request(&r.id).unwrap().last_response_id, Some(res.id.clone()));
+
+        db.delete_http_response(&res, &SOURCE).unwrap();
+        assert_eq!(db.get_http_request(&r.id).unwrap().last_response_id, None);
+    }
+
+    #[test]
+    fn bulk_deletes_clear_last_response() {
+        let (qm, _rx) = QueryManager::new_in_memory();
+        let db = qm.connect();
+        let r = request(&db);
+        let (_, first_body) = response(&db, &r);
+        let (_, last_body) = response(&db, &r);
+
+        db.delete_all_http_responses_for_request(&r.id, &SOURCE).unwrap();
+        assert_eq!(db.get_http_request(&r.id).unwrap().last_response_id, None);
+        assert!(!first_body.exists());
+        assert!(!last_body.exists());
+
+        let (_, body) = response(&db, &r);
+        db.delete_all_http_responses_for_workspace(&r.workspace_id, &SOURCE).unwrap();
+        assert_eq!(db.get_http_request(&r.id).unwrap().last_response_id, None);
+        assert!(db.list_http_responses_for_request(&r.id, None).unwrap().is_empty());
+        assert!(!body.exists());
+    }
+}
@@ -0,0 +1,172 @@
+use crate::db_context::DbContext;
+use crate::error::Result;
+use crate::models::{
+    AuthProfile, AuthProfileIden, CookieJar, CookieJarIden, Environment, EnvironmentIden, Folder,
+    FolderIden, GrpcConnection, GrpcConnectionIden, GrpcEvent, GrpcEventIden, GrpcRequest,
+    GrpcRequestIden, HttpRequest, HttpRequestIden, HttpResponse, HttpResponseIden,
+    WebsocketConnection, WebsocketConnectionIden, WebsocketEvent, WebsocketEventIden,
+    WebsocketRequest, WebsocketRequestIden, Workspace, WorkspaceIden, WorkspaceMeta,
+    WorkspaceMetaIden,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use ts_rs::TS;
+
+/// A reference from one row to another that doesn't exist
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct IntegrityIssue {
+    /// Model type of the row with the dangling reference, like `http_request`
+    pub model: String,
+    pub id: String,
+    /// Column holding the reference, like `folder_id`
+    pub field: String,
+    /// ID that was referenced but not found
+    pub missing_id: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct IntegrityReport {
+    pub workspace_id: String,
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl IntegrityReport {
+    fn check(&mut self, model: &str, id: &str, field: &str, value: &str, ids: &HashSet<String>) {
+        if !ids.contains(value) {
+            self.issues.push(IntegrityIssue {
+                model: model.to_string(),
+                id: id.to_string(),
+                field: field.to_string(),
+                missing_id: value.to_string(),
+            });
+        }
+    }
+}
+
+impl<'a> DbContext<'a> {
+    /// Find references between the rows of a workspace that point at rows that don't exist, like
+    /// requests in a deleted folder or responses to a deleted request. Rows are matched by
+    /// `workspace_id`, so rows left behind by a deleted workspace are reported too.
+    pub fn check_workspace_integrity(&self, workspace_id: &str) -> Result<IntegrityReport> {
+        let wid = workspace_id;
+        let auth_profiles: Vec<AuthProfile> =
+            self.find_many(AuthProfileIden::WorkspaceId, wid, None)?;
+        let cookie_jars: Vec<CookieJar> = self.find_many(CookieJarIden::WorkspaceId, wid, None)?;
+        let environments: Vec<Environment> =
+            self.find_many(EnvironmentIden::WorkspaceId, wid, None)?;
+        let folders: Vec<Folder> = self.find_many(FolderIden::WorkspaceId, wid, None)?;
+        let http_requests: Vec<HttpRequest> =
+            self.find_many(HttpRequestIden::WorkspaceId, wid, None)?;
+        let http_responses: Vec<HttpResponse> =
+            self.find_many(HttpResponseIden::WorkspaceId, wid, None)?;
+        let grpc_requests: Vec<GrpcRequest> =
+            self.find_many(GrpcRequestIden::WorkspaceId, wid, None)?;
+        let grpc_connections: Vec<GrpcConnection> =
+            self.find_many(GrpcConnectionIden::WorkspaceId, wid, None)?;
+        let grpc_events: Vec<GrpcEvent> = self.find_many(GrpcEventIden::WorkspaceId, wid, None)?;
+        let websocket_requests: Vec<WebsocketRequest> =
+            self.find_many(WebsocketRequestIden::WorkspaceId, wid, None)?;
+        let websocket_connections: Vec<WebsocketConnection> =
+            self.find_many(WebsocketConnectionIden::WorkspaceId, wid, None)?;
+        let websocket_events: Vec<WebsocketEvent> =
+            self.find_many(WebsocketEventIden::WorkspaceId, wid, None)?;
+        let workspace_metas: Vec<WorkspaceMeta> =
+            self.find_many(WorkspaceMetaIden::WorkspaceId, wid, None)?;
+
+        let workspace_ids = match self.find_optional::<Workspace>(WorkspaceIden::Id, wid) {
+            Some(w) => HashSet::from([w.id]),
+            None => HashSet::new(),
+        };
+        let auth_profile_ids = id_set(auth_profiles.iter().map(|m| &m.id));
+        let environment_ids = id_set(environments.iter().map(|m| &m.id));
+        let folder_ids = id_set(folders.iter().map(|m| &m.id));
+        let http_request_ids = id_set(http_requests.iter().map(|m| &m.id));
+        let grpc_request_ids = id_set(grpc_requests.iter().map(|m| &m.id));
+        let grpc_connection_ids = id_set(grpc_connections.iter().map(|m| &m.id));
+        let websocket_request_ids = id_set(websocket_requests.iter().map(|m| &m.id));
+        let websocket_connection_ids = id_set(websocket_connections.iter().map(|m| &m.id));
+
+        let mut r = IntegrityReport {
+            workspace_id: workspace_id.to_string(),
+            ..Default::default()
+        };
+
+        for m in &auth_profiles {
+            r.check(&m.model, &m.id, "workspace_id", &m.workspace_id, &workspace_ids);
+        }
+        for m in &cookie_jars {
+            r.check(&m.model, &m.id, "workspace_id", &m.workspace_id, &workspace_ids);
+        }
+        for m in &environments {
+            r.check(&m.model, &m.id, "workspace_id", &m.workspace_id, &workspace_ids);
+            if let Some(id) = &m.environment_id {
+                r.check(&m.model, &m.id, "environment_id", id, &environment_ids);
+            }
+        }
+        for m in &folders {
+            r.check(&m.model, &m.id, "workspace_id", &m.workspace_id, &workspace_ids);
+            if let Some(id) = &m.folder_id {
+                r.check(&m.model, &m.id, "folder_id", id, &folder_ids);
+            }
+        }
+        for m in &http_requests {
+            r.check(&m.model, &m.id, "workspace_id", &m.workspace_id, &workspace_ids);
+            if let Some(id) = &m.folder_id {
+                r.check(&m.model, &m.id, "folder_id", id, &folder_ids);
+            }
+            if let Some(id) = &m.auth_profile_id {
+                r.check(&m.model, &m.id, "auth_profile_id", id, &auth_profile_ids);
+            }
+        }
+        for m in &http_responses {
+            r.check(&m.model, &m.id, "workspace_id", &m.workspace_id, &workspace_ids);
+            r.check(&m.model, &m.id, "request_id", &m.request_id, &http_request_ids);
+        }
+        for m in &grpc_requests {
+            r.check(&m.model, &m.id, "workspace_id", &m.workspace_id, &workspace_ids);
+            if let Some(id) = &m.folder_id {
+                r.check(&m.model, &m.id, "folder_id", id, &folder_ids);
+            }
+        }
+        for m in &grpc_connections {
+            r.check(&m.model, &m.id, "workspace_id", &m.workspace_id, &workspace_ids);
+            r.check(&m.model, &m.id, "request_id", &m.request_id, &grpc_request_ids);
+        }
+        for m in &grpc_events {
+            r.check(&m.model, &m.id, "workspace_id", &m.workspace_id, &workspace_ids);
+            r.check(&m.model, &m.id, "request_id", &m.request_id, &grpc_request_ids);
+            r.check(&m.model, &m.id, "connection_id", &m.connection_id, &grpc_connection_ids);
+        }
+        for m in &websocket_requests {
+            r.check(&m.model, &m.id, "workspace_id", &m.workspace_id, &workspace_ids);
+            if let Some(id) = &m.folder_id {
+                r.check(&m.model, &m.id, "folder_id", id, &folder_ids);
+            }
+        }
+        for m in &websocket_connections {
+            r.check(&m.model, &m.id, "workspace_id", &m.workspace_id, &workspace_ids);
+            r.check(&m.model, &m.id, "request_id", &m.request_id, &websocket_request_ids);
+        }
+        for m in &websocket_events {
+            r.check(&m.model, &m.id, "workspace_id", &m.workspace_id, &workspace_ids);
+            r.check(&m.model, &m.id, "request_id", &m.request_id, &websocket_request_ids);
+            r.check(&m.model, &m.id, "connection_id", &m.connection_id, &websocket_connection_ids);
+        }
+        for m in &workspace_metas {
+            r.check(&m.model, &m.id, "workspace_id", &m.workspace_id, &workspace_ids);
+            if let Some(id) = &m.last_environment_id {
+                r.check(&m.model, &m.id, "last_environment_id", id, &environment_ids);
+            }
+        }
+
+        Ok(r)
+    }
+}
+
+fn id_set<'m>(ids: impl Iterator<Item = &'m String>) -> HashSet<String> {
+    ids.cloned().collect()
+}
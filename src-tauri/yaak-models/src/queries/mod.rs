@@ -1,5 +1,7 @@
+mod auth_profiles;
 mod batch;
 mod cookie_jars;
+mod deleted;
 mod environments;
 mod folders;
 mod grpc_connections;
@@ -7,9 +9,11 @@ mod grpc_events;
 mod grpc_requests;
 mod http_requests;
 mod http_responses;
+mod integrity;
 mod key_values;
 mod plugin_key_values;
 mod plugins;
+mod search;
 mod settings;
 mod sync_states;
 mod websocket_connections;
@@ -18,4 +22,8 @@ mod websocket_requests;
 mod workspace_metas;
 mod workspaces;
 
+pub use deleted::DELETED_RETENTION_DAYS;
+pub use integrity::{IntegrityIssue, IntegrityReport};
+pub use search::SearchResult;
+
 const MAX_HISTORY_ITEMS: usize = 20;
@@ -0,0 +1,102 @@
+use crate::db_context::DbContext;
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Request or folder whose name or URL matches a search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct SearchResult {
+    /// Model type of the match, like `http_request` or `folder`
+    pub model: String,
+    pub id: String,
+    pub folder_id: Option<String>,
+    pub name: String,
+    /// URL of a request. None for folders.
+    pub url: Option<String>,
+    /// How well the query matched, where higher is better
+    pub score: i32,
+}
+
+impl<'a> DbContext<'a> {
+    /// Find the folders and HTTP, gRPC and WebSocket requests of a workspace whose name or URL
+    /// fuzzy-matches a query, best matches first. Names count for more than URLs.
+    pub fn search_workspace(
+        &self,
+        workspace_id: &str,
+        query: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<SearchResult>> {
+        let mut candidates = Vec::new();
+        for m in self.list_folders(workspace_id)? {
+            candidates.push(("folder", m.id, m.folder_id, m.name, None));
+        }
+        for m in self.list_http_requests(workspace_id)? {
+            candidates.push(("http_request", m.id, m.folder_id, m.name, Some(m.url)));
+        }
+        for m in self.list_grpc_requests(workspace_id)? {
+            candidates.push(("grpc_request", m.id, m.folder_id, m.name, Some(m.url)));
+        }
+        for m in self.list_websocket_requests(workspace_id)? {
+            candidates.push(("websocket_request", m.id, m.folder_id, m.name, Some(m.url)));
+        }
+
+        let mut results = Vec::new();
+        for (model, id, folder_id, name, url) in candidates {
+            let name_score = fuzzy_score(query, &name).map(|s| s * 2);
+            let url_score = url.as_deref().and_then(|u| fuzzy_score(query, u));
+            let score = match name_score.max(url_score) {
+                Some(s) => s,
+                None => continue,
+            };
+            results.push(SearchResult {
+                model: model.to_string(),
+                id,
+                folder_id,
+                name,
+                url,
+                score,
+            });
+        }
+
+        results.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.name.cmp(&b.name)));
+        if let Some(limit) = limit {
+            results.truncate(limit);
+        }
+        Ok(results)
+    }
+}
+
+/// Score for `text` containing the characters of `query` in order, ignoring case and whitespace
+/// in the query, or None if it doesn't. Runs of consecutive characters, matches at the start of a
+/// word and matching the query as a whole score higher.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let query = query.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect::<String>();
+    if query.is_empty() {
+        return None;
+    }
+
+    let lower_text = text.to_lowercase();
+    let chars = lower_text.chars().collect::<Vec<_>>();
+    let mut score = 0;
+    let mut start = 0;
+    let mut previous = None;
+    for c in query.chars() {
+        let i = start + chars[start..].iter().position(|t| *t == c)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == i) {
+            score += 4;
+        }
+        if i == 0 || !chars[i - 1].is_alphanumeric() {
+            score += 2;
+        }
+        previous = Some(i);
+        start = i + 1;
+    }
+
+    if lower_text.contains(&query) {
+        score += 10;
+    }
+    Some(score)
+}
@@ -18,6 +18,8 @@ impl<'a> DbContext<'a> {
             updated_at: Default::default(),
 
             appearance: "system".to_string(),
+            dark_appearance_start: None,
+            dark_appearance_end: None,
             editor_font_size: 13,
             editor_keymap: EditorKeymap::Default,
             editor_soft_wrap: true,
@@ -33,6 +35,9 @@ impl<'a> DbContext<'a> {
     }
 
     pub fn upsert_settings(&self, settings: &Settings, source: &UpdateSource) -> Result<Settings> {
+        if let Some(proxy) = &settings.proxy {
+            proxy.validate()?;
+        }
         self.upsert(settings, source)
     }
 }
@@ -122,3 +122,29 @@ impl QueryManager {
         }
     }
 }
+
+#[cfg(test)]
+impl QueryManager {
+    /// Query manager over a new in-memory database with every migration applied. The receiver
+    /// has to be kept alive, since sending a model event panics once it's dropped.
+    pub(crate) fn new_in_memory() -> (Self, mpsc::Receiver<ModelPayload>) {
+        use std::fs;
+        use std::path::Path;
+
+        // Every connection to ":memory:" is its own database, so there can only be one
+        let pool = Pool::builder().max_size(1).build(SqliteConnectionManager::memory()).unwrap();
+
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../migrations");
+        let mut migrations: Vec<_> =
+            fs::read_dir(dir).unwrap().map(|e| e.unwrap().path()).collect();
+        migrations.sort();
+        let conn = pool.get().unwrap();
+        for p in migrations.iter().filter(|p| p.extension().is_some_and(|e| e == "sql")) {
+            conn.execute_batch(&fs::read_to_string(p).unwrap()).unwrap();
+        }
+        drop(conn);
+
+        let (tx, rx) = mpsc::channel(1000);
+        (QueryManager::new(pool, tx), rx)
+    }
+}
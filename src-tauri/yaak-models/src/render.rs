@@ -5,14 +5,9 @@ pub fn make_vars_hashmap(
     base_environment: &Environment,
     environment: Option<&Environment>,
 ) -> HashMap<String, String> {
-    let mut variables = HashMap::new();
-    variables = add_variable_to_map(variables, &base_environment.variables);
-
-    if let Some(e) = environment {
-        variables = add_variable_to_map(variables, &e.variables);
-    }
-
-    variables
+    let merged = environment.map(|e| base_environment.merge(e));
+    let environment = merged.as_ref().unwrap_or(base_environment);
+    add_variable_to_map(HashMap::new(), &environment.variables)
 }
 
 fn add_variable_to_map(
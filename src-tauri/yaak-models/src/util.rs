@@ -6,6 +6,7 @@ use chrono::{NaiveDateTime, Utc};
 use log::warn;
 use nanoid::nanoid;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use tauri::{AppHandle, Listener, Runtime, WebviewWindow};
 use ts_rs::TS;
 
@@ -141,6 +142,11 @@ pub async fn get_workspace_export_resources<R: Runtime>(
         data.resources.websocket_requests.append(&mut db.list_websocket_requests(workspace_id)?);
     }
 
+    // Which response is the latest only means something in this database
+    for r in data.resources.http_requests.iter_mut() {
+        r.last_response_id = None;
+    }
+
     // Nuke environments if we don't want them
     if !include_environments {
         data.resources.environments.clear();
@@ -173,3 +179,38 @@ pub fn maybe_gen_id_opt<M: UpsertModelInfo>(
         None => None,
     }
 }
+
+/// Name for a duplicated model. Unnamed models stay unnamed, so they keep falling back to
+/// whatever the UI shows in place of a name (like a request's URL).
+pub fn copy_name(name: &str) -> String {
+    match name.trim() {
+        "" => String::new(),
+        n => format!("{n} (copy)"),
+    }
+}
+
+/// Apply a JSON merge patch (RFC 7386) to a document. Objects are merged key by key, a `null`
+/// removes the key, and anything else (including arrays) replaces the target outright.
+pub fn json_merge_patch(target: &Value, patch: &Value) -> Value {
+    let patch = match patch {
+        Value::Object(p) => p,
+        _ => return patch.clone(),
+    };
+
+    let mut result = match target {
+        Value::Object(t) => t.clone(),
+        _ => Map::new(),
+    };
+    for (key, value) in patch {
+        match value {
+            Value::Null => {
+                result.remove(key);
+            }
+            _ => {
+                let merged = json_merge_patch(result.get(key).unwrap_or(&Value::Null), value);
+                result.insert(key.clone(), merged);
+            }
+        }
+    }
+    Value::Object(result)
+}
@@ -1,27 +1,263 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type Environment = { model: "environment", id: string, workspaceId: string, environmentId: string | null, createdAt: string, updatedAt: string, name: string, variables: Array<EnvironmentVariable>, };
+export type Cookie = { raw_cookie: string, domain: CookieDomain, expires: CookieExpires, path: [string, boolean], };
 
-export type EnvironmentVariable = { enabled?: boolean, name: string, value: string, id?: string, };
+export type CookieDomain = { "HostOnly": string } | { "Suffix": string } | "NotPresent" | "Empty";
 
-export type Folder = { model: "folder", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, name: string, description: string, sortPriority: number, };
+export type CookieExpires = { "AtUtc": string } | "SessionEnd";
+
+export type Environment = { model: "environment", id: string, workspaceId: string, environmentId: string | null, createdAt: string, updatedAt: string, 
+/**
+ * When the environment was deleted. Deleted environments are hidden, and can be restored until
+ * they're purged.
+ */
+deletedAt: string | null, name: string, 
+/**
+ * URL that request URLs starting with `/` are relative to, like `https://api.example.com`.
+ * Environments without one use the base environment's.
+ */
+baseUrl: string | null, variables: Array<EnvironmentVariable>, };
+
+export type EnvironmentVariable = { enabled?: boolean, name: string, value: string, id?: string, 
+/**
+ * Secret values are left as placeholders when rendering for export
+ */
+secret?: boolean, };
+
+export type Folder = { model: "folder", id: string, createdAt: string, updatedAt: string, 
+/**
+ * When the folder was deleted. Deleted folders are hidden, and can be restored until they're
+ * purged.
+ */
+deletedAt: string | null, workspaceId: string, folderId: string | null, name: string, description: string, sortPriority: number, 
+/**
+ * Headers sent with every request in the folder and its sub-folders, unless a sub-folder or
+ * the request sets the same header
+ */
+defaultHeaders: Array<HttpRequestHeader>, };
 
 export type GrpcMetadataEntry = { enabled?: boolean, name: string, value: string, id?: string, };
 
 export type GrpcRequest = { model: "grpc_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authenticationType: string | null, authentication: Record<string, any>, description: string, message: string, metadata: Array<GrpcMetadataEntry>, method: string | null, name: string, service: string | null, sortPriority: number, url: string, };
 
-export type HttpRequest = { model: "http_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, body: Record<string, any>, bodyType: string | null, description: string, headers: Array<HttpRequestHeader>, method: string, name: string, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, };
+export type HttpRequest = { model: "http_request", id: string, createdAt: string, updatedAt: string, 
+/**
+ * When the request was deleted. Deleted requests are hidden, and can be restored until they're
+ * purged.
+ */
+deletedAt: string | null, workspaceId: string, folderId: string | null, 
+/**
+ * Shared auth profile to authenticate with, which takes the place of the request's own
+ * authentication
+ */
+authProfileId: string | null, authentication: Record<string, any>, authenticationType: string | null, body: Record<string, any>, bodyType: string | null, 
+/**
+ * PEM text of CA certificates to trust for this request, in addition to the system roots
+ */
+caCertPem: string | null, 
+/**
+ * Revalidate the last response with `If-None-Match`/`If-Modified-Since`, and reuse its body
+ * when the server answers `304 Not Modified`
+ */
+cacheResponses: boolean, 
+/**
+ * IDs of requests that must be sent, and succeed, before this one when it's run as a chain.
+ * Later requests can use the responses of earlier ones through response template functions.
+ */
+dependsOn: Array<string>, description: string, headers: Array<HttpRequestHeader>, 
+/**
+ * Most recent response sent for this request, so it can be shown without searching the
+ * responses. Only moved by `set_last_response_id`, and cleared when that response is
+ * deleted. It only means something in this database, so upserts, sync and export skip it.
+ */
+lastResponseId: string | null, method: string, name: string, 
+/**
+ * Scratch notes for debugging, separate from the description, which is documentation
+ */
+notes: string, sortPriority: number, 
+/**
+ * Milliseconds to wait for this request to finish, instead of the workspace's timeout. 0
+ * means no timeout, and negative values are invalid.
+ */
+timeoutMs: number | null, 
+/**
+ * Hostname to send with SNI instead of the URL's host, for connecting to an IP address while
+ * presenting the name the server's certificate is for. The Host header is left alone.
+ */
+tlsServerName: string | null, 
+/**
+ * Trailers sent after the body, like the ones gRPC uses. They're meant for HTTP/2. Over
+ * HTTP/1.1 they go in a chunked body, which many servers ignore, and GET and HEAD requests
+ * drop them along with the body.
+ */
+trailers: Array<HttpRequestHeader>, url: string, urlParameters: Array<HttpUrlParameter>, };
+
+export type HttpRequestHeader = { enabled?: boolean, name: string, value: string, id?: string, 
+/**
+ * IDs of the only environments the header is sent in. Empty sends it in all of them.
+ */
+environments?: Array<string>, };
 
-export type HttpRequestHeader = { enabled?: boolean, name: string, value: string, id?: string, };
+export type HttpResponse = { model: "http_response", id: string, createdAt: string, updatedAt: string, workspaceId: string, requestId: string, 
 
-export type HttpResponse = { model: "http_response", id: string, createdAt: string, updatedAt: string, workspaceId: string, requestId: string, bodyPath: string | null, contentLength: number | null, elapsed: number, elapsedHeaders: number, error: string | null, headers: Array<HttpResponseHeader>, remoteAddr: string | null, status: number, statusReason: string | null, state: HttpResponseState, url: string, version: string | null, };
+/**
+ * Known-good response that future responses are compared against. It is never pruned from
+ * history, and there is at most one per request.
+ */
+baseline: boolean, bodyPath: string | null, 
+/**
+ * Start of the body (UTF-8 lossy), so small responses can be shown without reading the file
+ */
+bodyPreview: string | null, 
+/**
+ * Seconds the response spent in a shared cache, like a CDN, from its `Age` header
+ */
+cacheAge: number | null, contentLength: number | null, 
+/**
+ * Bytes received over the wire, before decoding
+ */
+contentLengthRaw: number | null, 
+/**
+ * Cookie jar the request was sent with, if any
+ */
+cookieJarId: string | null, correlationId: string | null, elapsed: number, elapsedHeaders: number, error: string | null, errorKind: HttpResponseErrorKind | null, 
+/**
+ * Whether a shared cache, like a CDN or proxy, answered rather than the origin server, going
+ * by headers like `Age`, `X-Cache` and `Warning`
+ */
+fromCache: boolean, headers: Array<HttpResponseHeader>, 
+/**
+ * Names of the cookies sent from the cookie jar. Their values are in the `Cookie` request
+ * header.
+ */
+requestCookies: Array<string>, requestHeaders: Array<HttpResponseHeader>, 
+/**
+ * Rendered query parameters that were sent, after disabled and unnamed ones were dropped
+ */
+requestUrlParameters: Array<HttpResponseUrlParameter>, localAddr: string | null, remoteAddr: string | null, 
+/**
+ * Every IP address the URL's host resolved to, of which `remote_addr` is the one connected
+ * to. Empty when the host wasn't looked up, like for an IP address or through a proxy.
+ */
+resolvedAddrs: Array<string>, reusedConnection: boolean, setCookies: Array<Cookie>, 
+/**
+ * Hostname sent with SNI on a TLS connection. None for plain HTTP and IP addresses.
+ */
+sniHostname: string | null, status: number, statusReason: string | null, state: HttpResponseState, 
+/**
+ * Why the server's certificate failed validation, like it being expired or for another
+ * host. Only set when certificates aren't validated, since the request fails otherwise.
+ */
+tlsError: string | null, url: string, version: string | null, 
+/**
+ * Problems noticed with the request that didn't stop it from being sent
+ */
+warnings: Array<string>, };
+
+export type HttpResponseErrorKind = "not_sent" | "connection" | "body" | "status";
 
 export type HttpResponseHeader = { name: string, value: string, };
 
 export type HttpResponseState = "initialized" | "connected" | "closed";
 
-export type HttpUrlParameter = { enabled?: boolean, name: string, value: string, id?: string, };
+export type HttpResponseUrlParameter = { name: string, value: string, };
+
+export type HttpUrlParameter = { enabled?: boolean, name: string, value: string, id?: string, 
+/**
+ * Brackets to add to the name when it's sent. None sends the name as it's typed.
+ */
+notation?: UrlParameterNotation, };
+
+/**
+ * Bracket notation for grouping URL parameters into arrays and objects, which many frameworks
+ * parse query strings with
+ */
+export type UrlParameterNotation = "array" | "object";
 
-export type WebsocketRequest = { model: "websocket_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, description: string, headers: Array<HttpRequestHeader>, message: string, name: string, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, };
+export type WebsocketRequest = { model: "websocket_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, description: string, headers: Array<HttpRequestHeader>, 
+/**
+ * Outbound messages allowed per second, queueing any beyond that. 0 means unlimited.
+ */
+maxMessagesPerSecond: number, message: string, name: string, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, };
 
-export type Workspace = { model: "workspace", id: string, createdAt: string, updatedAt: string, name: string, description: string, settingValidateCertificates: boolean, settingFollowRedirects: boolean, settingRequestTimeout: number, };
+export type Workspace = { model: "workspace", id: string, createdAt: string, updatedAt: string, name: string, description: string, settingValidateCertificates: boolean, settingAcceptEncoding: string | null, 
+/**
+ * Add `; charset=utf-8` to the `Content-Type` of text and JSON bodies that don't name a
+ * charset. Bodies whose `Content-Type` names another charset are always sent in it.
+ */
+settingAddCharset: boolean, settingCaBundlePath: string | null, settingClientCertPath: string | null, settingClientKeyPath: string | null, settingDecompress: boolean, 
+/**
+ * Attempt HTTP/3 (QUIC) for HTTPS requests, falling back to HTTP/1.1 or HTTP/2 over TCP when
+ * the server doesn't answer it
+ */
+settingEnableHttp3: boolean, 
+/**
+ * Status codes and ranges, like `404, 500-599` or `5xx`, that mark a response as an error.
+ * The body is still stored.
+ */
+settingErrorStatusCodes: string | null, settingFollowRedirects: boolean, 
+/**
+ * Hostnames, separated by commas, whose certificates are accepted even when invalid, while
+ * every other host is still validated. `*.example.com` matches any subdomain.
+ */
+settingInsecureHosts: string | null, 
+/**
+ * Largest rendered body, in bytes, that will be sent. 0 means no limit.
+ */
+settingMaxBodySize: number, settingMaxConcurrency: number, 
+/**
+ * Largest size, in bytes, that a compressed response body may decode to before it's cut
+ * off, to guard against decompression bombs. 0 means no limit.
+ */
+settingMaxDecodedSize: number, 
+/**
+ * Most headers a response may have. 0 means no limit beyond the HTTP client's own, which is
+ * 100 headers over HTTP/1.1.
+ */
+settingMaxHeaderCount: number, 
+/**
+ * Largest total size, in bytes, of a response's header names and values. 0 means no limit
+ * beyond the HTTP client's own, which is about 400 KiB over HTTP/1.1 and 16 KiB over HTTP/2.
+ * Larger values raise the HTTP/2 limit, but the HTTP/1.1 one can't be raised.
+ */
+settingMaxHeaderSize: number, settingMaxRetries: number, settingMaxRetryWait: number, 
+/**
+ * How long, in milliseconds, an idle connection is kept for reuse. 0 closes connections as
+ * soon as they're idle.
+ */
+settingPoolIdleTimeout: number, 
+/**
+ * Follow redirects from HTTPS to plain HTTP, which are otherwise blocked
+ */
+settingRedirectAllowDowngrade: boolean, 
+/**
+ * Keep the method and body when following 301 and 302 redirects, instead of switching POST
+ * to GET like browsers do
+ */
+settingRedirectPreserveMethod: boolean, 
+/**
+ * Refuse to send a request whose rendered URL, query parameters or headers still contain
+ * template markers, like `{{ token }}` or `${[ token ]}`, rather than sending them as-is
+ */
+settingRejectUnresolvedTemplates: boolean, settingRequestIdHeader: string | null, 
+/**
+ * Milliseconds to wait for a request to finish. 0 means no timeout, and negative values are
+ * invalid, so requests fail before being sent.
+ */
+settingRequestTimeout: number, settingResponseDir: string | null, 
+/**
+ * Send an `Accept-Encoding` header, with the workspace's encodings or the ones that can be
+ * decoded. Off sends none unless the request sets its own, so servers aren't asked for
+ * compression at all.
+ */
+settingSendAcceptEncoding: boolean, 
+/**
+ * Send a `yaak` User-Agent and an `Accept` for any type unless a request sets its own. Off
+ * leaves them out, though the HTTP client always adds the same `Accept` if there's none.
+ */
+settingSendDefaultHeaders: boolean, 
+/**
+ * Send HTTP/1 header names in Title-Case (eg. `X-Api-Key`) instead of lowercase. The exact
+ * casing typed can't be kept, and HTTP/2 always uses lowercase.
+ */
+settingTitleCaseHeaders: boolean, };
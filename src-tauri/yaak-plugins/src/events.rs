@@ -788,6 +788,8 @@ pub struct CallTemplateFunctionResponse {
 pub struct CallTemplateFunctionArgs {
     pub purpose: RenderPurpose,
     pub values: HashMap<String, String>,
+    /// When set, random output (eg. uuids) should be derived from this seed
+    pub seed: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -796,6 +798,8 @@ pub struct CallTemplateFunctionArgs {
 pub enum RenderPurpose {
     Send,
     Preview,
+    /// Rendering something that gets shared, like a cURL command, so secrets are left out
+    Export,
 }
 
 impl Default for RenderPurpose {
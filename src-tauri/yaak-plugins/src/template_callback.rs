@@ -1,6 +1,8 @@
 use crate::events::{RenderPurpose, WindowContext};
 use crate::manager::PluginManager;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
 use tauri::{AppHandle, Manager, Runtime};
 use yaak_templates::error::Result;
 use yaak_templates::TemplateCallback;
@@ -10,6 +12,8 @@ pub struct PluginTemplateCallback {
     plugin_manager: PluginManager,
     window_context: WindowContext,
     render_purpose: RenderPurpose,
+    seed: Option<u32>,
+    calls: Arc<AtomicU32>,
 }
 
 impl PluginTemplateCallback {
@@ -23,8 +27,18 @@ impl PluginTemplateCallback {
             plugin_manager: plugin_manager.to_owned(),
             window_context: window_context.to_owned(),
             render_purpose,
+            seed: None,
+            calls: Default::default(),
         }
     }
+
+    /// Pass a fixed seed to template functions, so random helpers (eg. uuid) produce the same
+    /// output on every render. Each call gets its own seed, derived from this one and the order
+    /// of the call, so multiple calls in one render still differ from each other.
+    pub fn with_seed(mut self, seed: Option<u32>) -> Self {
+        self.seed = seed;
+        self
+    }
 }
 
 impl TemplateCallback for PluginTemplateCallback {
@@ -33,6 +47,7 @@ impl TemplateCallback for PluginTemplateCallback {
         // Keep this here for a while because there's no easy way to migrate
         let fn_name = if fn_name == "Response" { "response" } else { fn_name };
 
+        let seed = self.seed.map(|s| s.wrapping_add(self.calls.fetch_add(1, Ordering::SeqCst)));
         let resp = self
             .plugin_manager
             .call_template_function(
@@ -40,6 +55,7 @@ impl TemplateCallback for PluginTemplateCallback {
                 fn_name,
                 args,
                 self.render_purpose.to_owned(),
+                seed,
             )
             .await?;
         Ok(resp)
@@ -116,6 +116,7 @@ impl TryFrom<AnyModel> for SyncModel {
             AnyModel::Workspace(m) => SyncModel::Workspace(m),
 
             // Non-sync models
+            AnyModel::AuthProfile(m) => return Err(UnknownModel(m.model)),
             AnyModel::CookieJar(m) => return Err(UnknownModel(m.model)),
             AnyModel::GrpcConnection(m) => return Err(UnknownModel(m.model)),
             AnyModel::GrpcEvent(m) => return Err(UnknownModel(m.model)),
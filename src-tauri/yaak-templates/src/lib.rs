@@ -2,6 +2,7 @@ pub mod format;
 pub mod parser;
 pub mod renderer;
 pub mod error;
+pub mod usage;
 
 pub use parser::*;
 pub use renderer::*;
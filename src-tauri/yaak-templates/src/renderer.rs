@@ -13,6 +13,9 @@ pub trait TemplateCallback {
         fn_name: &str,
         args: HashMap<String, String>,
     ) -> impl Future<Output = Result<String>> + Send;
+
+    /// Called with a variable's fully rendered value each time it's substituted into a template
+    fn on_variable(&self, _name: &str, _value: &str) {}
 }
 
 pub async fn render_json_value_raw<T: TemplateCallback>(
@@ -100,6 +103,7 @@ async fn render_value<T: TemplateCallback>(
         Val::Var { name } => match vars.get(name.as_str()) {
             Some(v) => {
                 let r = Box::pin(parse_and_render_at_depth(v, vars, cb, depth)).await?;
+                cb.on_variable(name.as_str(), r.as_str());
                 r.to_string()
             }
             None => return Err(VariableNotFound(name)),
@@ -332,6 +336,41 @@ mod parse_and_render_tests {
         );
         Ok(())
     }
+
+    #[tokio::test]
+    async fn render_reports_variables() -> Result<()> {
+        let template = "${[ foo ]} and ${[ bar ]}";
+        let mut vars = HashMap::new();
+        vars.insert("foo".to_string(), "foo: ${[ bar ]}".to_string());
+        vars.insert("bar".to_string(), "bar".to_string());
+
+        struct CB {
+            seen: std::sync::Mutex<Vec<(String, String)>>,
+        }
+        impl TemplateCallback for CB {
+            async fn run(&self, _fn_name: &str, _args: HashMap<String, String>) -> Result<String> {
+                todo!()
+            }
+
+            fn on_variable(&self, name: &str, value: &str) {
+                self.seen.lock().unwrap().push((name.to_string(), value.to_string()));
+            }
+        }
+
+        let cb = CB {
+            seen: Default::default(),
+        };
+        assert_eq!(parse_and_render(template, &vars, &cb).await?, "foo: bar and bar");
+        assert_eq!(
+            cb.seen.into_inner().unwrap(),
+            vec![
+                ("bar".to_string(), "bar".to_string()),
+                ("foo".to_string(), "foo: bar".to_string()),
+                ("bar".to_string(), "bar".to_string()),
+            ]
+        );
+        Ok(())
+    }
 }
 
 #[cfg(test)]
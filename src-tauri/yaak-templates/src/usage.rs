@@ -0,0 +1,76 @@
+use crate::{Parser, Token, Val};
+use std::collections::BTreeSet;
+
+/// Names of every variable a template references, including ones nested inside function
+/// arguments. Templates that fail to parse reference nothing.
+pub fn referenced_variables(template: &str) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    collect_template(template, &mut names);
+    names
+}
+
+fn collect_template(template: &str, names: &mut BTreeSet<String>) {
+    let tokens = match Parser::new(template).parse() {
+        Ok(t) => t,
+        Err(_) => return,
+    };
+
+    for t in tokens.tokens {
+        if let Token::Tag { val } = t {
+            collect_val(&val, names);
+        }
+    }
+}
+
+fn collect_val(val: &Val, names: &mut BTreeSet<String>) {
+    match val {
+        Val::Var { name } => {
+            names.insert(name.to_string());
+        }
+        // String args are rendered as templates too, so they may reference variables
+        Val::Str { text } => collect_template(text, names),
+        Val::Fn { args, .. } => {
+            for a in args {
+                collect_val(&a.value, names);
+            }
+        }
+        Val::Bool { .. } | Val::Null => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::usage::referenced_variables;
+    use std::collections::BTreeSet;
+
+    fn names(n: &[&str]) -> BTreeSet<String> {
+        n.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn no_variables() {
+        assert_eq!(referenced_variables(""), names(&[]));
+        assert_eq!(referenced_variables("https://example.com"), names(&[]));
+    }
+
+    #[test]
+    fn simple_variables() {
+        assert_eq!(
+            referenced_variables("${[ base_url ]}/users/${[ user_id ]}?q=${[ base_url ]}"),
+            names(&["base_url", "user_id"])
+        );
+    }
+
+    #[test]
+    fn function_args() {
+        assert_eq!(
+            referenced_variables("${[ sign(key=secret, alg='HS256', data=b64(v=token)) ]}"),
+            names(&["secret", "token"])
+        );
+    }
+
+    #[test]
+    fn template_in_string_arg() {
+        assert_eq!(referenced_variables("${[ upper(text='Hi ${[ name ]}') ]}"), names(&["name"]));
+    }
+}
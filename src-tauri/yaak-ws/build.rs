@@ -9,6 +9,7 @@ const COMMANDS: &[&str] = &[
     "list_connections",
     "list_events",
     "list_requests",
+    "resend",
     "send",
     "upsert_request",
 ];
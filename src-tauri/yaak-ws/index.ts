@@ -81,3 +81,7 @@ export function sendWebsocket({
     environmentId,
   });
 }
+
+export function resendWebsocketEvent({ eventId }: { eventId: string }) {
+  return invoke('plugin:yaak-ws|resend', { eventId }) as Promise<WebsocketEvent>;
+}
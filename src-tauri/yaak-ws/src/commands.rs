@@ -1,5 +1,6 @@
+use crate::error::Error::GenericError;
 use crate::error::Result;
-use crate::manager::WebsocketManager;
+use crate::manager::{SendOutcome, WebsocketManager};
 use crate::render::render_request;
 use log::{info, warn};
 use std::str::FromStr;
@@ -9,6 +10,7 @@ use tokio::sync::{mpsc, Mutex};
 use tokio_tungstenite::tungstenite::http::HeaderValue;
 use tokio_tungstenite::tungstenite::Message;
 use yaak_http::apply_path_placeholders;
+use yaak_http::query::query_param_name;
 use yaak_models::query_manager::QueryManagerExt;
 use yaak_models::models::{
     HttpResponseHeader, WebsocketConnection, WebsocketConnectionState, WebsocketEvent,
@@ -132,24 +134,80 @@ pub(crate) async fn send<R: Runtime>(
     .await?;
 
     let mut ws_manager = ws_manager.lock().await;
-    ws_manager.send(&connection.id, Message::Text(request.message.clone().into())).await?;
+    let outcome =
+        ws_manager.send(&connection.id, Message::Text(request.message.clone().into())).await?;
 
+    let event = WebsocketEvent {
+        connection_id: connection.id.clone(),
+        request_id: request.id.clone(),
+        workspace_id: connection.workspace_id.clone(),
+        is_server: false,
+        message_type: WebsocketEventType::Text,
+        message: request.message.into(),
+        ..Default::default()
+    };
     app_handle.db().upsert_websocket_event(
-        &WebsocketEvent {
-            connection_id: connection.id.clone(),
-            request_id: request.id.clone(),
-            workspace_id: connection.workspace_id.clone(),
-            is_server: false,
-            message_type: WebsocketEventType::Text,
-            message: request.message.into(),
-            ..Default::default()
-        },
+        &outcome_event(event, outcome),
         &UpdateSource::from_window(&window),
     )?;
 
     Ok(connection)
 }
 
+/// Send a message from a previous client event again, on that event's connection
+#[tauri::command]
+pub(crate) async fn resend<R: Runtime>(
+    event_id: &str,
+    app_handle: AppHandle<R>,
+    window: WebviewWindow<R>,
+    ws_manager: State<'_, Mutex<WebsocketManager>>,
+) -> Result<WebsocketEvent> {
+    let event = app_handle.db().get_websocket_event(event_id)?;
+    if event.is_server {
+        return Err(GenericError("Cannot resend a message received from the server".to_string()));
+    }
+
+    let message = match event.message_type {
+        WebsocketEventType::Text => {
+            Message::Text(String::from_utf8_lossy(&event.message).to_string().into())
+        }
+        WebsocketEventType::Binary => Message::Binary(event.message.clone().into()),
+        t => return Err(GenericError(format!("Cannot resend {t:?} event"))),
+    };
+
+    let mut ws_manager = ws_manager.lock().await;
+    let outcome = ws_manager.send(&event.connection_id, message).await?;
+
+    let event = WebsocketEvent {
+        id: "".to_string(),
+        created_at: Default::default(),
+        updated_at: Default::default(),
+        ..event
+    };
+    let event = app_handle.db().upsert_websocket_event(
+        &outcome_event(event, outcome),
+        &UpdateSource::from_window(&window),
+    )?;
+
+    Ok(event)
+}
+
+/// The event to record for a sent message. When the connection's outbound queue was full, the
+/// message was never sent, so a throttle event with the running counts is recorded instead.
+fn outcome_event(event: WebsocketEvent, outcome: SendOutcome) -> WebsocketEvent {
+    match outcome {
+        SendOutcome::Sent => event,
+        SendOutcome::Dropped { dropped, delayed } => WebsocketEvent {
+            message_type: WebsocketEventType::Throttle,
+            message: format!(
+                "Outbound queue full, message dropped ({dropped} dropped, {delayed} delayed)"
+            )
+            .into(),
+            ..event
+        },
+    }
+}
+
 #[tauri::command]
 pub(crate) async fn close<R: Runtime>(
     connection_id: &str,
@@ -207,6 +265,19 @@ pub(crate) async fn connect<R: Runtime>(
     .await?;
 
     let mut headers = HeaderMap::new();
+    for h in request.headers.iter() {
+        if !h.enabled || h.name.is_empty() || !h.in_environment(environment_id) {
+            continue;
+        }
+        match (HeaderName::from_str(&h.name), HeaderValue::from_str(&h.value)) {
+            (Ok(name), Ok(value)) => {
+                headers.append(name, value);
+            }
+            _ => warn!("Skipping invalid header {}", h.name),
+        }
+    }
+
+    // Auth headers replace any of the request's headers with the same name
     if let Some(auth_name) = request.authentication_type.clone() {
         let auth = request.authentication.clone();
         let plugin_req = CallHttpAuthenticationRequest {
@@ -262,11 +333,27 @@ pub(crate) async fn connect<R: Runtime>(
             if !p.enabled || p.name.is_empty() {
                 continue;
             }
-            query_pairs.append_pair(p.name.as_str(), p.value.as_str());
+            let name = match query_param_name(&p.name, p.notation) {
+                Ok(name) => name,
+                Err(e) => {
+                    return Ok(app_handle.db().upsert_websocket_connection(
+                        &WebsocketConnection {
+                            error: Some(e),
+                            state: WebsocketConnectionState::Closed,
+                            ..connection
+                        },
+                        &UpdateSource::from_window(&window),
+                    )?);
+                }
+            };
+            query_pairs.append_pair(name.as_str(), p.value.as_str());
         }
     }
 
-    let response = match ws_manager.connect(&connection.id, url.as_str(), headers, receive_tx).await
+    let max_messages_per_second = request.max_messages_per_second.max(0) as u32;
+    let response = match ws_manager
+        .connect(&connection.id, url.as_str(), headers, receive_tx, max_messages_per_second)
+        .await
     {
         Ok(r) => r,
         Err(e) => {
@@ -6,7 +6,7 @@ mod render;
 
 use crate::commands::{
     close, connect, delete_connection, delete_connections, delete_request, duplicate_request,
-    list_connections, list_events, list_requests, send, upsert_request,
+    list_connections, list_events, list_requests, resend, send, upsert_request,
 };
 use crate::manager::WebsocketManager;
 use tauri::plugin::{Builder, TauriPlugin};
@@ -25,6 +25,7 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             list_connections,
             list_events,
             list_requests,
+            resend,
             send,
             upsert_request,
         ])
@@ -5,23 +5,49 @@ use futures_util::{SinkExt, StreamExt};
 use log::{debug, warn};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 use tokio::net::TcpStream;
 use tokio::sync::{mpsc, Mutex};
+use tokio::time::Instant;
 use tokio_tungstenite::tungstenite::handshake::client::Response;
 use tokio_tungstenite::tungstenite::http::{HeaderMap, HeaderValue};
 use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
 
+/// Messages a throttled connection holds waiting to be sent before new ones are dropped
+const OUTBOUND_QUEUE_SIZE: usize = 100;
+
+pub enum SendOutcome {
+    /// Sent, or queued to be sent as soon as the connection's rate limit allows
+    Sent,
+    /// Dropped because the outbound queue was full. Counts are totals for the connection.
+    Dropped { dropped: u64, delayed: u64 },
+}
+
+#[derive(Default)]
+struct ThrottleStats {
+    dropped: AtomicU64,
+    delayed: AtomicU64,
+}
+
+struct Throttle {
+    queue: mpsc::Sender<Message>,
+    stats: Arc<ThrottleStats>,
+}
+
 #[derive(Clone)]
 pub struct WebsocketManager {
     connections:
         Arc<Mutex<HashMap<String, SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>>>>,
+    throttles: Arc<Mutex<HashMap<String, Throttle>>>,
 }
 
 impl WebsocketManager {
     pub fn new() -> Self {
         WebsocketManager {
             connections: Default::default(),
+            throttles: Default::default(),
         }
     }
 
@@ -31,8 +57,10 @@ impl WebsocketManager {
         url: &str,
         headers: HeaderMap<HeaderValue>,
         receive_tx: mpsc::Sender<Message>,
+        max_messages_per_second: u32,
     ) -> Result<Response> {
         let connections = self.connections.clone();
+        let throttles = self.throttles.clone();
         let connection_id = id.to_string();
         let tx = receive_tx.clone();
 
@@ -40,6 +68,10 @@ impl WebsocketManager {
         let (write, mut read) = stream.split();
 
         connections.lock().await.insert(id.to_string(), write);
+        if max_messages_per_second > 0 {
+            let throttle = self.spawn_throttle(id, max_messages_per_second);
+            throttles.lock().await.insert(id.to_string(), throttle);
+        }
 
         tauri::async_runtime::spawn(async move {
             while let Some(msg) = read.next().await {
@@ -53,19 +85,67 @@ impl WebsocketManager {
             }
             debug!("Connection {} closed", connection_id);
             connections.lock().await.remove(&connection_id);
+            throttles.lock().await.remove(&connection_id);
         });
         Ok(response)
     }
 
-    pub async fn send(&mut self, id: &str, msg: Message) -> Result<()> {
+    /// Spawn a task that drains a connection's outbound queue, spacing messages out so no more
+    /// than `max_messages_per_second` are sent. It stops once the queue's sender is dropped.
+    fn spawn_throttle(&self, id: &str, max_messages_per_second: u32) -> Throttle {
+        let connections = self.connections.clone();
+        let connection_id = id.to_string();
+        let stats = Arc::new(ThrottleStats::default());
+        let (queue_tx, mut queue_rx) = mpsc::channel::<Message>(OUTBOUND_QUEUE_SIZE);
+        let period = Duration::from_secs(1) / max_messages_per_second;
+
+        let task_stats = stats.clone();
+        tauri::async_runtime::spawn(async move {
+            let mut next_send = Instant::now();
+            while let Some(msg) = queue_rx.recv().await {
+                if next_send > Instant::now() {
+                    task_stats.delayed.fetch_add(1, Ordering::Relaxed);
+                    tokio::time::sleep_until(next_send).await;
+                }
+                next_send = Instant::now() + period;
+
+                let mut connections = connections.lock().await;
+                let connection = match connections.get_mut(&connection_id) {
+                    None => break,
+                    Some(c) => c,
+                };
+                if let Err(e) = connection.send(msg).await {
+                    warn!("Failed to send queued websocket message: {}", e);
+                }
+            }
+            debug!("Outbound queue for {} closed", connection_id);
+        });
+
+        Throttle {
+            queue: queue_tx,
+            stats,
+        }
+    }
+
+    pub async fn send(&mut self, id: &str, msg: Message) -> Result<SendOutcome> {
         debug!("Send websocket message {msg:?}");
+        if let Some(throttle) = self.throttles.lock().await.get(id) {
+            return Ok(match throttle.queue.try_send(msg) {
+                Ok(()) => SendOutcome::Sent,
+                Err(_) => SendOutcome::Dropped {
+                    dropped: throttle.stats.dropped.fetch_add(1, Ordering::Relaxed) + 1,
+                    delayed: throttle.stats.delayed.load(Ordering::Relaxed),
+                },
+            });
+        }
+
         let mut connections = self.connections.lock().await;
         let connection = match connections.get_mut(id) {
-            None => return Ok(()),
+            None => return Ok(SendOutcome::Sent),
             Some(c) => c,
         };
         connection.send(msg).await?;
-        Ok(())
+        Ok(SendOutcome::Sent)
     }
 
     pub async fn close(&mut self, id: &str) -> Result<()> {
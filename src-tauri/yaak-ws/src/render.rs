@@ -1,6 +1,6 @@
 use crate::error::Result;
 use std::collections::BTreeMap;
-use yaak_models::models::{Environment, HttpRequestHeader, WebsocketRequest};
+use yaak_models::models::{Environment, HttpRequestHeader, HttpUrlParameter, WebsocketRequest};
 use yaak_models::render::make_vars_hashmap;
 use yaak_templates::{parse_and_render, render_json_value_raw, TemplateCallback};
 
@@ -12,6 +12,17 @@ pub async fn render_request<T: TemplateCallback>(
 ) -> Result<WebsocketRequest> {
     let vars = &make_vars_hashmap(base_environment, environment);
 
+    let mut url_parameters = Vec::new();
+    for p in r.url_parameters.clone() {
+        url_parameters.push(HttpUrlParameter {
+            enabled: p.enabled,
+            name: parse_and_render(&p.name, vars, cb).await?,
+            value: parse_and_render(&p.value, vars, cb).await?,
+            id: p.id,
+            notation: p.notation,
+        })
+    }
+
     let mut headers = Vec::new();
     for p in r.headers.clone() {
         headers.push(HttpRequestHeader {
@@ -19,6 +30,7 @@ pub async fn render_request<T: TemplateCallback>(
             name: parse_and_render(&p.name, vars, cb).await?,
             value: parse_and_render(&p.value, vars, cb).await?,
             id: p.id,
+            environments: p.environments,
         })
     }
 
@@ -33,6 +45,7 @@ pub async fn render_request<T: TemplateCallback>(
 
     Ok(WebsocketRequest {
         url,
+        url_parameters,
         headers,
         authentication,
         message,
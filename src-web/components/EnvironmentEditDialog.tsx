@@ -17,6 +17,7 @@ import { Heading } from './core/Heading';
 import { Icon } from './core/Icon';
 import { IconButton } from './core/IconButton';
 import { InlineCode } from './core/InlineCode';
+import { Input } from './core/Input';
 import type { PairEditorProps } from './core/PairEditor';
 import { PairOrBulkEditor } from './core/PairOrBulkEditor';
 import { Separator } from './core/Separator';
@@ -177,9 +178,28 @@ const EnvironmentEditor = function ({
           />
         </Heading>
       </HStack>
+      <div className="pr-2">
+        <Input
+          size="sm"
+          label="Base URL"
+          labelPosition="left"
+          placeholder={
+            activeEnvironment.environmentId == null
+              ? 'https://api.example.com'
+              : 'Uses the Global Variables base URL'
+          }
+          defaultValue={activeEnvironment.baseUrl ?? ''}
+          forceUpdateKey={activeEnvironment.id}
+          autocompleteVariables
+          autocompleteFunctions
+          onChange={(v) => patchModel(activeEnvironment, { baseUrl: v.trim() || null })}
+          stateKey={`base_url.${activeEnvironment.id}`}
+        />
+      </div>
       <div className="h-full pr-2 pb-2">
         <PairOrBulkEditor
           allowMultilineValues
+          allowSecretValues
           preferenceName="environment"
           nameAutocomplete={nameAutocomplete}
           namePlaceholder="VAR_NAME"
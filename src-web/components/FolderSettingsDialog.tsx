@@ -1,7 +1,10 @@
 import { foldersAtom, patchModel } from '@yaakapp-internal/models';
 import { useAtomValue } from 'jotai/index';
+import { useEnvironmentsBreakdown } from '../hooks/useEnvironmentsBreakdown';
+import { Heading } from './core/Heading';
 import { Input } from './core/Input';
 import { VStack } from './core/Stacks';
+import { HeadersEditor } from './HeadersEditor';
 import { MarkdownEditor } from './MarkdownEditor';
 
 interface Props {
@@ -11,6 +14,7 @@ interface Props {
 export function FolderSettingsDialog({ folderId }: Props) {
   const folders = useAtomValue(foldersAtom);
   const folder = folders.find((f) => f.id === folderId);
+  const { subEnvironments } = useEnvironmentsBreakdown();
 
   if (folder == null) return null;
 
@@ -31,6 +35,21 @@ export function FolderSettingsDialog({ folderId }: Props) {
         stateKey={`description.${folder.id}`}
         onChange={(description) => patchModel(folder, { description })}
       />
+
+      <Heading level={2}>Default Headers</Heading>
+      <p className="text-text-subtle text-sm">
+        Sent with every request in this folder. Headers set by sub-folders and requests take
+        precedence.
+      </p>
+      <div className="h-[12rem]">
+        <HeadersEditor
+          environments={subEnvironments}
+          forceUpdateKey={folder.id}
+          headers={folder.defaultHeaders}
+          stateKey={`default_headers.${folder.id}`}
+          onChange={(defaultHeaders) => patchModel(folder, { defaultHeaders })}
+        />
+      </div>
     </VStack>
   );
 }
@@ -18,6 +18,8 @@ export function FormMultipartEditor({ request, forceUpdateKey, onChange }: Props
         value: p.file ?? p.value,
         contentType: p.contentType,
         isFile: !!p.file,
+        isDirectory: !!p.directory,
+        filter: p.filter,
         id: p.id,
       })),
     [request.body.form],
@@ -32,6 +34,8 @@ export function FormMultipartEditor({ request, forceUpdateKey, onChange }: Props
           contentType: p.contentType,
           file: p.isFile ? p.value : undefined,
           value: p.isFile ? undefined : p.value,
+          directory: p.isFile && p.isDirectory ? true : undefined,
+          filter: p.isFile && p.isDirectory ? p.filter : undefined,
           id: p.id,
         })),
       }),
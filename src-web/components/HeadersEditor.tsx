@@ -1,4 +1,4 @@
-import type { HttpRequestHeader } from '@yaakapp-internal/models';
+import type { Environment, HttpRequestHeader } from '@yaakapp-internal/models';
 import type { GenericCompletionOption } from '@yaakapp-internal/plugins';
 import { charsets } from '../lib/data/charsets';
 import { connections } from '../lib/data/connections';
@@ -10,17 +10,26 @@ import type { PairEditorProps } from './core/PairEditor';
 import { PairOrBulkEditor } from './core/PairOrBulkEditor';
 
 type Props = {
+  /** Environments that headers can be limited to */
+  environments?: Environment[];
   forceUpdateKey: string;
   headers: HttpRequestHeader[];
   stateKey: string;
   onChange: (headers: HttpRequestHeader[]) => void;
 };
 
-export function HeadersEditor({ stateKey, headers, onChange, forceUpdateKey }: Props) {
+export function HeadersEditor({
+  environments,
+  stateKey,
+  headers,
+  onChange,
+  forceUpdateKey,
+}: Props) {
   return (
     <PairOrBulkEditor
       preferenceName="headers"
       stateKey={stateKey}
+      environments={environments}
       valueAutocompleteFunctions
       valueAutocompleteVariables
       nameAutocompleteFunctions
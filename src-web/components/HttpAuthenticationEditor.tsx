@@ -1,4 +1,9 @@
-import type { GrpcRequest, HttpRequest, WebsocketRequest } from '@yaakapp-internal/models';
+import type {
+  AuthProfile,
+  GrpcRequest,
+  HttpRequest,
+  WebsocketRequest,
+} from '@yaakapp-internal/models';
 import { patchModel } from '@yaakapp-internal/models';
 import React, { useCallback } from 'react';
 import { useHttpAuthenticationConfig } from '../hooks/useHttpAuthenticationConfig';
@@ -13,22 +18,25 @@ import { EmptyStateText } from './EmptyStateText';
 
 interface Props {
   request: HttpRequest | GrpcRequest | WebsocketRequest;
+  /** Profile the request takes its authentication from, which edits are made to instead */
+  authProfile?: AuthProfile | null;
 }
 
-export function HttpAuthenticationEditor({ request }: Props) {
+export function HttpAuthenticationEditor({ request, authProfile }: Props) {
+  const auth = authProfile ?? request;
   const authConfig = useHttpAuthenticationConfig(
-    request.authenticationType,
-    request.authentication,
+    auth.authenticationType,
+    auth.authentication,
     request.id,
   );
 
   const handleChange = useCallback(
-    (authentication: Record<string, boolean>) => patchModel(request, { authentication }),
-    [request],
+    (authentication: Record<string, boolean>) => patchModel(auth, { authentication }),
+    [auth],
   );
 
   if (authConfig.data == null) {
-    return <EmptyStateText>No Authentication {request.authenticationType}</EmptyStateText>;
+    return <EmptyStateText>No Authentication {auth.authenticationType}</EmptyStateText>;
   }
 
   return (
@@ -36,9 +44,9 @@ export function HttpAuthenticationEditor({ request }: Props) {
       <HStack space={2} className="mb-2" alignItems="center">
         <Checkbox
           className="w-full"
-          checked={!request.authentication.disabled}
-          onChange={(disabled) => handleChange({ ...request.authentication, disabled: !disabled })}
-          title="Enabled"
+          checked={!auth.authentication.disabled}
+          onChange={(disabled) => handleChange({ ...auth.authentication, disabled: !disabled })}
+          title={authProfile ? `Enabled (shared by ${authProfile.name})` : 'Enabled'}
         />
         {authConfig.data.actions && authConfig.data.actions.length > 0 && (
           <Dropdown
@@ -55,12 +63,12 @@ export function HttpAuthenticationEditor({ request }: Props) {
         )}
       </HStack>
       <DynamicForm
-        disabled={request.authentication.disabled}
+        disabled={auth.authentication.disabled}
         autocompleteVariables
         autocompleteFunctions
-        stateKey={`auth.${request.id}.${request.authenticationType}`}
+        stateKey={`auth.${auth.id}.${auth.authenticationType}`}
         inputs={authConfig.data.args}
-        data={request.authentication}
+        data={auth.authentication}
         onChange={handleChange}
       />
     </div>
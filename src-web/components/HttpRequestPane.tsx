@@ -1,5 +1,5 @@
 import type { HttpRequest } from '@yaakapp-internal/models';
-import { patchModel } from '@yaakapp-internal/models';
+import { authProfilesAtom, createWorkspaceModel, patchModel } from '@yaakapp-internal/models';
 import type { GenericCompletionOption } from '@yaakapp-internal/plugins';
 import classNames from 'classnames';
 import { atom, useAtomValue } from 'jotai';
@@ -7,6 +7,7 @@ import type { CSSProperties } from 'react';
 import React, { useCallback, useMemo, useState } from 'react';
 import { activeRequestIdAtom } from '../hooks/useActiveRequestId';
 import { useCancelHttpResponse } from '../hooks/useCancelHttpResponse';
+import { useEnvironmentsBreakdown } from '../hooks/useEnvironmentsBreakdown';
 import { useHttpAuthenticationSummaries } from '../hooks/useHttpAuthentication';
 import { useImportCurl } from '../hooks/useImportCurl';
 import { useKeyValue } from '../hooks/useKeyValue';
@@ -29,13 +30,18 @@ import {
   BODY_TYPE_XML,
   getContentTypeFromHeaders,
 } from '../lib/model_util';
+import { deleteModelWithConfirm } from '../lib/deleteModelWithConfirm';
 import { prepareImportQuerystring } from '../lib/prepareImportQuerystring';
+import { showPrompt } from '../lib/prompt';
+import { renameModelWithPrompt } from '../lib/renameModelWithPrompt';
 import { resolvedModelName } from '../lib/resolvedModelName';
 import { showToast } from '../lib/toast';
 import { BinaryFileEditor } from './BinaryFileEditor';
+import { Checkbox } from './core/Checkbox';
 import { CountBadge } from './core/CountBadge';
 import { Editor } from './core/Editor/Editor';
 import type { GenericCompletionConfig } from './core/Editor/genericCompletion';
+import { Icon } from './core/Icon';
 import { InlineCode } from './core/InlineCode';
 import type { Pair } from './core/PairEditor';
 import { PlainInput } from './core/PlainInput';
@@ -61,9 +67,13 @@ interface Props {
 const TAB_BODY = 'body';
 const TAB_PARAMS = 'params';
 const TAB_HEADERS = 'headers';
+const TAB_TRAILERS = 'trailers';
 const TAB_AUTH = 'auth';
 const TAB_DESCRIPTION = 'description';
 
+/** Prefix for auth options that pick a shared auth profile instead of an authentication type */
+const AUTH_PROFILE_PREFIX = 'auth_profile::';
+
 const nonActiveRequestUrlsAtom = atom((get) => {
   const activeRequestId = get(activeRequestIdAtom);
   const requests = get(allRequestsAtom);
@@ -86,6 +96,9 @@ export function HttpRequestPane({ style, fullHeight, className, activeRequest }:
   const [{ urlKey }, { focusParamsTab, forceUrlRefresh, forceParamsRefresh }] = useRequestEditor();
   const contentType = getContentTypeFromHeaders(activeRequest.headers);
   const authentication = useHttpAuthenticationSummaries();
+  const authProfiles = useAtomValue(authProfilesAtom);
+  const authProfile = authProfiles.find((p) => p.id === activeRequest.authProfileId) ?? null;
+  const { subEnvironments } = useEnvironmentsBreakdown();
 
   const handleContentTypeChange = useCallback(
     async (contentType: string | null, patch: Partial<Omit<HttpRequest, 'headers'>> = {}) => {
@@ -219,28 +232,96 @@ export function HttpRequestPane({ style, fullHeight, className, activeRequest }:
         label: 'Headers',
         rightSlot: <CountBadge count={activeRequest.headers.filter((h) => h.name).length} />,
       },
+      {
+        value: TAB_TRAILERS,
+        label: 'Trailers',
+        rightSlot: <CountBadge count={activeRequest.trailers.filter((h) => h.name).length} />,
+      },
       {
         value: TAB_AUTH,
         label: 'Auth',
         options: {
-          value: activeRequest.authenticationType,
+          value:
+            authProfile != null
+              ? AUTH_PROFILE_PREFIX + authProfile.id
+              : activeRequest.authenticationType,
           items: [
             ...authentication.map((a) => ({
               label: a.label || 'UNKNOWN',
               shortLabel: a.shortLabel,
               value: a.name,
             })),
+            ...(authProfiles.length > 0
+              ? [
+                  { type: 'separator' as const, label: 'Auth Profiles' },
+                  ...authProfiles.map((p) => ({
+                    label: p.name,
+                    shortLabel: p.name,
+                    value: AUTH_PROFILE_PREFIX + p.id,
+                  })),
+                ]
+              : []),
             { type: 'separator' },
             { label: 'No Authentication', shortLabel: 'Auth', value: null },
           ],
-          onChange: async (authenticationType) => {
+          extraItems: [
+            {
+              label: 'Save as Auth Profile',
+              leftSlot: <Icon icon="plus" />,
+              hidden: authProfile != null || activeRequest.authenticationType == null,
+              onSelect: async () => {
+                const name = await showPrompt({
+                  id: 'new-auth-profile',
+                  title: 'New Auth Profile',
+                  label: 'Name',
+                  placeholder: 'My Token',
+                  confirmText: 'Create',
+                  description: 'Requests using the profile share its authentication',
+                });
+                if (name == null) return;
+                const authProfileId = await createWorkspaceModel({
+                  model: 'auth_profile',
+                  workspaceId: activeRequest.workspaceId,
+                  name,
+                  authenticationType: activeRequest.authenticationType,
+                  authentication: activeRequest.authentication,
+                });
+                await patchModel(activeRequest, { authProfileId });
+              },
+            },
+            {
+              label: 'Rename Auth Profile',
+              leftSlot: <Icon icon="pencil" />,
+              hidden: authProfile == null,
+              onSelect: () => renameModelWithPrompt(authProfile),
+            },
+            {
+              label: 'Delete Auth Profile',
+              leftSlot: <Icon icon="trash" />,
+              color: 'danger',
+              hidden: authProfile == null,
+              onSelect: () => deleteModelWithConfirm(authProfile),
+            },
+          ],
+          onChange: async (value) => {
+            if (value?.startsWith(AUTH_PROFILE_PREFIX)) {
+              const authProfileId = value.slice(AUTH_PROFILE_PREFIX.length);
+              await patchModel(activeRequest, { authProfileId });
+              return;
+            }
+
+            const authenticationType = value;
             let authentication: HttpRequest['authentication'] = activeRequest.authentication;
             if (activeRequest.authenticationType !== authenticationType) {
               authentication = {
                 // Reset auth if changing types
               };
             }
-            await patchModel(activeRequest, { authenticationType, authentication });
+            await patchModel(activeRequest, {
+              authenticationType,
+              authentication,
+              authProfileId: null,
+            });
           },
         },
       },
@@ -249,7 +330,15 @@ export function HttpRequestPane({ style, fullHeight, className, activeRequest }:
         label: 'Info',
       },
     ],
-    [activeRequest, authentication, handleContentTypeChange, numParams, urlParameterPairs.length],
+    [
+      activeRequest,
+      authProfile,
+      authProfiles,
+      authentication,
+      handleContentTypeChange,
+      numParams,
+      urlParameterPairs.length,
+    ],
   );
 
   const { mutate: sendRequest } = useSendAnyHttpRequest();
@@ -372,16 +461,26 @@ export function HttpRequestPane({ style, fullHeight, className, activeRequest }:
             tabListClassName="mt-2 !mb-1.5"
           >
             <TabContent value={TAB_AUTH}>
-              <HttpAuthenticationEditor request={activeRequest} />
+              <HttpAuthenticationEditor request={activeRequest} authProfile={authProfile} />
             </TabContent>
             <TabContent value={TAB_HEADERS}>
               <HeadersEditor
+                environments={subEnvironments}
                 forceUpdateKey={`${forceUpdateHeaderEditorKey}::${forceUpdateKey}`}
                 headers={activeRequest.headers}
                 stateKey={`headers.${activeRequest.id}`}
                 onChange={(headers) => patchModel(activeRequest, { headers })}
               />
             </TabContent>
+            <TabContent value={TAB_TRAILERS}>
+              <HeadersEditor
+                environments={subEnvironments}
+                forceUpdateKey={forceUpdateKey}
+                headers={activeRequest.trailers}
+                stateKey={`trailers.${activeRequest.id}`}
+                onChange={(trailers) => patchModel(activeRequest, { trailers })}
+              />
+            </TabContent>
             <TabContent value={TAB_PARAMS}>
               <UrlParametersEditor
                 stateKey={`params.${activeRequest.id}`}
@@ -459,7 +558,7 @@ export function HttpRequestPane({ style, fullHeight, className, activeRequest }:
               )}
             </TabContent>
             <TabContent value={TAB_DESCRIPTION}>
-              <div className="grid grid-rows-[auto_minmax(0,1fr)] h-full">
+              <div className="grid grid-rows-[auto_auto_auto_minmax(0,1fr)_auto] gap-y-2 h-full">
                 <PlainInput
                   label="Request Name"
                   hideLabel
@@ -470,6 +569,35 @@ export function HttpRequestPane({ style, fullHeight, className, activeRequest }:
                   placeholder={resolvedModelName(activeRequest)}
                   onChange={(name) => patchModel(activeRequest, { name })}
                 />
+                <Checkbox
+                  checked={activeRequest.cacheResponses}
+                  title="Cache responses and revalidate with If-None-Match / If-Modified-Since"
+                  onChange={(cacheResponses) => patchModel(activeRequest, { cacheResponses })}
+                />
+                <PlainInput
+                  size="sm"
+                  label="TLS Server Name (SNI)"
+                  labelPosition="left"
+                  labelClassName="w-[12rem]"
+                  placeholder="Host from the URL"
+                  forceUpdateKey={updateKey}
+                  defaultValue={activeRequest.tlsServerName ?? ''}
+                  onChange={(v) => patchModel(activeRequest, { tlsServerName: v.trim() || null })}
+                />
+                <PlainInput
+                  size="sm"
+                  type="number"
+                  label="Timeout (ms)"
+                  labelPosition="left"
+                  labelClassName="w-[12rem]"
+                  placeholder="Workspace timeout"
+                  forceUpdateKey={updateKey}
+                  defaultValue={activeRequest.timeoutMs == null ? '' : `${activeRequest.timeoutMs}`}
+                  validate={(v) => v.trim() === '' || parseInt(v) >= 0}
+                  onChange={(v) =>
+                    patchModel(activeRequest, { timeoutMs: v.trim() === '' ? null : parseInt(v) || 0 })
+                  }
+                />
                 <MarkdownEditor
                   name="request-description"
                   placeholder="Request description"
@@ -478,6 +606,31 @@ export function HttpRequestPane({ style, fullHeight, className, activeRequest }:
                   forceUpdateKey={updateKey}
                   onChange={(description) => patchModel(activeRequest, { description })}
                 />
+                <div className="border-t border-border-subtle pt-2 mt-2 max-h-[10rem]">
+                  <Editor
+                    hideGutter
+                    wrapLines
+                    language="text"
+                    heightMode="auto"
+                    placeholder="Scratch notes (not part of the description)"
+                    defaultValue={activeRequest.notes}
+                    stateKey={`notes.${activeRequest.id}`}
+                    forceUpdateKey={updateKey}
+                    onChange={(notes) => patchModel(activeRequest, { notes })}
+                  />
+                </div>
+                <div className="border-t border-border-subtle pt-2 mt-2 max-h-[10rem]">
+                  <Editor
+                    hideGutter
+                    language="text"
+                    heightMode="auto"
+                    placeholder="Extra CA certificate to trust (PEM)"
+                    defaultValue={activeRequest.caCertPem ?? ''}
+                    stateKey={`ca_cert_pem.${activeRequest.id}`}
+                    forceUpdateKey={updateKey}
+                    onChange={(v) => patchModel(activeRequest, { caCertPem: v.trim() ? v : null })}
+                  />
+                </div>
               </div>
             </TabContent>
           </Tabs>
@@ -27,6 +27,7 @@ import { CsvViewer } from './responseViewers/CsvViewer';
 import { EventStreamViewer } from './responseViewers/EventStreamViewer';
 import { HTMLOrTextViewer } from './responseViewers/HTMLOrTextViewer';
 import { ImageViewer } from './responseViewers/ImageViewer';
+import { NdjsonViewer } from './responseViewers/NdjsonViewer';
 import { PdfViewer } from './responseViewers/PdfViewer';
 import { SvgViewer } from './responseViewers/SvgViewer';
 import { VideoViewer } from './responseViewers/VideoViewer';
@@ -50,6 +51,7 @@ export function HttpResponsePane({ style, className, activeRequestId }: Props) {
     {},
   );
   const contentType = getContentTypeFromHeaders(activeResponse?.headers ?? null);
+  const warnings = activeResponse?.warnings ?? [];
 
   const tabs = useMemo<TabItem[]>(
     () => [
@@ -147,63 +149,84 @@ export function HttpResponsePane({ style, className, activeRequestId }: Props) {
             )}
           </HStack>
 
-          {activeResponse?.error ? (
+          {activeResponse?.error && activeResponse.errorKind !== 'status' ? (
             <Banner color="danger" className="m-2">
+              {activeResponse.errorKind === 'not_sent' && (
+                <p className="font-semibold">Request was not sent</p>
+              )}
               {activeResponse.error}
             </Banner>
           ) : (
-            <Tabs
-              key={activeRequestId} // Freshen tabs on request change
-              value={activeTab}
-              onChangeValue={setActiveTab}
-              tabs={tabs}
-              label="Response"
-              className="ml-3 mr-3 mb-3"
-              tabListClassName="mt-1.5"
+            <div
+              className={classNames(
+                'grid grid-cols-1 min-h-0',
+                activeResponse?.error || warnings.length > 0
+                  ? 'grid-rows-[auto_minmax(0,1fr)]'
+                  : 'grid-rows-[minmax(0,1fr)]',
+              )}
             >
-              <TabContent value={TAB_BODY}>
-                <ConfirmLargeResponse response={activeResponse}>
-                  {activeResponse.state === 'initialized' ? (
-                    <EmptyStateText>
-                      <LoadingIcon size="xl" className="text-text-subtlest" />
-                    </EmptyStateText>
-                  ) : activeResponse.state === 'closed' && activeResponse.contentLength === 0 ? (
-                    <EmptyStateText>Empty </EmptyStateText>
-                  ) : contentType?.match(/^text\/event-stream$/i) && viewMode === 'pretty' ? (
-                    <EventStreamViewer response={activeResponse} />
-                  ) : contentType?.match(/^image\/svg/) ? (
-                    <SvgViewer response={activeResponse} />
-                  ) : contentType?.match(/^image/i) ? (
-                    <EnsureCompleteResponse response={activeResponse} render={ImageViewer} />
-                  ) : contentType?.match(/^audio/i) ? (
-                    <EnsureCompleteResponse response={activeResponse} render={AudioViewer} />
-                  ) : contentType?.match(/^video/i) ? (
-                    <EnsureCompleteResponse response={activeResponse} render={VideoViewer} />
-                  ) : contentType?.match(/pdf/i) ? (
-                    <EnsureCompleteResponse response={activeResponse} render={PdfViewer} />
-                  ) : contentType?.match(/csv|tab-separated/i) ? (
-                    <CsvViewer className="pb-2" response={activeResponse} />
-                  ) : (
-                    // ) : viewMode === 'pretty' && contentType?.includes('json') ? (
-                    //   <JsonAttributeTree attrValue={activeResponse} />
-                    <HTMLOrTextViewer
-                      textViewerClassName="-mr-2 bg-surface" // Pull to the right
-                      response={activeResponse}
-                      pretty={viewMode === 'pretty'}
-                    />
-                  )}
-                </ConfirmLargeResponse>
-              </TabContent>
-              <TabContent value={TAB_HEADERS}>
-                <ResponseHeaders response={activeResponse} />
-              </TabContent>
-              <TabContent value={TAB_INFO}>
-                <ResponseInfo response={activeResponse} />
-              </TabContent>
-              <TabContent value={TAB_TIMELINE}>
-                <ResponseTimeline response={activeResponse} />
-              </TabContent>
-            </Tabs>
+              {(activeResponse?.error || warnings.length > 0) && (
+                <div className="mx-3 mt-2 flex flex-col gap-1">
+                  {activeResponse?.error && <Banner color="danger">{activeResponse.error}</Banner>}
+                  {warnings.length > 0 && <Banner color="warning">{warnings.join('\n')}</Banner>}
+                </div>
+              )}
+              <Tabs
+                key={activeRequestId} // Freshen tabs on request change
+                value={activeTab}
+                onChangeValue={setActiveTab}
+                tabs={tabs}
+                label="Response"
+                className="ml-3 mr-3 mb-3"
+                tabListClassName="mt-1.5"
+              >
+                <TabContent value={TAB_BODY}>
+                  <ConfirmLargeResponse response={activeResponse}>
+                    {activeResponse.state === 'initialized' ? (
+                      <EmptyStateText>
+                        <LoadingIcon size="xl" className="text-text-subtlest" />
+                      </EmptyStateText>
+                    ) : activeResponse.state === 'closed' && activeResponse.contentLength === 0 ? (
+                      <EmptyStateText>Empty </EmptyStateText>
+                    ) : contentType?.match(/^text\/event-stream$/i) && viewMode === 'pretty' ? (
+                      <EventStreamViewer response={activeResponse} />
+                    ) : contentType?.match(/^application\/(x-)?(ndjson|jsonl|jsonlines)\b/i) &&
+                      viewMode === 'pretty' ? (
+                      <NdjsonViewer response={activeResponse} />
+                    ) : contentType?.match(/^image\/svg/) ? (
+                      <SvgViewer response={activeResponse} />
+                    ) : contentType?.match(/^image/i) ? (
+                      <EnsureCompleteResponse response={activeResponse} render={ImageViewer} />
+                    ) : contentType?.match(/^audio/i) ? (
+                      <EnsureCompleteResponse response={activeResponse} render={AudioViewer} />
+                    ) : contentType?.match(/^video/i) ? (
+                      <EnsureCompleteResponse response={activeResponse} render={VideoViewer} />
+                    ) : contentType?.match(/pdf/i) ? (
+                      <EnsureCompleteResponse response={activeResponse} render={PdfViewer} />
+                    ) : contentType?.match(/csv|tab-separated/i) ? (
+                      <CsvViewer className="pb-2" response={activeResponse} />
+                    ) : (
+                      // ) : viewMode === 'pretty' && contentType?.includes('json') ? (
+                      //   <JsonAttributeTree attrValue={activeResponse} />
+                      <HTMLOrTextViewer
+                        textViewerClassName="-mr-2 bg-surface" // Pull to the right
+                        response={activeResponse}
+                        pretty={viewMode === 'pretty'}
+                      />
+                    )}
+                  </ConfirmLargeResponse>
+                </TabContent>
+                <TabContent value={TAB_HEADERS}>
+                  <ResponseHeaders response={activeResponse} />
+                </TabContent>
+                <TabContent value={TAB_INFO}>
+                  <ResponseInfo response={activeResponse} />
+                </TabContent>
+                <TabContent value={TAB_TIMELINE}>
+                  <ResponseTimeline response={activeResponse} />
+                </TabContent>
+              </Tabs>
+            </div>
           )}
         </div>
       )}
@@ -2,6 +2,7 @@ import type { HttpResponse } from '@yaakapp-internal/models';
 import { deleteModel } from '@yaakapp-internal/models';
 import { useCopyHttpResponse } from '../hooks/useCopyHttpResponse';
 import { useDeleteHttpResponses } from '../hooks/useDeleteHttpResponses';
+import { useResendHttpResponse } from '../hooks/useResendHttpResponse';
 import { useSaveResponse } from '../hooks/useSaveResponse';
 import { pluralize } from '../lib/pluralize';
 import { Dropdown } from './core/Dropdown';
@@ -26,6 +27,7 @@ export const RecentHttpResponsesDropdown = function ResponsePane({
   const latestResponseId = responses[0]?.id ?? 'n/a';
   const saveResponse = useSaveResponse(activeResponse);
   const copyResponse = useCopyHttpResponse(activeResponse);
+  const resendResponse = useResendHttpResponse(activeResponse);
 
   return (
     <Dropdown
@@ -44,6 +46,13 @@ export const RecentHttpResponsesDropdown = function ResponsePane({
           hidden: responses.length === 0,
           disabled: activeResponse.state !== 'closed' && activeResponse.status >= 100,
         },
+        {
+          label: 'Resend Exact Request',
+          onSelect: resendResponse.mutate,
+          leftSlot: <Icon icon="refresh" />,
+          hidden: responses.length === 0,
+          disabled: activeResponse.state !== 'closed' || activeResponse.url === '',
+        },
         {
           label: 'Delete',
           leftSlot: <Icon icon="trash" />,
@@ -22,6 +22,7 @@ const radioItems: RadioDropdownItem<string>[] = [
   'OPTIONS',
   'QUERY',
   'HEAD',
+  'CONNECT',
 ].map((m) => ({
   value: m,
   label: m,
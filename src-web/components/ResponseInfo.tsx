@@ -1,5 +1,8 @@
 import { openUrl } from '@tauri-apps/plugin-opener';
+import { formatSize } from '@yaakapp-internal/lib/formatSize';
 import type { HttpResponse } from '@yaakapp-internal/models';
+import { cookieJarsAtom } from '@yaakapp-internal/models';
+import { useAtomValue } from 'jotai';
 import { IconButton } from './core/IconButton';
 import { KeyValueRow, KeyValueRows } from './core/KeyValueRow';
 
@@ -8,6 +11,9 @@ interface Props {
 }
 
 export function ResponseInfo({ response }: Props) {
+  const size = response.contentLength;
+  const rawSize = response.contentLengthRaw;
+  const cookieJar = useAtomValue(cookieJarsAtom)?.find((j) => j.id === response.cookieJarId);
   return (
     <div className="overflow-auto h-full pb-4">
       <KeyValueRows>
@@ -17,6 +23,56 @@ export function ResponseInfo({ response }: Props) {
         <KeyValueRow labelColor="info" label="Remote Address">
           {response.remoteAddr}
         </KeyValueRow>
+        {response.resolvedAddrs.length > 0 && (
+          <KeyValueRow labelColor="info" label="Resolved Addresses">
+            {response.resolvedAddrs.join(', ')}
+          </KeyValueRow>
+        )}
+        <KeyValueRow labelColor="info" label="Local Address">
+          {response.localAddr}
+        </KeyValueRow>
+        <KeyValueRow labelColor="info" label="Reused Connection">
+          {response.reusedConnection ? 'Yes' : 'No'}
+        </KeyValueRow>
+        {response.sniHostname && (
+          <KeyValueRow labelColor="info" label="SNI Hostname">
+            {response.sniHostname}
+          </KeyValueRow>
+        )}
+        {response.tlsError && (
+          <KeyValueRow labelColor="info" label="Certificate Error">
+            <span className="select-text cursor-text text-danger">{response.tlsError}</span>
+          </KeyValueRow>
+        )}
+        {size != null && rawSize != null && rawSize !== size && (
+          <KeyValueRow labelColor="info" label="Transfer Size">
+            {formatSize(rawSize)} of {formatSize(size)}
+            {size > 0 && ` (${Math.round((rawSize / size) * 100)}%)`}
+          </KeyValueRow>
+        )}
+        {(response.fromCache || response.cacheAge != null) && (
+          <KeyValueRow labelColor="info" label="Cache">
+            {response.fromCache ? 'Hit' : 'Miss'}
+            {response.cacheAge != null && ` (age ${response.cacheAge}s)`}
+          </KeyValueRow>
+        )}
+        {response.cookieJarId && (
+          <KeyValueRow labelColor="info" label="Cookie Jar">
+            <span className="select-text cursor-text">
+              {cookieJar?.name ?? `${response.cookieJarId} (deleted)`}
+            </span>
+          </KeyValueRow>
+        )}
+        {response.requestCookies.length > 0 && (
+          <KeyValueRow labelColor="info" label="Cookies Sent">
+            <span className="select-text cursor-text">{response.requestCookies.join(', ')}</span>
+          </KeyValueRow>
+        )}
+        {response.correlationId && (
+          <KeyValueRow labelColor="info" label="Correlation ID">
+            <span className="select-text cursor-text">{response.correlationId}</span>
+          </KeyValueRow>
+        )}
         <KeyValueRow
           labelColor="info"
           label={
@@ -38,6 +94,11 @@ export function ResponseInfo({ response }: Props) {
             </div>
           }
         </KeyValueRow>
+        {response.requestUrlParameters.map((p, i) => (
+          <KeyValueRow key={i} label={p.name}>
+            <span className="select-text cursor-text">{p.value}</span>
+          </KeyValueRow>
+        ))}
       </KeyValueRows>
     </div>
   );
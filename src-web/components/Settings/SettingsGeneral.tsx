@@ -90,7 +90,7 @@ export function SettingsGeneral() {
           name="requestTimeout"
           label="Request Timeout (ms)"
           labelClassName="w-[14rem]"
-          placeholder="0"
+          placeholder="0 (no timeout)"
           labelPosition="left"
           defaultValue={`${workspace.settingRequestTimeout}`}
           validate={(value) => parseInt(value) >= 0}
@@ -98,6 +98,207 @@ export function SettingsGeneral() {
           type="number"
         />
 
+        <PlainInput
+          required
+          size="sm"
+          name="maxRetries"
+          label="Retries on 429/503"
+          labelClassName="w-[14rem]"
+          placeholder="0"
+          labelPosition="left"
+          defaultValue={`${workspace.settingMaxRetries}`}
+          validate={(value) => parseInt(value) >= 0}
+          onChange={(v) => patchModel(workspace, { settingMaxRetries: parseInt(v) || 0 })}
+          type="number"
+        />
+
+        <PlainInput
+          required
+          size="sm"
+          name="maxRetryWait"
+          label="Max Retry Wait (ms)"
+          labelClassName="w-[14rem]"
+          placeholder="30000"
+          labelPosition="left"
+          defaultValue={`${workspace.settingMaxRetryWait}`}
+          validate={(value) => parseInt(value) >= 0}
+          onChange={(v) => patchModel(workspace, { settingMaxRetryWait: parseInt(v) || 0 })}
+          type="number"
+        />
+
+        <PlainInput
+          required
+          size="sm"
+          name="maxBodySize"
+          label="Max Body Size (bytes)"
+          labelClassName="w-[14rem]"
+          placeholder="104857600"
+          labelPosition="left"
+          defaultValue={`${workspace.settingMaxBodySize}`}
+          validate={(value) => parseInt(value) >= 0}
+          onChange={(v) => patchModel(workspace, { settingMaxBodySize: parseInt(v) || 0 })}
+          type="number"
+        />
+
+        <PlainInput
+          required
+          size="sm"
+          name="maxDecodedSize"
+          label="Max Decompressed Size (bytes)"
+          labelClassName="w-[14rem]"
+          placeholder="1073741824"
+          labelPosition="left"
+          defaultValue={`${workspace.settingMaxDecodedSize}`}
+          validate={(value) => parseInt(value) >= 0}
+          onChange={(v) => patchModel(workspace, { settingMaxDecodedSize: parseInt(v) || 0 })}
+          type="number"
+        />
+
+        <PlainInput
+          required
+          size="sm"
+          name="maxHeaderSize"
+          label="Max Response Header Size (bytes)"
+          labelClassName="w-[14rem]"
+          placeholder="0"
+          labelPosition="left"
+          defaultValue={`${workspace.settingMaxHeaderSize}`}
+          validate={(value) => parseInt(value) >= 0}
+          onChange={(v) => patchModel(workspace, { settingMaxHeaderSize: parseInt(v) || 0 })}
+          type="number"
+        />
+
+        <PlainInput
+          required
+          size="sm"
+          name="maxHeaderCount"
+          label="Max Response Headers"
+          labelClassName="w-[14rem]"
+          placeholder="0"
+          labelPosition="left"
+          defaultValue={`${workspace.settingMaxHeaderCount}`}
+          validate={(value) => parseInt(value) >= 0}
+          onChange={(v) => patchModel(workspace, { settingMaxHeaderCount: parseInt(v) || 0 })}
+          type="number"
+        />
+
+        <PlainInput
+          required
+          size="sm"
+          name="maxConcurrency"
+          label="Folder Run Concurrency"
+          labelClassName="w-[14rem]"
+          placeholder="1"
+          labelPosition="left"
+          defaultValue={`${workspace.settingMaxConcurrency}`}
+          validate={(value) => parseInt(value) >= 1}
+          onChange={(v) => patchModel(workspace, { settingMaxConcurrency: parseInt(v) || 1 })}
+          type="number"
+        />
+
+        <PlainInput
+          required
+          size="sm"
+          name="poolIdleTimeout"
+          label="Idle Connection Timeout (ms)"
+          labelClassName="w-[14rem]"
+          placeholder="90000"
+          labelPosition="left"
+          defaultValue={`${workspace.settingPoolIdleTimeout}`}
+          validate={(value) => parseInt(value) >= 0}
+          onChange={(v) => patchModel(workspace, { settingPoolIdleTimeout: parseInt(v) || 0 })}
+          type="number"
+        />
+
+        <PlainInput
+          size="sm"
+          name="requestIdHeader"
+          label="Request ID Header"
+          labelClassName="w-[14rem]"
+          placeholder="X-Request-Id"
+          labelPosition="left"
+          defaultValue={workspace.settingRequestIdHeader ?? ''}
+          onChange={(v) => patchModel(workspace, { settingRequestIdHeader: v.trim() || null })}
+        />
+
+        <PlainInput
+          size="sm"
+          name="errorStatusCodes"
+          label="Error Status Codes"
+          labelClassName="w-[14rem]"
+          placeholder="eg. 404, 5xx"
+          labelPosition="left"
+          defaultValue={workspace.settingErrorStatusCodes ?? ''}
+          validate={(value) => /^[\d\sx,-]*$/i.test(value)}
+          onChange={(v) => patchModel(workspace, { settingErrorStatusCodes: v.trim() || null })}
+        />
+
+        <PlainInput
+          size="sm"
+          name="responseDir"
+          label="Response Directory"
+          labelClassName="w-[14rem]"
+          placeholder={`${appInfo.appDataDir}/responses`}
+          labelPosition="left"
+          defaultValue={workspace.settingResponseDir ?? ''}
+          onChange={(v) => patchModel(workspace, { settingResponseDir: v.trim() || null })}
+        />
+
+        <PlainInput
+          size="sm"
+          name="acceptEncoding"
+          label="Accept-Encoding"
+          labelClassName="w-[14rem]"
+          placeholder="gzip, br, deflate"
+          labelPosition="left"
+          defaultValue={workspace.settingAcceptEncoding ?? ''}
+          onChange={(v) => patchModel(workspace, { settingAcceptEncoding: v.trim() || null })}
+        />
+
+        <PlainInput
+          size="sm"
+          name="caBundlePath"
+          label="CA Certificates (PEM)"
+          labelClassName="w-[14rem]"
+          placeholder="/path/to/ca-bundle.pem"
+          labelPosition="left"
+          defaultValue={workspace.settingCaBundlePath ?? ''}
+          onChange={(v) => patchModel(workspace, { settingCaBundlePath: v.trim() || null })}
+        />
+
+        <PlainInput
+          size="sm"
+          name="clientCertPath"
+          label="Client Certificate (PEM)"
+          labelClassName="w-[14rem]"
+          placeholder="/path/to/client.crt"
+          labelPosition="left"
+          defaultValue={workspace.settingClientCertPath ?? ''}
+          onChange={(v) => patchModel(workspace, { settingClientCertPath: v.trim() || null })}
+        />
+
+        <PlainInput
+          size="sm"
+          name="clientKeyPath"
+          label="Client Key (PEM)"
+          labelClassName="w-[14rem]"
+          placeholder="/path/to/client.key"
+          labelPosition="left"
+          defaultValue={workspace.settingClientKeyPath ?? ''}
+          onChange={(v) => patchModel(workspace, { settingClientKeyPath: v.trim() || null })}
+        />
+
+        <PlainInput
+          size="sm"
+          name="insecureHosts"
+          label="Skip TLS Validation For"
+          labelClassName="w-[14rem]"
+          placeholder="eg. localhost, *.dev.internal"
+          labelPosition="left"
+          defaultValue={workspace.settingInsecureHosts ?? ''}
+          onChange={(v) => patchModel(workspace, { settingInsecureHosts: v.trim() || null })}
+        />
+
         <Checkbox
           checked={workspace.settingValidateCertificates}
           title="Validate TLS Certificates"
@@ -106,6 +307,56 @@ export function SettingsGeneral() {
           }
         />
 
+        <Checkbox
+          checked={workspace.settingDecompress}
+          title="Decompress Responses"
+          onChange={(settingDecompress) => patchModel(workspace, { settingDecompress })}
+        />
+
+        <Checkbox
+          checked={workspace.settingSendAcceptEncoding}
+          title="Send Accept-Encoding Header"
+          onChange={(settingSendAcceptEncoding) =>
+            patchModel(workspace, { settingSendAcceptEncoding })
+          }
+        />
+
+        <Checkbox
+          checked={workspace.settingSendDefaultHeaders}
+          title="Send Default User-Agent and Accept Headers"
+          onChange={(settingSendDefaultHeaders) =>
+            patchModel(workspace, { settingSendDefaultHeaders })
+          }
+        />
+
+        <Checkbox
+          checked={workspace.settingRejectUnresolvedTemplates}
+          title="Don't Send Requests With Unrendered Templates"
+          onChange={(settingRejectUnresolvedTemplates) =>
+            patchModel(workspace, { settingRejectUnresolvedTemplates })
+          }
+        />
+
+        <Checkbox
+          checked={workspace.settingAddCharset}
+          title="Add UTF-8 Charset to Text Bodies"
+          onChange={(settingAddCharset) => patchModel(workspace, { settingAddCharset })}
+        />
+
+        <Checkbox
+          checked={workspace.settingEnableHttp3}
+          title="Attempt HTTP/3 (HTTPS only)"
+          onChange={(settingEnableHttp3) => patchModel(workspace, { settingEnableHttp3 })}
+        />
+
+        <Checkbox
+          checked={workspace.settingTitleCaseHeaders}
+          title="Title-Case Header Names (HTTP/1)"
+          onChange={(settingTitleCaseHeaders) =>
+            patchModel(workspace, { settingTitleCaseHeaders })
+          }
+        />
+
         <Checkbox
           checked={workspace.settingFollowRedirects}
           title="Follow Redirects"
@@ -115,6 +366,24 @@ export function SettingsGeneral() {
             })
           }
         />
+
+        <Checkbox
+          checked={workspace.settingRedirectPreserveMethod}
+          disabled={!workspace.settingFollowRedirects}
+          title="Preserve Method on 301/302 Redirects"
+          onChange={(settingRedirectPreserveMethod) =>
+            patchModel(workspace, { settingRedirectPreserveMethod })
+          }
+        />
+
+        <Checkbox
+          checked={workspace.settingRedirectAllowDowngrade}
+          disabled={!workspace.settingFollowRedirects}
+          title="Allow HTTPS to HTTP Redirects"
+          onChange={(settingRedirectAllowDowngrade) =>
+            patchModel(workspace, { settingRedirectAllowDowngrade })
+          }
+        />
       </VStack>
 
       <Separator className="my-4" />
@@ -51,7 +51,11 @@ export function SettingsProxy() {
               onChange={async (http) => {
                 const https = settings.proxy?.type === 'enabled' ? settings.proxy.https : '';
                 const auth = settings.proxy?.type === 'enabled' ? settings.proxy.auth : null;
-                await patchModel(settings, { proxy: { type: 'enabled', http, https, auth } });
+                const alwaysConnect =
+                  settings.proxy?.type === 'enabled' ? settings.proxy.alwaysConnect : false;
+                await patchModel(settings, {
+                  proxy: { type: 'enabled', http, https, auth, alwaysConnect },
+                });
               }}
             />
             <PlainInput
@@ -62,10 +66,26 @@ export function SettingsProxy() {
               onChange={async (https) => {
                 const http = settings.proxy?.type === 'enabled' ? settings.proxy.http : '';
                 const auth = settings.proxy?.type === 'enabled' ? settings.proxy.auth : null;
-                await patchModel(settings, { proxy: { type: 'enabled', http, https, auth } });
+                const alwaysConnect =
+                  settings.proxy?.type === 'enabled' ? settings.proxy.alwaysConnect : false;
+                await patchModel(settings, {
+                  proxy: { type: 'enabled', http, https, auth, alwaysConnect },
+                });
               }}
             />
           </HStack>
+          <Checkbox
+            checked={settings.proxy.alwaysConnect ?? false}
+            title="Always tunnel with CONNECT"
+            onChange={async (alwaysConnect) => {
+              const http = settings.proxy?.type === 'enabled' ? settings.proxy.http : '';
+              const https = settings.proxy?.type === 'enabled' ? settings.proxy.https : '';
+              const auth = settings.proxy?.type === 'enabled' ? settings.proxy.auth : null;
+              await patchModel(settings, {
+                proxy: { type: 'enabled', http, https, auth, alwaysConnect },
+              });
+            }}
+          />
           <Separator className="my-6" />
           <Checkbox
             checked={settings.proxy.auth != null}
@@ -74,7 +94,11 @@ export function SettingsProxy() {
               const http = settings.proxy?.type === 'enabled' ? settings.proxy.http : '';
               const https = settings.proxy?.type === 'enabled' ? settings.proxy.https : '';
               const auth = enabled ? { user: '', password: '' } : null;
-              await patchModel(settings, { proxy: { type: 'enabled', http, https, auth } });
+              const alwaysConnect =
+                settings.proxy?.type === 'enabled' ? settings.proxy.alwaysConnect : false;
+              await patchModel(settings, {
+                proxy: { type: 'enabled', http, https, auth, alwaysConnect },
+                });
             }}
           />
 
@@ -92,7 +116,11 @@ export function SettingsProxy() {
                   const password =
                     settings.proxy?.type === 'enabled' ? (settings.proxy.auth?.password ?? '') : '';
                   const auth = { user, password };
-                  await patchModel(settings, { proxy: { type: 'enabled', http, https, auth } });
+                  const alwaysConnect =
+                    settings.proxy?.type === 'enabled' ? settings.proxy.alwaysConnect : false;
+                  await patchModel(settings, {
+                    proxy: { type: 'enabled', http, https, auth, alwaysConnect },
+                });
                 }}
               />
               <PlainInput
@@ -107,7 +135,11 @@ export function SettingsProxy() {
                   const user =
                     settings.proxy?.type === 'enabled' ? (settings.proxy.auth?.user ?? '') : '';
                   const auth = { user, password };
-                  await patchModel(settings, { proxy: { type: 'enabled', http, https, auth } });
+                  const alwaysConnect =
+                    settings.proxy?.type === 'enabled' ? settings.proxy.alwaysConnect : false;
+                  await patchModel(settings, {
+                    proxy: { type: 'enabled', http, https, auth, alwaysConnect },
+                });
                 }}
               />
             </HStack>
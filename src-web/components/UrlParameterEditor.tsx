@@ -34,6 +34,7 @@ export function UrlParametersEditor({ pairs, forceUpdateKey, onChange, stateKey
       <PairOrBulkEditor
         ref={pairEditor}
         allowMultilineValues
+        allowNotation
         forceUpdateKey={forceUpdateKey + urlParametersKey}
         nameAutocompleteFunctions
         nameAutocompleteVariables
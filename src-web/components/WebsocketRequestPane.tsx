@@ -298,7 +298,7 @@ export function WebsocketRequestPane({ style, fullHeight, className, activeReque
               />
             </TabContent>
             <TabContent value={TAB_DESCRIPTION}>
-              <div className="grid grid-rows-[auto_minmax(0,1fr)] h-full">
+              <div className="grid grid-rows-[auto_auto_minmax(0,1fr)] gap-y-2 h-full">
                 <PlainInput
                   label="Request Name"
                   hideLabel
@@ -309,6 +309,21 @@ export function WebsocketRequestPane({ style, fullHeight, className, activeReque
                   placeholder={resolvedModelName(activeRequest)}
                   onChange={(name) => patchModel(activeRequest, { name })}
                 />
+                <PlainInput
+                  size="sm"
+                  name="maxMessagesPerSecond"
+                  label="Max Messages per Second"
+                  labelPosition="left"
+                  labelClassName="w-[12rem]"
+                  placeholder="0 (unlimited)"
+                  forceUpdateKey={forceUpdateKey}
+                  defaultValue={`${activeRequest.maxMessagesPerSecond}`}
+                  validate={(value) => parseInt(value) >= 0}
+                  onChange={(v) =>
+                    patchModel(activeRequest, { maxMessagesPerSecond: parseInt(v) || 0 })
+                  }
+                  type="number"
+                />
                 <MarkdownEditor
                   name="request-description"
                   placeholder="Request description"
@@ -128,7 +128,9 @@ export function WebsocketResponsePane({ activeRequest }: Props) {
                     ? 'Connection Closed'
                     : activeEvent.messageType === 'open'
                       ? 'Connection open'
-                      : `Message ${activeEvent.isServer ? 'Received' : 'Sent'}`}
+                      : activeEvent.messageType === 'throttle'
+                        ? 'Message Dropped'
+                        : `Message ${activeEvent.isServer ? 'Received' : 'Sent'}`}
                 </div>
                 {message != '' && (
                   <HStack space={1}>
@@ -221,16 +223,20 @@ function EventRow({
           color={
             messageType === 'close' || messageType === 'open'
               ? 'secondary'
-              : isServer
-                ? 'info'
-                : 'primary'
+              : messageType === 'throttle'
+                ? 'warning'
+                : isServer
+                  ? 'info'
+                  : 'primary'
           }
           icon={
             messageType === 'close' || messageType === 'open'
               ? 'info'
-              : isServer
-                ? 'arrow_big_down_dash'
-                : 'arrow_big_up_dash'
+              : messageType === 'throttle'
+                ? 'alert_triangle'
+                : isServer
+                  ? 'arrow_big_down_dash'
+                  : 'arrow_big_up_dash'
           }
         />
         <div className={classNames('w-full truncate text-xs')}>
@@ -9,6 +9,7 @@ import { openWorkspaceSettings } from '../commands/openWorkspaceSettings';
 import { switchWorkspace } from '../commands/switchWorkspace';
 import { activeWorkspaceAtom, activeWorkspaceMetaAtom } from '../hooks/useActiveWorkspace';
 import { useCreateWorkspace } from '../hooks/useCreateWorkspace';
+import { useDeleteOldHttpResponses } from '../hooks/useDeleteOldHttpResponses';
 import { useDeleteSendHistory } from '../hooks/useDeleteSendHistory';
 import { showDialog } from '../lib/dialog';
 import { jotaiStore } from '../lib/jotai';
@@ -32,6 +33,7 @@ export const WorkspaceActionsDropdown = memo(function WorkspaceActionsDropdown({
   const createWorkspace = useCreateWorkspace();
   const workspaceMeta = useAtomValue(activeWorkspaceMetaAtom);
   const { mutate: deleteSendHistory } = useDeleteSendHistory();
+  const { mutate: deleteOldHttpResponses } = useDeleteOldHttpResponses();
 
   const { workspaceItems, extraItems } = useMemo<{
     workspaceItems: RadioDropdownItem[];
@@ -66,6 +68,12 @@ export const WorkspaceActionsDropdown = memo(function WorkspaceActionsDropdown({
         leftSlot: <Icon icon="history" />,
         onSelect: deleteSendHistory,
       },
+      {
+        label: 'Delete Old Responses',
+        color: 'warning',
+        leftSlot: <Icon icon="trash" />,
+        onSelect: deleteOldHttpResponses,
+      },
       { type: 'separator' },
       {
         label: 'New Workspace',
@@ -89,7 +97,14 @@ export const WorkspaceActionsDropdown = memo(function WorkspaceActionsDropdown({
     ];
 
     return { workspaceItems, extraItems };
-  }, [workspaces, workspaceMeta, deleteSendHistory, createWorkspace, workspace?.id]);
+  }, [
+    workspaces,
+    workspaceMeta,
+    deleteSendHistory,
+    deleteOldHttpResponses,
+    createWorkspace,
+    workspace?.id,
+  ]);
 
   const handleSwitchWorkspace = useCallback(async (workspaceId: string | null) => {
     if (workspaceId == null) return;
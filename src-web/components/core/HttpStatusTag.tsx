@@ -8,7 +8,7 @@ interface Props {
 }
 
 export function HttpStatusTag({ response, className, showReason }: Props) {
-  const { status, state } = response;
+  const { status, state, errorKind } = response;
 
   let colorClass;
   let label = `${status}`;
@@ -16,6 +16,9 @@ export function HttpStatusTag({ response, className, showReason }: Props) {
   if (state === 'initialized') {
     label = 'CONNECTING';
     colorClass = 'text-text-subtle';
+  } else if (errorKind === 'not_sent') {
+    label = 'NOT SENT';
+    colorClass = 'text-danger';
   } else if (status < 100) {
     label = 'ERROR';
     colorClass = 'text-danger';
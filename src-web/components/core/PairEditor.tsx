@@ -1,3 +1,4 @@
+import type { Environment, UrlParameterNotation } from '@yaakapp-internal/models';
 import classNames from 'classnames';
 import type { EditorView } from 'codemirror';
 import {
@@ -40,7 +41,12 @@ export interface PairEditorRef {
 export type PairEditorProps = {
   allowFileValues?: boolean;
   allowMultilineValues?: boolean;
+  /** Let pairs choose a bracket notation for their name, like URL parameters can */
+  allowNotation?: boolean;
+  allowSecretValues?: boolean;
   className?: string;
+  /** Environments that pairs can be limited to, listed in each pair's menu */
+  environments?: Environment[];
   forceUpdateKey?: string;
   nameAutocomplete?: GenericCompletionConfig;
   nameAutocompleteFunctions?: boolean;
@@ -66,7 +72,16 @@ export type Pair = {
   value: string;
   contentType?: string;
   isFile?: boolean;
+  /** File value that's a directory, to send every file in it */
+  isDirectory?: boolean;
+  /** Patterns, like `*.png, *.jpg`, for the files sent from a directory */
+  filter?: string;
+  /** Brackets added to the name when it's sent, like `items[]` */
+  notation?: UrlParameterNotation;
+  /** IDs of the only environments the pair is sent in */
+  environments?: string[];
   readOnlyName?: boolean;
+  secret?: boolean;
 };
 
 export type PairWithId = Pair & {
@@ -81,7 +96,10 @@ export const PairEditor = forwardRef<PairEditorRef, PairEditorProps>(function Pa
     stateKey,
     allowFileValues,
     allowMultilineValues,
+    allowNotation,
+    allowSecretValues,
     className,
+    environments,
     forceUpdateKey,
     nameAutocomplete,
     nameAutocompleteFunctions,
@@ -234,7 +252,10 @@ export const PairEditor = forwardRef<PairEditorRef, PairEditorProps>(function Pa
             <PairEditorRow
               allowFileValues={allowFileValues}
               allowMultilineValues={allowMultilineValues}
+              allowNotation={allowNotation}
+              allowSecretValues={allowSecretValues}
               className="py-1"
+              environments={environments}
               forceFocusNamePairId={forceFocusNamePairId}
               forceFocusValuePairId={forceFocusValuePairId}
               forceUpdateKey={forceUpdateKey}
@@ -292,6 +313,9 @@ type PairEditorRowProps = {
   PairEditorProps,
   | 'allowFileValues'
   | 'allowMultilineValues'
+  | 'allowNotation'
+  | 'allowSecretValues'
+  | 'environments'
   | 'forceUpdateKey'
   | 'nameAutocomplete'
   | 'nameAutocompleteVariables'
@@ -310,7 +334,10 @@ type PairEditorRowProps = {
 function PairEditorRow({
   allowFileValues,
   allowMultilineValues,
+  allowNotation,
+  allowSecretValues,
   className,
+  environments,
   forceFocusNamePairId,
   forceFocusValuePairId,
   forceUpdateKey,
@@ -365,14 +392,48 @@ function PairEditorRow({
   );
 
   const handleChangeValueText = useMemo(
-    () => (value: string) => onChange({ ...pair, value, isFile: false }),
+    () => (value: string) => onChange({ ...pair, value, isFile: false, isDirectory: false }),
     [onChange, pair],
   );
 
   const handleChangeValueFile = useMemo(
     () =>
-      ({ filePath }: { filePath: string | null }) =>
-        onChange({ ...pair, value: filePath ?? '', isFile: true }),
+      ({ filePath, directory }: { filePath: string | null; directory?: boolean }) =>
+        onChange({
+          ...pair,
+          value: filePath ?? '',
+          isFile: true,
+          isDirectory: directory ?? pair.isDirectory,
+        }),
+    [onChange, pair],
+  );
+
+  const handleChangeFilter = useMemo(
+    () => (filter: string) => onChange({ ...pair, filter }),
+    [onChange, pair],
+  );
+
+  const handleToggleNotation = useCallback(
+    (notation: UrlParameterNotation) =>
+      onChange({ ...pair, notation: pair.notation === notation ? undefined : notation }),
+    [onChange, pair],
+  );
+
+  const handleToggleEnvironment = useCallback(
+    (environmentId: string) => {
+      const ids = pair.environments ?? [];
+      onChange({
+        ...pair,
+        environments: ids.includes(environmentId)
+          ? ids.filter((id) => id !== environmentId)
+          : [...ids, environmentId],
+      });
+    },
+    [onChange, pair],
+  );
+
+  const handleToggleSecret = useCallback(
+    () => onChange({ ...pair, secret: !pair.secret }),
     [onChange, pair],
   );
 
@@ -406,13 +467,50 @@ function PairEditorRow({
         onSelect: handleEditMultiLineValue,
         hidden: !allowMultilineValues,
       },
+      {
+        label: 'Send as Array (name[])',
+        leftSlot: <Icon icon={pair.notation === 'array' ? 'check' : 'empty'} />,
+        onSelect: () => handleToggleNotation('array'),
+        hidden: !allowNotation,
+      },
+      {
+        label: 'Send as Object (a.b → a[b])',
+        leftSlot: <Icon icon={pair.notation === 'object' ? 'check' : 'empty'} />,
+        onSelect: () => handleToggleNotation('object'),
+        hidden: !allowNotation,
+      },
+      ...(environments ?? []).map(
+        (e): DropdownItem => ({
+          label: `Only in ${e.name}`,
+          leftSlot: <Icon icon={pair.environments?.includes(e.id) ? 'check' : 'empty'} />,
+          onSelect: () => handleToggleEnvironment(e.id),
+        }),
+      ),
+      {
+        label: pair.secret ? 'Unmark as Secret' : 'Mark as Secret',
+        onSelect: handleToggleSecret,
+        hidden: !allowSecretValues,
+      },
       {
         label: 'Delete',
         onSelect: handleDelete,
         color: 'danger',
       },
     ],
-    [allowMultilineValues, handleDelete, handleEditMultiLineValue],
+    [
+      allowMultilineValues,
+      allowNotation,
+      allowSecretValues,
+      environments,
+      handleDelete,
+      handleEditMultiLineValue,
+      handleToggleEnvironment,
+      handleToggleNotation,
+      handleToggleSecret,
+      pair.environments,
+      pair.notation,
+      pair.secret,
+    ],
   );
 
   const [, connectDrop] = useDrop<Pair>(
@@ -517,7 +615,13 @@ function PairEditorRow({
         )}
         <div className="w-full grid grid-cols-[minmax(0,1fr)_auto] gap-1 items-center">
           {pair.isFile ? (
-            <SelectFile inline size="xs" filePath={pair.value} onChange={handleChangeValueFile} />
+            <SelectFile
+              inline
+              size="xs"
+              directory={pair.isDirectory}
+              filePath={pair.value}
+              onChange={handleChangeValueFile}
+            />
           ) : isLast ? (
             // Use PlainInput for last ones because there's a unique bug where clicking below
             // the Codemirror input focuses it.
@@ -555,7 +659,7 @@ function PairEditorRow({
               name={`value[${index}]`}
               onChange={handleChangeValueText}
               onFocus={handleFocus}
-              type={isLast ? 'text' : valueType}
+              type={isLast ? 'text' : pair.secret ? 'password' : valueType}
               placeholder={valuePlaceholder ?? 'value'}
               autocomplete={valueAutocomplete?.(pair.name)}
               autocompleteFunctions={valueAutocompleteFunctions}
@@ -570,6 +674,7 @@ function PairEditorRow({
           onChangeFile={handleChangeValueFile}
           onChangeText={handleChangeValueText}
           onChangeContentType={handleChangeValueContentType}
+          onChangeFilter={handleChangeFilter}
           onDelete={handleDelete}
           editMultiLine={handleEditMultiLineValue}
         />
@@ -590,6 +695,7 @@ function PairEditorRow({
 const fileItems: RadioDropdownItem<string>[] = [
   { label: 'Text', value: 'text' },
   { label: 'File', value: 'file' },
+  { label: 'Folder', value: 'directory' },
 ];
 
 function FileActionsDropdown({
@@ -597,19 +703,22 @@ function FileActionsDropdown({
   onChangeFile,
   onChangeText,
   onChangeContentType,
+  onChangeFilter,
   onDelete,
   editMultiLine,
 }: {
   pair: Pair;
-  onChangeFile: ({ filePath }: { filePath: string | null }) => void;
+  onChangeFile: ({ filePath, directory }: { filePath: string | null; directory?: boolean }) => void;
   onChangeText: (text: string) => void;
   onChangeContentType: (contentType: string) => void;
+  onChangeFilter: (filter: string) => void;
   onDelete: () => void;
   editMultiLine: () => void;
 }) {
   const onChange = useCallback(
     (v: string) => {
-      if (v === 'file') onChangeFile({ filePath: '' });
+      if (v === 'file') onChangeFile({ filePath: '', directory: false });
+      else if (v === 'directory') onChangeFile({ filePath: '', directory: true });
       else onChangeText('');
     },
     [onChangeFile, onChangeText],
@@ -640,6 +749,24 @@ function FileActionsDropdown({
           onChangeContentType(contentType);
         },
       },
+      {
+        label: 'Set File Filter',
+        leftSlot: <Icon icon="filter" />,
+        hidden: !pair.isDirectory,
+        onSelect: async () => {
+          const filter = await showPrompt({
+            id: 'file-filter',
+            title: 'Filter Folder Files',
+            label: 'Patterns',
+            placeholder: '*.png, *.jpg',
+            defaultValue: pair.filter ?? '',
+            confirmText: 'Set',
+            description: 'Leave blank to send every file in the folder',
+          });
+          if (filter == null) return;
+          onChangeFilter(filter);
+        },
+      },
       {
         label: 'Unset File',
         leftSlot: <Icon icon="x" />,
@@ -656,12 +783,22 @@ function FileActionsDropdown({
         color: 'danger',
       },
     ],
-    [editMultiLine, onChangeContentType, onChangeFile, onDelete, pair.contentType, pair.isFile],
+    [
+      editMultiLine,
+      onChangeContentType,
+      onChangeFile,
+      onChangeFilter,
+      onDelete,
+      pair.contentType,
+      pair.filter,
+      pair.isDirectory,
+      pair.isFile,
+    ],
   );
 
   return (
     <RadioDropdown
-      value={pair.isFile ? 'file' : 'text'}
+      value={pair.isDirectory ? 'directory' : pair.isFile ? 'file' : 'text'}
       onChange={onChange}
       items={fileItems}
       extraItems={extraItems}
@@ -100,6 +100,7 @@ export function Tabs({
                   key={t.value}
                   items={t.options.items}
                   value={t.options.value}
+                  extraItems={t.options.extraItems}
                   onChange={t.options.onChange}
                 >
                   <button
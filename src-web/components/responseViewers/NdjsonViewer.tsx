@@ -0,0 +1,128 @@
+import type { HttpResponse } from '@yaakapp-internal/models';
+import classNames from 'classnames';
+import { Fragment, useMemo, useState } from 'react';
+import { useFormatText } from '../../hooks/useFormatText';
+import { useResponseBodyNdjson } from '../../hooks/useResponseBodyNdjson';
+import type { NdjsonEvent } from '../../lib/responseBody';
+import { AutoScroller } from '../core/AutoScroller';
+import { Banner } from '../core/Banner';
+import { Editor } from '../core/Editor/Editor';
+import { Icon } from '../core/Icon';
+import { InlineCode } from '../core/InlineCode';
+import { Separator } from '../core/Separator';
+import { SplitLayout } from '../core/SplitLayout';
+
+interface Props {
+  response: HttpResponse;
+}
+
+export function NdjsonViewer({ response }: Props) {
+  return (
+    <Fragment
+      key={response.id} // force a refresh when the response changes
+    >
+      <ActualNdjsonViewer response={response} />
+    </Fragment>
+  );
+}
+
+function ActualNdjsonViewer({ response }: Props) {
+  const [activeEventIndex, setActiveEventIndex] = useState<number | null>(null);
+  const events = useResponseBodyNdjson(response);
+  const activeEvent = useMemo(
+    () => (activeEventIndex == null ? null : events.data?.[activeEventIndex]),
+    [activeEventIndex, events],
+  );
+
+  return (
+    <SplitLayout
+      layout="vertical"
+      name="ndjson_events"
+      defaultRatio={0.4}
+      minHeightPx={20}
+      firstSlot={() => (
+        <AutoScroller
+          data={events.data ?? []}
+          header={
+            events.error && (
+              <Banner color="danger" className="m-3">
+                {String(events.error)}
+              </Banner>
+            )
+          }
+          render={(event, i) => (
+            <EventRow
+              event={event}
+              isActive={i === activeEventIndex}
+              onClick={() => {
+                if (i === activeEventIndex) setActiveEventIndex(null);
+                else setActiveEventIndex(i);
+              }}
+            />
+          )}
+        />
+      )}
+      secondSlot={
+        activeEvent
+          ? () => (
+              <div className="grid grid-rows-[auto_minmax(0,1fr)]">
+                <div className="pb-3 px-2">
+                  <Separator />
+                </div>
+                <div className="pl-2 overflow-y-auto">
+                  <div className="mb-2 font-semibold">Line {activeEvent.line}</div>
+                  {activeEvent.error && (
+                    <Banner color="danger" className="mb-2">
+                      {activeEvent.error}
+                    </Banner>
+                  )}
+                  <FormattedEditor
+                    text={activeEvent.data}
+                    language={activeEvent.error ? 'text' : 'json'}
+                  />
+                </div>
+              </div>
+            )
+          : null
+      }
+    />
+  );
+}
+
+function FormattedEditor({ text, language }: { text: string; language: 'text' | 'json' }) {
+  const formatted = useFormatText({ text, language, pretty: true });
+  if (formatted.data == null) return null;
+  return <Editor readOnly defaultValue={formatted.data} language={language} stateKey={null} />;
+}
+
+function EventRow({
+  onClick,
+  isActive,
+  event,
+}: {
+  onClick: () => void;
+  isActive: boolean;
+  event: NdjsonEvent;
+}) {
+  return (
+    <button
+      onClick={onClick}
+      className={classNames(
+        'w-full grid grid-cols-[auto_auto_minmax(0,3fr)] gap-2 items-center text-left',
+        '-mx-1.5 px-1.5 h-xs font-mono group focus:outline-none focus:text-text rounded',
+        isActive && '!bg-surface-active !text-text',
+        'text-text-subtle hover:text',
+      )}
+    >
+      {event.error ? (
+        <Icon color="danger" title={event.error} icon="alert_triangle" />
+      ) : (
+        <Icon color="info" title="Server Message" icon="arrow_big_down_dash" />
+      )}
+      <InlineCode className={classNames('py-0 text-sm', isActive && 'bg-text-subtlest text-text')}>
+        {event.line}
+      </InlineCode>
+      <div className="w-full truncate text-xs">{event.data.slice(0, 1000)}</div>
+    </button>
+  );
+}
@@ -6,9 +6,12 @@ import {
 } from '@yaakapp-internal/models';
 import { useAtomValue } from 'jotai';
 import React, { useMemo } from 'react';
+import { useCopy } from '../../hooks/useCopy';
 import { useCreateDropdownItems } from '../../hooks/useCreateDropdownItems';
+import { useDryRunHttpRequest } from '../../hooks/useDryRunHttpRequest';
 import { useHttpRequestActions } from '../../hooks/useHttpRequestActions';
 import { useMoveToWorkspace } from '../../hooks/useMoveToWorkspace';
+import { useSendAndDownloadHttpRequest } from '../../hooks/useSendAndDownloadHttpRequest';
 import { useSendAnyHttpRequest } from '../../hooks/useSendAnyHttpRequest';
 import { useSendManyRequests } from '../../hooks/useSendManyRequests';
 import { deleteModelWithConfirm } from '../../lib/deleteModelWithConfirm';
@@ -16,6 +19,7 @@ import { duplicateRequestAndNavigate } from '../../lib/deleteRequestAndNavigate'
 
 import { showDialog } from '../../lib/dialog';
 import { renameModelWithPrompt } from '../../lib/renameModelWithPrompt';
+import { invokeCmd } from '../../lib/tauri';
 import type { DropdownItem } from '../core/Dropdown';
 import { ContextMenu } from '../core/Dropdown';
 import { Icon } from '../core/Icon';
@@ -32,6 +36,9 @@ export function SidebarItemContextMenu({ child, show, close }: Props) {
   const sendManyRequests = useSendManyRequests();
   const httpRequestActions = useHttpRequestActions();
   const sendRequest = useSendAnyHttpRequest();
+  const sendAndDownload = useSendAndDownloadHttpRequest();
+  const dryRun = useDryRunHttpRequest();
+  const copy = useCopy();
   const workspaces = useAtomValue(workspacesAtom);
   const moveToWorkspace = useMoveToWorkspace(child.id);
   const createDropdownItems = useCreateDropdownItems({
@@ -84,6 +91,26 @@ export function SidebarItemContextMenu({ child, show, close }: Props) {
                 leftSlot: <Icon icon="send_horizontal" />,
                 onSelect: () => sendRequest.mutate(child.id),
               },
+              {
+                label: 'Send and Download',
+                leftSlot: <Icon icon="download" />,
+                onSelect: () => sendAndDownload.mutate(child.id),
+              },
+              {
+                label: 'Validate Without Sending',
+                leftSlot: <Icon icon="check_circle" />,
+                onSelect: () => dryRun.mutate(child.id),
+              },
+              {
+                label: 'Copy as OpenAPI',
+                leftSlot: <Icon icon="copy" />,
+                onSelect: async () => {
+                  const operation = await invokeCmd<string>('cmd_http_request_openapi', {
+                    requestId: child.id,
+                  });
+                  copy(operation);
+                },
+              },
               ...httpRequestActions.map((a) => ({
                 label: a.label,
                 // eslint-disable-next-line @typescript-eslint/no-explicit-any
@@ -142,9 +169,12 @@ export function SidebarItemContextMenu({ child, show, close }: Props) {
     child.children,
     child.id,
     child.model,
+    copy,
     createDropdownItems,
+    dryRun,
     httpRequestActions,
     moveToWorkspace.mutate,
+    sendAndDownload,
     sendManyRequests,
     sendRequest,
     workspaces.length,
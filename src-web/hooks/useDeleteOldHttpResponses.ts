@@ -0,0 +1,41 @@
+import { formatSize } from '@yaakapp-internal/lib/formatSize';
+import { jotaiStore } from '../lib/jotai';
+import { pluralizeCount } from '../lib/pluralize';
+import { showPrompt } from '../lib/prompt';
+import { invokeCmd } from '../lib/tauri';
+import { showToast } from '../lib/toast';
+import { activeWorkspaceIdAtom } from './useActiveWorkspace';
+import { useFastMutation } from './useFastMutation';
+
+export function useDeleteOldHttpResponses() {
+  return useFastMutation({
+    mutationKey: ['delete_old_http_responses'],
+    mutationFn: async () => {
+      const days = await showPrompt({
+        id: 'delete-old-http-responses',
+        title: 'Delete Old Responses',
+        description: 'Delete responses, and their body files, older than a number of days',
+        label: 'Days',
+        defaultValue: '30',
+        confirmText: 'Delete',
+      });
+      if (days == null) return;
+
+      const maxAgeDays = parseInt(days);
+      if (isNaN(maxAgeDays) || maxAgeDays < 0) {
+        throw new Error(`Invalid number of days "${days}"`);
+      }
+
+      const workspaceId = jotaiStore.get(activeWorkspaceIdAtom);
+      const { count, bytes } = await invokeCmd<{ count: number; bytes: number }>(
+        'cmd_delete_old_http_responses',
+        { workspaceId, maxAgeDays },
+      );
+      showToast({
+        id: 'delete-old-http-responses',
+        color: 'success',
+        message: `Deleted ${pluralizeCount('response', count)}, reclaiming ${formatSize(bytes)}`,
+      });
+    },
+  });
+}
@@ -0,0 +1,40 @@
+import type { HttpResponse } from '@yaakapp-internal/models';
+import { getModel } from '@yaakapp-internal/models';
+import { InlineCode } from '../components/core/InlineCode';
+import { invokeCmd } from '../lib/tauri';
+import { showToast } from '../lib/toast';
+import { getActiveCookieJar } from './useActiveCookieJar';
+import { getActiveEnvironment } from './useActiveEnvironment';
+import { useFastMutation } from './useFastMutation';
+
+/** Render and build a request without sending it, to check it for errors while offline */
+export function useDryRunHttpRequest() {
+  return useFastMutation<HttpResponse | null, string, string>({
+    mutationKey: ['dry_run_request'],
+    mutationFn: async (id) => {
+      const request = getModel('http_request', id);
+      if (request == null) return null;
+
+      const response = await invokeCmd<HttpResponse>('cmd_dry_run_http_request', {
+        request,
+        environmentId: getActiveEnvironment()?.id,
+        cookieJarId: getActiveCookieJar()?.id,
+        // Auth can call out to a server, like for an OAuth token
+        skipAuth: true,
+      });
+      showToast({
+        id: 'dry-run-request',
+        color: response.error == null ? 'success' : 'danger',
+        message:
+          response.error == null ? (
+            <>
+              Request to <InlineCode>{response.url}</InlineCode> is valid
+            </>
+          ) : (
+            response.error
+          ),
+      });
+      return response;
+    },
+  });
+}
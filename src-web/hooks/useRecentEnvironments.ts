@@ -1,3 +1,4 @@
+import { workspaceMetasAtom } from '@yaakapp-internal/models';
 import { useAtomValue } from 'jotai';
 import { useEffect, useMemo } from 'react';
 import { jotaiStore } from '../lib/jotai';
@@ -49,9 +50,16 @@ export function useSubscribeRecentEnvironments() {
 }
 
 export async function getRecentEnvironments(workspaceId: string) {
-  return getKeyValue<string[]>({
+  const recentIds = getKeyValue<string[]>({
     namespace,
     key: kvKey(workspaceId),
     fallback,
   });
+  if (recentIds.length > 0) return recentIds;
+
+  // Fall back to the environment a request was last sent with
+  const workspaceMeta = jotaiStore
+    .get(workspaceMetasAtom)
+    .find((m) => m.workspaceId === workspaceId);
+  return workspaceMeta?.lastEnvironmentId ? [workspaceMeta.lastEnvironmentId] : fallback;
 }
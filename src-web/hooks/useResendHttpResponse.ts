@@ -0,0 +1,15 @@
+import type { HttpResponse } from '@yaakapp-internal/models';
+import { invokeCmd } from '../lib/tauri';
+import { getActiveEnvironment } from './useActiveEnvironment';
+import { useFastMutation } from './useFastMutation';
+
+export function useResendHttpResponse(response: HttpResponse) {
+  return useFastMutation<HttpResponse>({
+    mutationKey: ['resend_http_response', response.id],
+    mutationFn: () =>
+      invokeCmd('cmd_resend_http_response', {
+        responseId: response.id,
+        environmentId: getActiveEnvironment()?.id,
+      }),
+  });
+}
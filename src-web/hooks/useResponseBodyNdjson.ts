@@ -0,0 +1,12 @@
+import { useQuery } from '@tanstack/react-query';
+import type { HttpResponse } from '@yaakapp-internal/models';
+import type { NdjsonEvent } from '../lib/responseBody';
+import { getResponseBodyNdjson } from '../lib/responseBody';
+
+export function useResponseBodyNdjson(response: HttpResponse) {
+  return useQuery<NdjsonEvent[]>({
+    placeholderData: (prev) => prev, // Keep previous data on refetch
+    queryKey: ['response-body-ndjson', response.id, response.contentLength],
+    queryFn: () => getResponseBodyNdjson(response),
+  });
+}
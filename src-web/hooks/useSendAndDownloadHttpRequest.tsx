@@ -0,0 +1,44 @@
+import { save } from '@tauri-apps/plugin-dialog';
+import type { HttpResponse } from '@yaakapp-internal/models';
+import { getModel } from '@yaakapp-internal/models';
+import slugify from 'slugify';
+import { InlineCode } from '../components/core/InlineCode';
+import { invokeCmd } from '../lib/tauri';
+import { showToast } from '../lib/toast';
+import { getActiveCookieJar } from './useActiveCookieJar';
+import { getActiveEnvironment } from './useActiveEnvironment';
+import { useFastMutation } from './useFastMutation';
+
+/** Send a request and write the response body straight to a chosen file, for big downloads */
+export function useSendAndDownloadHttpRequest() {
+  return useFastMutation<HttpResponse | null, string, string>({
+    mutationKey: ['send_and_download_request'],
+    mutationFn: async (id) => {
+      const request = getModel('http_request', id);
+      if (request == null) return null;
+
+      const downloadPath = await save({
+        defaultPath: slugify(request.name || 'download', { lower: true }),
+        title: 'Download Response To',
+      });
+      if (downloadPath == null) return null;
+
+      const response = await invokeCmd<HttpResponse>('cmd_send_http_request', {
+        request,
+        environmentId: getActiveEnvironment()?.id,
+        cookieJarId: getActiveCookieJar()?.id,
+        downloadPath,
+      });
+      if (response.error == null) {
+        showToast({
+          message: (
+            <>
+              Response downloaded to <InlineCode>{downloadPath}</InlineCode>
+            </>
+          ),
+        });
+      }
+      return response;
+    },
+  });
+}
@@ -4,12 +4,34 @@ import type { ServerSentEvent } from '@yaakapp-internal/sse';
 import { getCharsetFromContentType } from './model_util';
 import { invokeCmd } from './tauri';
 
+/** Line of a newline-delimited JSON body */
+export interface NdjsonEvent {
+  responseId: string;
+  line: number;
+  data: string;
+  error: string | null;
+}
+
 export async function getResponseBodyText(response: HttpResponse): Promise<string | null> {
   if (!response.bodyPath) return null;
 
-  const bytes = await readFile(response.bodyPath);
   const charset = getCharsetFromContentType(response.headers);
 
+  // Small bodies are kept inline on the response, so there's no need to read the file. The
+  // preview is decoded lossily, so only use it if it re-encodes to the full body.
+  const preview = response.bodyPreview;
+  if (
+    preview != null &&
+    response.state === 'closed' &&
+    (charset == null || /^utf-?8$/i.test(charset)) &&
+    new TextEncoder().encode(preview).length === response.contentLength &&
+    !preview.includes('\uFFFD')
+  ) {
+    return preview;
+  }
+
+  const bytes = await readFile(response.bodyPath);
+
   try {
     return new TextDecoder(charset ?? 'utf-8', { fatal: true }).decode(bytes);
     // eslint-disable-next-line @typescript-eslint/no-unused-vars
@@ -32,3 +54,11 @@ export async function getResponseBodyEventSource(
     filePath: response.bodyPath,
   });
 }
+
+export async function getResponseBodyNdjson(response: HttpResponse): Promise<NdjsonEvent[]> {
+  if (!response.bodyPath) return [];
+  return invokeCmd<NdjsonEvent[]>('cmd_get_ndjson_events', {
+    responseId: response.id,
+    filePath: response.bodyPath,
+  });
+}
@@ -9,33 +9,46 @@ type TauriCmd =
   | 'cmd_curl_to_request'
   | 'cmd_delete_all_grpc_connections'
   | 'cmd_delete_all_http_responses'
+  | 'cmd_delete_old_http_responses'
   | 'cmd_delete_send_history'
   | 'cmd_dismiss_notification'
+  | 'cmd_dry_run_http_request'
   | 'cmd_export_data'
   | 'cmd_filter_response'
   | 'cmd_format_json'
   | 'cmd_get_http_authentication_config'
   | 'cmd_get_http_authentication_summaries'
+  | 'cmd_get_ndjson_events'
   | 'cmd_get_sse_events'
   | 'cmd_get_workspace_meta'
   | 'cmd_grpc_go'
   | 'cmd_grpc_reflect'
   | 'cmd_http_request_actions'
+  | 'cmd_http_request_openapi'
   | 'cmd_import_data'
   | 'cmd_install_plugin'
   | 'cmd_metadata'
   | 'cmd_new_child_window'
   | 'cmd_new_main_window'
   | 'cmd_parse_template'
+  | 'cmd_ping_url'
   | 'cmd_plugin_info'
+  | 'cmd_preview_json_merge_patch'
   | 'cmd_reload_plugins'
   | 'cmd_render_template'
+  | 'cmd_resend_http_response'
+  | 'cmd_run_chain'
+  | 'cmd_run_with_data_file'
+  | 'cmd_save_http_trace'
   | 'cmd_save_response'
   | 'cmd_send_ephemeral_request'
   | 'cmd_send_http_request'
+  | 'cmd_send_http_request_to_file'
+  | 'cmd_send_to_environments'
   | 'cmd_template_functions'
   | 'cmd_template_tokens_to_string'
-  | 'cmd_uninstall_plugin';
+  | 'cmd_uninstall_plugin'
+  | 'cmd_variable_usage';
 
 export async function invokeCmd<T>(cmd: TauriCmd, args?: InvokeArgs): Promise<T> {
   // console.log('RUN COMMAND', cmd, args);